@@ -1,14 +1,11 @@
 use std::env;
 
-use rdiff::Rdiff;
-
-pub mod rdiff;
+use rolling_hash_diff::rdiff::Rdiff;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let rdiff_main_result = Rdiff::main_rdiff(args);
-    match rdiff_main_result {
-        Err(e) => eprintln!("{}", e.to_string()),
-        _ => {}
+    if let Err(e) = rdiff_main_result {
+        eprintln!("{}", e)
     }
 }
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rdiff::{
+    constants::MAX_BLOCK_SIZE,
+    error::{messages::INVALID_CHUNK_SIZE, RollingHashError},
+    io::RdiffFile,
+};
+
+use super::{RdiffChunk, RdiffChunkIterator};
+
+// Gear hash table, 256 pseudo random numbers from a fixed splitmix64 sequence.
+// Chunk boundaries depend on it, so it must never change
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0_u64; 256];
+    let mut state: u64 = 0;
+    let mut index = 0;
+    while index < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[index] = value ^ (value >> 31);
+        index += 1;
+    }
+    table
+}
+
+// FastCDC chunk sizes, chunks are at least min size and at most max size long,
+// their average size is close to avg size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FastCdcParameters {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+}
+
+impl FastCdcParameters {
+    pub fn new(
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+    ) -> Result<FastCdcParameters, RollingHashError> {
+        let fastcdc_parameters = FastCdcParameters {
+            min_size,
+            avg_size,
+            max_size,
+        };
+        fastcdc_parameters.validate()?;
+        Ok(fastcdc_parameters)
+    }
+
+    // Min size is a quarter of avg size and max size is four times avg size
+    pub fn new_with_avg_size(avg_size: usize) -> Result<FastCdcParameters, RollingHashError> {
+        FastCdcParameters::new(avg_size / 4, avg_size, avg_size.saturating_mul(4))
+    }
+
+    pub fn get_min_size(&self) -> usize {
+        self.min_size
+    }
+
+    pub fn get_avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    pub fn get_max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn validate(&self) -> Result<(), RollingHashError> {
+        if self.min_size == 0
+            || self.min_size > self.avg_size
+            || self.avg_size > self.max_size
+            || self.max_size > MAX_BLOCK_SIZE
+        {
+            return Err(RollingHashError::new(INVALID_CHUNK_SIZE));
+        }
+        Ok(())
+    }
+
+    fn find_cut(&self, data: &[u8]) -> usize {
+        // Chunks shorter than min size are only found at the end of the data
+        if data.len() <= self.min_size {
+            return data.len();
+        }
+        let end = data.len().min(self.max_size);
+        let normal_size = self.avg_size.min(end);
+        // Normalized chunking, a harder mask before avg size and an easier one after it
+        // keep chunk sizes close to avg size. Mask bits are the gear hash high bits,
+        // which depend on the most bytes
+        let bits = self.avg_size.ilog2();
+        let hard_mask = !0_u64 << (63 - bits);
+        let easy_mask = !0_u64 << (65 - bits.max(1)).min(63);
+        let mut fingerprint: u64 = 0;
+        for (position, byte) in data.iter().enumerate().take(end).skip(self.min_size) {
+            fingerprint = (fingerprint << 1).wrapping_add(GEAR[*byte as usize]);
+            let mask = if position < normal_size {
+                hard_mask
+            } else {
+                easy_mask
+            };
+            if fingerprint & mask == 0 {
+                return position + 1;
+            }
+        }
+        end
+    }
+}
+
+// Content defined chunk iterator, chunk boundaries are found with the FastCDC
+// gear hash so an insertion only moves the boundaries around it
+#[derive(Debug, PartialEq)]
+pub struct FastCdcChunkIterator<'a> {
+    fastcdc_parameters: FastCdcParameters,
+    buffer: Vec<u8>,
+    rdiff_file: RdiffFile<'a>,
+}
+
+impl<'a> FastCdcChunkIterator<'a> {
+    pub fn new(
+        fastcdc_parameters: FastCdcParameters,
+        rdiff_file: RdiffFile<'a>,
+    ) -> FastCdcChunkIterator<'a> {
+        let buffer: Vec<u8> = Vec::new();
+        FastCdcChunkIterator {
+            fastcdc_parameters,
+            buffer,
+            rdiff_file,
+        }
+    }
+}
+
+impl RdiffChunkIterator for FastCdcChunkIterator<'_> {
+    // Chunk size of content defined chunks is their average size
+    fn get_chunk_size(&self) -> usize {
+        self.fastcdc_parameters.get_avg_size()
+    }
+
+    fn get_fastcdc_parameters(&self) -> Option<&FastCdcParameters> {
+        Some(&self.fastcdc_parameters)
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<RdiffChunk>, RollingHashError> {
+        // Read blocks from file until memory buffer contains a max size chunk
+        // or there are no more blocks, so the cut does not depend on block boundaries
+        while self.buffer.len() < self.fastcdc_parameters.get_max_size() {
+            match self
+                .rdiff_file
+                .read_block()
+                .map_err(RollingHashError::from)?
+            {
+                Some((size, next_block)) => self.buffer.extend_from_slice(&next_block[..size]),
+                None => break,
+            }
+        }
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        // Get next chunk and remove it from buffer
+        let chunk_size = self.fastcdc_parameters.find_cut(&self.buffer);
+        let rdiff_chunk: RdiffChunk = self.buffer.drain(..chunk_size).collect();
+        Ok(Some(rdiff_chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests;
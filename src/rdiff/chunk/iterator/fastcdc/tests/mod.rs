@@ -0,0 +1,150 @@
+use std::fs;
+
+use crate::rdiff::{
+    chunk::{iterator::RdiffChunkIterator, RdiffChunk},
+    constants::MAX_BLOCK_SIZE,
+    error::{messages::INVALID_CHUNK_SIZE, RollingHashError},
+    io::RdiffFile,
+    util::now_as_millis,
+};
+
+use super::{FastCdcChunkIterator, FastCdcParameters};
+
+// Pseudo random file content, the same for every run
+fn get_test_data(size: usize) -> Vec<u8> {
+    let mut state: u32 = 12345;
+    (0..size)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as u8
+        })
+        .collect()
+}
+
+fn get_chunks(
+    file_name: &str,
+    data: &[u8],
+    fastcdc_parameters: FastCdcParameters,
+) -> Vec<RdiffChunk> {
+    fs::write(file_name, data).unwrap();
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = FastCdcChunkIterator::new(fastcdc_parameters, rdiff_file);
+    let mut chunks: Vec<RdiffChunk> = Vec::new();
+    while let Some(rdiff_chunk) = iterator.next_chunk().unwrap() {
+        chunks.push(rdiff_chunk);
+    }
+    fs::remove_file(file_name).unwrap();
+    chunks
+}
+
+#[test]
+fn test_fastcdc_parameters_new_case1() {
+    let fastcdc_parameters = FastCdcParameters::new(256, 1024, 4096).unwrap();
+    assert_eq!(fastcdc_parameters.get_min_size(), 256);
+    assert_eq!(fastcdc_parameters.get_avg_size(), 1024);
+    assert_eq!(fastcdc_parameters.get_max_size(), 4096);
+    let computed_parameters = FastCdcParameters::new_with_avg_size(1024).unwrap();
+    assert_eq!(computed_parameters, fastcdc_parameters);
+}
+
+#[test]
+fn test_fastcdc_parameters_new_error_case1() {
+    let expected_error = RollingHashError::new(INVALID_CHUNK_SIZE);
+    assert_eq!(
+        FastCdcParameters::new(0, 1024, 4096).unwrap_err(),
+        expected_error
+    );
+    assert_eq!(
+        FastCdcParameters::new(2048, 1024, 4096).unwrap_err(),
+        expected_error
+    );
+    assert_eq!(
+        FastCdcParameters::new(256, 1024, 512).unwrap_err(),
+        expected_error
+    );
+    assert_eq!(
+        FastCdcParameters::new(256, 1024, MAX_BLOCK_SIZE + 1).unwrap_err(),
+        expected_error
+    );
+    assert_eq!(
+        FastCdcParameters::new_with_avg_size(2).unwrap_err(),
+        expected_error
+    );
+    assert_eq!(
+        FastCdcParameters::new_with_avg_size(MAX_BLOCK_SIZE).unwrap_err(),
+        expected_error
+    );
+}
+
+#[test]
+fn test_fastcdc_chunk_iterator_next_chunk_case1() {
+    // Chunks are within min and max size and make up the file
+    let file_name = format!(
+        "resources/test_fastcdc_chunk_iterator_next_chunk_case1.{}.txt",
+        now_as_millis()
+    );
+    let data = get_test_data(256 * 1024);
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(4096).unwrap();
+    let chunks = get_chunks(file_name.as_str(), &data, fastcdc_parameters);
+    let (last_chunk, chunks_but_last) = chunks.split_last().unwrap();
+    assert!(!last_chunk.is_empty() && last_chunk.len() <= 4 * 4096);
+    for rdiff_chunk in chunks_but_last {
+        assert!(rdiff_chunk.len() >= 1024 && rdiff_chunk.len() <= 4 * 4096);
+    }
+    assert_eq!(chunks.concat(), data);
+
+    // Average chunk size is close to avg size
+    let avg_size = data.len() / chunks.len();
+    assert!(avg_size > 2048 && avg_size < 8192);
+}
+
+#[test]
+fn test_fastcdc_chunk_iterator_next_chunk_case2() {
+    // Boundaries of repeated content only depend on the gear table and must not change
+    let file_name = format!(
+        "resources/test_fastcdc_chunk_iterator_next_chunk_case2.{}.txt",
+        now_as_millis()
+    );
+    let fastcdc_parameters = FastCdcParameters::new(4, 8, 16).unwrap();
+    let data = vec![b'a'; 40];
+    let chunks = get_chunks(file_name.as_str(), &data, fastcdc_parameters);
+    let chunk_sizes: Vec<usize> = chunks.iter().map(|chunk| chunk.len()).collect();
+    assert_eq!(chunk_sizes, vec![13, 13, 13, 1]);
+
+    // Empty file has no chunks
+    let chunks = get_chunks(file_name.as_str(), &[], fastcdc_parameters);
+    assert!(chunks.is_empty());
+}
+
+#[test]
+fn test_fastcdc_chunk_iterator_next_chunk_case3() {
+    // Boundaries resynchronize after an insertion, so most chunks are kept
+    let file_name = format!(
+        "resources/test_fastcdc_chunk_iterator_next_chunk_case3.{}.txt",
+        now_as_millis()
+    );
+    let data = get_test_data(256 * 1024);
+    let mut new_data = data.clone();
+    new_data.insert(100, b'x');
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(4096).unwrap();
+    let chunks = get_chunks(file_name.as_str(), &data, fastcdc_parameters);
+    let new_chunks = get_chunks(file_name.as_str(), &new_data, fastcdc_parameters);
+    let kept_chunks = new_chunks
+        .iter()
+        .filter(|chunk| chunks.contains(chunk))
+        .count();
+    assert!(kept_chunks + 2 >= chunks.len());
+
+    // Chunks do not depend on the iterator
+    let computed_chunks = get_chunks(file_name.as_str(), &data, fastcdc_parameters);
+    assert_eq!(computed_chunks, chunks);
+}
+
+#[test]
+fn test_fastcdc_chunk_iterator_chunk_size_case1() {
+    let rdiff_file = RdiffFile::new("resources/buffered_rdiff_chunk_iterator_file1.txt").unwrap();
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(64).unwrap();
+    let iterator = FastCdcChunkIterator::new(fastcdc_parameters, rdiff_file);
+    assert_eq!(iterator.get_chunk_size(), 64);
+    assert_eq!(iterator.get_fastcdc_parameters(), Some(&fastcdc_parameters));
+}
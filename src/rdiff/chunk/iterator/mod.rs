@@ -1,28 +1,37 @@
 use std::cmp;
 
 use crate::rdiff::{
-    constants::BLOCK_SIZE,
+    constants::{BLOCK_SIZE, MAX_BLOCK_SIZE},
     error::{messages::INVALID_CHUNK_SIZE, RollingHashError},
     io::RdiffFile,
 };
 
+use self::fastcdc::FastCdcParameters;
+
 use super::RdiffChunk;
 
+pub mod fastcdc;
+
 pub trait RdiffChunkIterator {
     fn next_chunk(&mut self) -> Result<Option<RdiffChunk>, RollingHashError>;
     fn get_chunk_size(&self) -> usize;
+    // Content defined chunk iterators return the parameters they cut chunks with,
+    // fixed size chunk iterators return none
+    fn get_fastcdc_parameters(&self) -> Option<&FastCdcParameters> {
+        None
+    }
 }
 
 #[derive(Debug, PartialEq)]
-pub struct BufferedRdiffChunkIterator {
+pub struct BufferedRdiffChunkIterator<'a> {
     chunk_size: usize,
     buffer: Vec<u8>,
-    rdiff_file: RdiffFile,
+    rdiff_file: RdiffFile<'a>,
 }
 
-impl BufferedRdiffChunkIterator {
-    pub fn new(rdiff_file: RdiffFile) -> BufferedRdiffChunkIterator {
-        let chunk_size = BufferedRdiffChunkIterator::compute_chunk_size(&rdiff_file);
+impl<'a> BufferedRdiffChunkIterator<'a> {
+    pub fn new(rdiff_file: RdiffFile<'a>) -> BufferedRdiffChunkIterator<'a> {
+        let chunk_size = BufferedRdiffChunkIterator::compute_chunk_size(rdiff_file.size());
         let buffer: Vec<u8> = Vec::new();
         BufferedRdiffChunkIterator {
             chunk_size,
@@ -33,8 +42,8 @@ impl BufferedRdiffChunkIterator {
 
     pub fn new_with_chunk_size(
         chunk_size: usize,
-        rdiff_file: RdiffFile,
-    ) -> Result<BufferedRdiffChunkIterator, RollingHashError> {
+        rdiff_file: RdiffFile<'a>,
+    ) -> Result<BufferedRdiffChunkIterator<'a>, RollingHashError> {
         BufferedRdiffChunkIterator::validate_chunk_size(chunk_size)?;
         let buffer: Vec<u8> = Vec::new();
         Ok(BufferedRdiffChunkIterator {
@@ -44,20 +53,41 @@ impl BufferedRdiffChunkIterator {
         })
     }
 
-    fn compute_chunk_size(rdiff_file: &RdiffFile) -> usize {
-        if rdiff_file.size() > 1 {
-            if rdiff_file.size() > BLOCK_SIZE {
+    // Chunk size used when none is given, computed from file size, if known
+    pub(crate) fn compute_chunk_size(size: Option<usize>) -> usize {
+        match size {
+            // Use block size when file size is unknown, as for streams
+            None => BLOCK_SIZE,
+            Some(size) if size > 1 => {
+                if size > BLOCK_SIZE {
+                    // Use rsync rule, square root of file size rounded down to a
+                    // multiple of 8, bounded by block size and max block size
+                    let chunk_size = ((size as f64).sqrt() as usize) & !7;
+                    chunk_size.clamp(BLOCK_SIZE, MAX_BLOCK_SIZE)
+                } else {
+                    (((size as f64) / 2.0).round()) as usize
+                }
+            }
+            Some(_) => 1,
+        }
+    }
+
+    // Chunk size of signatures created before chunk size was computed from the
+    // square root of file size, delta files without header were created with it
+    pub(crate) fn compute_legacy_chunk_size(size: usize) -> usize {
+        if size > 1 {
+            if size > BLOCK_SIZE {
                 BLOCK_SIZE
             } else {
-                (((rdiff_file.size() as f64) / 2.0).round()) as usize
+                (((size as f64) / 2.0).round()) as usize
             }
         } else {
             1
         }
     }
 
-    fn validate_chunk_size(chunk_size: usize) -> Result<(), RollingHashError> {
-        if chunk_size > BLOCK_SIZE {
+    pub(crate) fn validate_chunk_size(chunk_size: usize) -> Result<(), RollingHashError> {
+        if chunk_size == 0 || chunk_size > MAX_BLOCK_SIZE {
             let rdiff_error = RollingHashError::new(INVALID_CHUNK_SIZE);
             return Err(rdiff_error);
         }
@@ -65,7 +95,7 @@ impl BufferedRdiffChunkIterator {
     }
 }
 
-impl RdiffChunkIterator for BufferedRdiffChunkIterator {
+impl RdiffChunkIterator for BufferedRdiffChunkIterator<'_> {
     fn get_chunk_size(&self) -> usize {
         self.chunk_size
     }
@@ -81,19 +111,24 @@ impl RdiffChunkIterator for BufferedRdiffChunkIterator {
             // Return rdiff chunk
             Ok(Some(rdiff_chunk))
         } else {
-            // If memory buffer does not contain a chunk, get one from file,
-            // if there is one
-            if let Some((size, next_block)) = self
-                .rdiff_file
-                .read_block()
-                .or_else(|e| Err(RollingHashError::from(e)))?
-            {
-                // Update memory buffer with chunk from file
-                let next_block_data = &next_block[..size];
-                self.buffer.extend_from_slice(next_block_data);
+            // If memory buffer does not contain a chunk, read blocks from file
+            // until there is one or there are no more blocks
+            while self.buffer.len() < self.chunk_size {
+                match self
+                    .rdiff_file
+                    .read_block()
+                    .map_err(RollingHashError::from)?
+                {
+                    Some((size, next_block)) => {
+                        // Update memory buffer with block from file
+                        let next_block_data = &next_block[..size];
+                        self.buffer.extend_from_slice(next_block_data);
+                    }
+                    None => break,
+                }
             }
             // If buffer contains data
-            if self.buffer.len() > 0 {
+            if !self.buffer.is_empty() {
                 // Get a normal chunk or the last chunk and update memory buffer
                 let next_chunk_size = cmp::min(self.chunk_size, self.buffer.len());
                 let next_chunk_iter = self.buffer.drain(..next_chunk_size);
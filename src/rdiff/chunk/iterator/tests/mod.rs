@@ -1,4 +1,11 @@
-use crate::rdiff::{constants::BLOCK_SIZE, io::RdiffFile};
+use std::fs;
+
+use crate::rdiff::{
+    constants::{BLOCK_SIZE, MAX_BLOCK_SIZE, READ_BLOCK_SIZE},
+    error::{messages::INVALID_CHUNK_SIZE, RollingHashError},
+    io::RdiffFile,
+    util::now_as_millis,
+};
 
 use super::{BufferedRdiffChunkIterator, RdiffChunkIterator};
 
@@ -6,14 +13,14 @@ use super::{BufferedRdiffChunkIterator, RdiffChunkIterator};
 fn test_rdiff_chunk_iterator_buffered_new_case1() {
     let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
     let rdiff_file = RdiffFile::new(file_name).unwrap();
-    let iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let _iterator = BufferedRdiffChunkIterator::new(rdiff_file);
 }
 
 #[test]
 fn test_rdiff_chunk_iterator_buffered_chunk_size_case1() {
     let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
     let rdiff_file = RdiffFile::new(file_name).unwrap();
-    assert_eq!(rdiff_file.size(), 3);
+    assert_eq!(rdiff_file.size(), Some(3));
     let iterator = BufferedRdiffChunkIterator::new(rdiff_file);
     assert_eq!(iterator.get_chunk_size(), 2);
 }
@@ -22,11 +29,37 @@ fn test_rdiff_chunk_iterator_buffered_chunk_size_case1() {
 fn test_rdiff_chunk_iterator_buffered_chunk_size_case2() {
     let file_name = "resources/buffered_rdiff_chunk_iterator_file2.txt";
     let rdiff_file = RdiffFile::new(file_name).unwrap();
-    assert!(rdiff_file.size() > BLOCK_SIZE);
+    assert!(rdiff_file.size().unwrap() > BLOCK_SIZE);
+    let iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    assert_eq!(iterator.get_chunk_size(), BLOCK_SIZE);
+}
+
+#[test]
+fn test_rdiff_chunk_iterator_buffered_chunk_size_unsized_case1() {
+    // File size of streams is unknown, block size is used
+    let data = fs::read("resources/buffered_rdiff_chunk_iterator_file2.txt").unwrap();
+    let rdiff_file = RdiffFile::new_with_unsized_reader("stream", data.as_slice());
+    assert_eq!(rdiff_file.size(), None);
     let iterator = BufferedRdiffChunkIterator::new(rdiff_file);
     assert_eq!(iterator.get_chunk_size(), BLOCK_SIZE);
 }
 
+#[test]
+fn test_rdiff_chunk_iterator_buffered_legacy_chunk_size_case1() {
+    // Legacy chunk size is block size for files bigger than block size
+    for (size, chunk_size) in [(0, 1), (1, 1), (3, 2), (BLOCK_SIZE, BLOCK_SIZE / 2)] {
+        assert_eq!(
+            BufferedRdiffChunkIterator::compute_legacy_chunk_size(size),
+            chunk_size
+        );
+    }
+    let size = 100 * 1024;
+    assert_eq!(
+        BufferedRdiffChunkIterator::compute_legacy_chunk_size(size),
+        BLOCK_SIZE
+    );
+}
+
 #[test]
 fn test_rdiff_chunk_iterator_buffered_get_rdiff_chunk_case1() {
     let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
@@ -36,7 +69,7 @@ fn test_rdiff_chunk_iterator_buffered_get_rdiff_chunk_case1() {
     loop {
         let result = iterator.next_chunk().unwrap();
         match result {
-            Some(rdiff_chunk) => {
+            Some(_rdiff_chunk) => {
                 number_of_chunks += 1;
             }
             None => break,
@@ -54,7 +87,7 @@ fn test_rdiff_chunk_iterator_buffered_get_rdiff_chunk_case2() {
     loop {
         let result = iterator.next_chunk().unwrap();
         match result {
-            Some(rdiff_chunk) => {
+            Some(_rdiff_chunk) => {
                 number_of_chunks += 1;
             }
             None => break,
@@ -72,7 +105,7 @@ fn test_rdiff_chunk_iterator_buffered_get_rdiff_chunk_case3() {
     loop {
         let result = iterator.next_chunk().unwrap();
         match result {
-            Some(rdiff_chunk) => {
+            Some(_rdiff_chunk) => {
                 number_of_chunks += 1;
             }
             None => break,
@@ -90,7 +123,7 @@ fn test_rdiff_chunk_iterator_buffered_get_rdiff_chunk_case4() {
     loop {
         let result = iterator.next_chunk().unwrap();
         match result {
-            Some(rdiff_chunk) => {
+            Some(_rdiff_chunk) => {
                 number_of_chunks += 1;
             }
             None => break,
@@ -127,10 +160,62 @@ fn test_rdiff_chunk_iterator_buffered_with_chunk_size_next_chunk_case1() {
     loop {
         let result = iterator.next_chunk().unwrap();
         match result {
-            Some(rdiff_chunk) => {
+            Some(_rdiff_chunk) => {
                 number_of_chunks += 1;
             }
             None => break,
         }
     }
+    assert_eq!(number_of_chunks, 2);
+}
+
+#[test]
+fn test_rdiff_chunk_iterator_buffered_chunk_size_case3() {
+    // Create a file big enough to use square root of file size as chunk size
+    let file_name = format!(
+        "resources/test_rdiff_chunk_iterator_chunk_size_case3.{}.txt",
+        now_as_millis()
+    );
+    let file_size: usize = 1024 * 1024;
+    fs::write(file_name.as_str(), vec![b'a'; file_size]).unwrap();
+
+    let rdiff_file = RdiffFile::new(file_name.as_str()).unwrap();
+    let iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    assert_eq!(iterator.get_chunk_size(), 1024);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_chunk_iterator_buffered_with_chunk_size_next_chunk_case2() {
+    // Create a file with chunks bigger than the read block size
+    let file_name = format!(
+        "resources/test_rdiff_chunk_iterator_next_chunk_case2.{}.txt",
+        now_as_millis()
+    );
+    let chunk_size = READ_BLOCK_SIZE + READ_BLOCK_SIZE / 2;
+    let file_size = 2 * chunk_size + 10;
+    fs::write(file_name.as_str(), vec![b'a'; file_size]).unwrap();
+
+    let rdiff_file = RdiffFile::new(file_name.as_str()).unwrap();
+    let mut iterator =
+        BufferedRdiffChunkIterator::new_with_chunk_size(chunk_size, rdiff_file).unwrap();
+    let mut chunk_sizes: Vec<usize> = Vec::new();
+    while let Some(rdiff_chunk) = iterator.next_chunk().unwrap() {
+        chunk_sizes.push(rdiff_chunk.len());
+    }
+    assert_eq!(chunk_sizes, vec![chunk_size, chunk_size, 10]);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_chunk_iterator_buffered_new_with_chunk_size_error_case1() {
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let error = BufferedRdiffChunkIterator::new_with_chunk_size(MAX_BLOCK_SIZE + 1, rdiff_file)
+        .unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_SIZE));
 }
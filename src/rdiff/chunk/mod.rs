@@ -8,9 +8,11 @@ pub type RdiffDigest = Vec<u8>;
 
 pub type RdiffChunk = Vec<u8>;
 
+// Chunks record their length, content defined chunks do not have a fixed size
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct RdiffChunkDigest {
     pub index: u32,
+    pub length: usize,
     pub digest: RdiffDigest,
 }
 
@@ -19,6 +21,12 @@ pub struct RdiffChunkTable {
     pub(crate) chunk_table: HashMap<RdiffChecksum, Vec<RdiffChunkDigest>>,
 }
 
+impl Default for RdiffChunkTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RdiffChunkTable {
     pub fn new() -> RdiffChunkTable {
         let chunk_table: HashMap<RdiffChecksum, Vec<RdiffChunkDigest>> = HashMap::new();
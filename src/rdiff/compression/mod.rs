@@ -0,0 +1,20 @@
+use crate::rdiff::error::{messages::UNKNOWN_COMPRESSION, RollingHashError};
+
+use self::rdiff_deflate::{RdiffDeflate, RDIFF_DEFLATE_ID};
+
+pub mod rdiff_deflate;
+
+pub type CompressionPtr = Box<dyn Compression>;
+
+pub trait Compression {
+    fn get_id(&self) -> u32;
+    fn compress(&self, data: &[u8]) -> Vec<u8>;
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, RollingHashError>;
+}
+
+pub fn new_compression_ptr(id: u32) -> Result<CompressionPtr, RollingHashError> {
+    match id {
+        RDIFF_DEFLATE_ID => Ok(RdiffDeflate::new_ptr()),
+        _ => Err(RollingHashError::new(UNKNOWN_COMPRESSION)),
+    }
+}
@@ -0,0 +1,49 @@
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+use crate::rdiff::{
+    constants::MAX_DIFF_SIZE,
+    error::{messages::INVALID_COMPRESSED_DATA, RollingHashError},
+};
+
+use super::Compression;
+
+pub const RDIFF_DEFLATE_ID: u32 = 1;
+const RDIFF_DEFLATE_LEVEL: u8 = 6;
+
+pub type RdiffDeflatePtr = Box<RdiffDeflate>;
+pub struct RdiffDeflate;
+
+impl Default for RdiffDeflate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffDeflate {
+    pub fn new() -> RdiffDeflate {
+        RdiffDeflate
+    }
+    pub fn new_ptr() -> RdiffDeflatePtr {
+        Box::new(RdiffDeflate::new())
+    }
+}
+
+impl Compression for RdiffDeflate {
+    fn get_id(&self) -> u32 {
+        RDIFF_DEFLATE_ID
+    }
+
+    fn compress(&self, data: &[u8]) -> Vec<u8> {
+        compress_to_vec(data, RDIFF_DEFLATE_LEVEL)
+    }
+
+    // Compressed differences are at most MAX_DIFF_SIZE bytes long, so data
+    // inflating past it is rejected before it is all allocated
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, RollingHashError> {
+        decompress_to_vec_with_limit(data, MAX_DIFF_SIZE)
+            .map_err(|_| RollingHashError::new(INVALID_COMPRESSED_DATA))
+    }
+}
+
+#[cfg(test)]
+mod tests;
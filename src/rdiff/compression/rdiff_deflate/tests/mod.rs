@@ -0,0 +1,56 @@
+use std::fs;
+
+use crate::rdiff::{
+    compression::{new_compression_ptr, Compression},
+    constants::MAX_DIFF_SIZE,
+    error::{
+        messages::{INVALID_COMPRESSED_DATA, UNKNOWN_COMPRESSION},
+        RollingHashError,
+    },
+};
+
+use super::{RdiffDeflate, RDIFF_DEFLATE_ID};
+
+#[test]
+fn test_rdiff_deflate_compress_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+    let compression = RdiffDeflate::new();
+    let compressed_data = compression.compress(&data);
+    assert!(compressed_data.len() < data.len());
+    assert_eq!(compression.decompress(&compressed_data).unwrap(), data);
+
+    // Empty data
+    let compressed_data = compression.compress(&[]);
+    assert_eq!(compression.decompress(&compressed_data).unwrap(), vec![]);
+}
+
+#[test]
+fn test_rdiff_deflate_decompress_error_case1() {
+    let compression = RdiffDeflate::new();
+    let error = compression.decompress(&[0xff; 8]).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_COMPRESSED_DATA));
+}
+
+#[test]
+fn test_rdiff_deflate_decompress_limit_case1() {
+    let compression = RdiffDeflate::new();
+    let compressed_data = compression.compress(&vec![0; MAX_DIFF_SIZE]);
+    assert_eq!(
+        compression.decompress(&compressed_data).unwrap(),
+        vec![0; MAX_DIFF_SIZE]
+    );
+
+    // Small data inflating past the differences size limit
+    let compressed_data = compression.compress(&vec![0; 64 * MAX_DIFF_SIZE]);
+    assert!(compressed_data.len() < MAX_DIFF_SIZE);
+    let error = compression.decompress(&compressed_data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_COMPRESSED_DATA));
+}
+
+#[test]
+fn test_new_compression_ptr_case1() {
+    let compression_ptr = new_compression_ptr(RDIFF_DEFLATE_ID).unwrap();
+    assert_eq!(compression_ptr.get_id(), RDIFF_DEFLATE_ID);
+    let error = new_compression_ptr(0).err().unwrap();
+    assert_eq!(error, RollingHashError::new(UNKNOWN_COMPRESSION));
+}
@@ -1,3 +1,59 @@
 pub const BLOCK_SIZE: usize = 128;
+pub const MAX_BLOCK_SIZE: usize = 128 * 1024;
+pub const READ_BLOCK_SIZE: usize = 8 * 1024;
+pub const WINDOW_SIZE: usize = 64 * 1024;
+pub const MAX_DIFF_SIZE: usize = 64 * 1024;
+pub const IN_PLACE_BLOCK_SIZE: usize = 1024 * 1024;
 pub const SIGNATURE: &str = "signature";
 pub const DELTA: &str = "delta";
+pub const PATCH: &str = "patch";
+pub const COMPOSE: &str = "compose";
+pub const STDIN_FILE_NAME: &str = "-";
+pub const BLOCK_SIZE_OPTION: &str = "--block-size";
+pub const COMPRESS_OPTION: &str = "--compress";
+pub const IN_PLACE_OPTION: &str = "--in-place";
+pub const ROLLBACK_OPTION: &str = "--rollback";
+pub const WITHOUT_BASIS_OPTION: &str = "--without-basis";
+pub const FORMAT_OPTION: &str = "--format";
+pub const RDIFF_FORMAT: &str = "rdiff";
+pub const LIBRSYNC_FORMAT: &str = "librsync";
+pub const VCDIFF_FORMAT: &str = "vcdiff";
+pub const CHUNKING_OPTION: &str = "--chunking";
+pub const FIXED_CHUNKING: &str = "fixed";
+pub const FASTCDC_CHUNKING: &str = "fastcdc";
+pub const FASTCDC_AVG_SIZE: usize = 8 * 1024;
+pub const WEAK_HASH_OPTION: &str = "--weak-hash";
+pub const ADLER32_HASH: &str = "adler32";
+pub const ROLLSUM_HASH: &str = "rollsum";
+pub const RABINKARP_HASH: &str = "rabinkarp";
+pub const STRONG_HASH_OPTION: &str = "--strong-hash";
+pub const SHA1_HASH: &str = "sha1";
+pub const MD4_HASH: &str = "md4";
+pub const BLAKE2_HASH: &str = "blake2";
+pub const BLAKE3_HASH: &str = "blake3";
+pub const STRONG_LEN_OPTION: &str = "--strong-len";
+pub const SIGNATURE_MAGIC: u32 = 0x5244_5347;
+pub const SIGNATURE_VERSION: u32 = SIGNATURE_VERSION_3;
+pub const SIGNATURE_VERSION_3: u32 = 3;
+pub const SIGNATURE_VERSION_2: u32 = 2;
+pub const SIGNATURE_VERSION_1: u32 = 1;
+pub const DELTA_MAGIC: u32 = 0x5244_4C54;
+pub const DELTA_VERSION: u32 = DELTA_VERSION_6;
+pub const DELTA_VERSION_6: u32 = 6;
+pub const DELTA_VERSION_5: u32 = 5;
+pub const DELTA_VERSION_4: u32 = 4;
+pub const DELTA_VERSION_3: u32 = 3;
+pub const DELTA_VERSION_2: u32 = 2;
+pub const DELTA_VERSION_1: u32 = 1;
+pub const DELTA_LEGACY_VERSION: u32 = 0;
+pub const JOURNAL_MAGIC: u32 = 0x5244_4A4E;
+pub const JOURNAL_VERSION: u32 = 1;
+pub const JOURNAL_FILE_SUFFIX: &str = ".journal";
+pub const LIBRSYNC_MD4_SIG_MAGIC: u32 = 0x7273_0136;
+pub const LIBRSYNC_BLAKE2_SIG_MAGIC: u32 = 0x7273_0137;
+pub const LIBRSYNC_RK_MD4_SIG_MAGIC: u32 = 0x7273_0146;
+pub const LIBRSYNC_RK_BLAKE2_SIG_MAGIC: u32 = 0x7273_0147;
+pub const LIBRSYNC_DELTA_MAGIC: u32 = 0x7273_0236;
+pub const VCDIFF_MAGIC: [u8; 4] = [0xD6, 0xC3, 0xC4, 0x00];
+pub const VCDIFF_WINDOW_SIZE: usize = 1024 * 1024;
+pub const VCDIFF_MAX_WINDOW_SIZE: usize = 16 * 1024 * 1024;
@@ -0,0 +1,572 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+};
+
+use crate::rdiff::{
+    chunk::iterator::{
+        fastcdc::FastCdcChunkIterator, BufferedRdiffChunkIterator, RdiffChunkIterator,
+    },
+    constants::READ_BLOCK_SIZE,
+    error::{
+        messages::{
+            COMPOSE_BASIS_REQUIRED, COMPOSE_HASH_MISMATCH, DELTA_BASIS_MISMATCH,
+            DELTA_GEOMETRY_UNKNOWN, DELTA_OUTPUT_SIZE_ERROR, INVALID_CHUNK_INDEX,
+            INVALID_SIGNATURE_FILE,
+        },
+        RollingHashError,
+    },
+    io::RdiffFile,
+    signature::Signature,
+};
+
+use super::{
+    header::DeltaHeader, patch::DeltaPatcher, patched_reader::PatchedReader, ChunkDelta, Delta,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum ChunkBoundaries {
+    Fixed(u64),
+    // Chunk index i starts at offset i - 1 and ends at offset i
+    Offsets(Vec<u64>),
+}
+
+// Chunk boundaries of a version of a file, match chunk deltas of a delta
+// are chunk indexes in the chunk geometry of its basis
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkGeometry {
+    boundaries: ChunkBoundaries,
+    size: u64,
+}
+
+impl ChunkGeometry {
+    pub fn new_with_chunk_size(
+        chunk_size: usize,
+        size: u64,
+    ) -> Result<ChunkGeometry, RollingHashError> {
+        BufferedRdiffChunkIterator::validate_chunk_size(chunk_size)?;
+        Ok(ChunkGeometry {
+            boundaries: ChunkBoundaries::Fixed(chunk_size as u64),
+            size,
+        })
+    }
+
+    pub fn new_with_chunk_lengths(chunk_lengths: &[usize]) -> ChunkGeometry {
+        let mut chunk_offsets: Vec<u64> = vec![0];
+        for chunk_length in chunk_lengths.iter() {
+            chunk_offsets.push(chunk_offsets[chunk_offsets.len() - 1] + *chunk_length as u64);
+        }
+        ChunkGeometry {
+            size: chunk_offsets[chunk_offsets.len() - 1],
+            boundaries: ChunkBoundaries::Offsets(chunk_offsets),
+        }
+    }
+
+    // Signature chunk table records the length of every basis chunk
+    pub fn new_with_signature(signature: &Signature) -> Result<ChunkGeometry, RollingHashError> {
+        let mut chunk_lengths: Vec<(u32, usize)> = signature
+            .get_rdiff_chunk_table()
+            .chunk_table
+            .values()
+            .flatten()
+            .map(|chunk_digest| (chunk_digest.index, chunk_digest.length))
+            .collect();
+        chunk_lengths.sort_unstable();
+        // Chunk indexes go from 1 to chunk count
+        if chunk_lengths
+            .iter()
+            .enumerate()
+            .any(|(position, (index, _))| *index as usize != position + 1)
+        {
+            return Err(RollingHashError::new(INVALID_SIGNATURE_FILE));
+        }
+        let chunk_lengths: Vec<usize> = chunk_lengths
+            .into_iter()
+            .map(|(_, chunk_length)| chunk_length)
+            .collect();
+        Ok(ChunkGeometry::new_with_chunk_lengths(&chunk_lengths))
+    }
+
+    // Chunk basis data the way it was chunked when the delta signature was created
+    pub fn new_with_rdiff_file(
+        mut rdiff_file: RdiffFile,
+        header: &DeltaHeader,
+    ) -> Result<ChunkGeometry, RollingHashError> {
+        if let Some(fastcdc_parameters) = header.get_fastcdc_parameters() {
+            let mut iterator = FastCdcChunkIterator::new(*fastcdc_parameters, rdiff_file);
+            let mut chunk_lengths: Vec<usize> = Vec::new();
+            while let Some(chunk) = iterator.next_chunk()? {
+                chunk_lengths.push(chunk.len());
+            }
+            return Ok(ChunkGeometry::new_with_chunk_lengths(&chunk_lengths));
+        }
+        // Fixed size chunks only need the data size, it is read when unknown
+        let size = match rdiff_file.size() {
+            Some(size) => size,
+            None => {
+                while rdiff_file
+                    .read_block()
+                    .map_err(RollingHashError::from)?
+                    .is_some()
+                {}
+                rdiff_file.get_read_size()
+            }
+        };
+        // Delta files without header were created with the legacy chunk size
+        let chunk_size = header
+            .get_chunk_size()
+            .unwrap_or_else(|| BufferedRdiffChunkIterator::compute_legacy_chunk_size(size));
+        ChunkGeometry::new_with_chunk_size(chunk_size, size as u64)
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn get_chunk_count(&self) -> usize {
+        match &self.boundaries {
+            ChunkBoundaries::Fixed(chunk_size) => self.size.div_ceil(*chunk_size) as usize,
+            ChunkBoundaries::Offsets(chunk_offsets) => chunk_offsets.len() - 1,
+        }
+    }
+
+    // Get byte range of count chunks from chunk index start index
+    fn get_chunk_range(
+        &self,
+        start_index: u32,
+        count: u32,
+    ) -> Result<(u64, u64), RollingHashError> {
+        let first_position = (start_index as usize)
+            .checked_sub(1)
+            .ok_or_else(|| RollingHashError::new(INVALID_CHUNK_INDEX))?;
+        let end_position = first_position + count as usize;
+        if count == 0 || end_position > self.get_chunk_count() {
+            return Err(RollingHashError::new(INVALID_CHUNK_INDEX));
+        }
+        Ok((
+            self.get_chunk_start(first_position),
+            self.get_chunk_start(end_position),
+        ))
+    }
+
+    // Get position of the chunk containing offset, chunk index is position + 1
+    fn get_chunk_position(&self, offset: u64) -> usize {
+        match &self.boundaries {
+            ChunkBoundaries::Fixed(chunk_size) => (offset / chunk_size) as usize,
+            ChunkBoundaries::Offsets(chunk_offsets) => {
+                chunk_offsets.partition_point(|chunk_offset| *chunk_offset <= offset) - 1
+            }
+        }
+    }
+
+    fn get_chunk_start(&self, position: usize) -> u64 {
+        match &self.boundaries {
+            ChunkBoundaries::Fixed(chunk_size) => (position as u64 * chunk_size).min(self.size),
+            ChunkBoundaries::Offsets(chunk_offsets) => chunk_offsets[position],
+        }
+    }
+
+    fn get_fixed_chunk_size(&self) -> Option<usize> {
+        match &self.boundaries {
+            ChunkBoundaries::Fixed(chunk_size) => Some(*chunk_size as usize),
+            ChunkBoundaries::Offsets(_) => None,
+        }
+    }
+}
+
+// Range of the intermediate version, copied from the oldest basis or from
+// differences of the first delta
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComposeSource {
+    Basis(u64),
+    Literal(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ComposeSegment {
+    offset: u64,
+    length: u64,
+    source: ComposeSource,
+}
+
+impl Delta {
+    // Compose a delta from basis to an intermediate version with a delta from
+    // the intermediate version to a new version, into a delta from basis to the
+    // new version. Each geometry is the chunk geometry of its delta basis.
+    // Matches of intermediate chunks are resolved to basis chunks and to first
+    // delta differences; basis ranges that are not whole basis chunks are
+    // read from basis and stored as differences
+    pub fn compose<B: Read + Seek>(
+        basis: &mut B,
+        first_delta: &Delta,
+        first_geometry: &ChunkGeometry,
+        second_delta: &Delta,
+        second_geometry: &ChunkGeometry,
+    ) -> Result<Delta, RollingHashError> {
+        Delta::compose_with_optional_basis(
+            Some(basis),
+            first_delta,
+            first_geometry,
+            second_delta,
+            second_geometry,
+        )
+    }
+
+    // Compose deltas without basis, it fails when the second delta copies
+    // parts of basis chunks, since chunk deltas only copy whole chunks
+    pub fn compose_without_basis(
+        first_delta: &Delta,
+        first_geometry: &ChunkGeometry,
+        second_delta: &Delta,
+        second_geometry: &ChunkGeometry,
+    ) -> Result<Delta, RollingHashError> {
+        Delta::compose_with_optional_basis(
+            None::<&mut io::Empty>,
+            first_delta,
+            first_geometry,
+            second_delta,
+            second_geometry,
+        )
+    }
+
+    fn compose_with_optional_basis<B: Read + Seek>(
+        mut basis: Option<&mut B>,
+        first_delta: &Delta,
+        first_geometry: &ChunkGeometry,
+        second_delta: &Delta,
+        second_geometry: &ChunkGeometry,
+    ) -> Result<Delta, RollingHashError> {
+        let header =
+            Delta::new_composed_header(&first_delta.header, first_geometry, &second_delta.header)?;
+        if first_delta
+            .header
+            .get_basis()
+            .is_some_and(|basis| basis.get_size() as u64 != first_geometry.get_size())
+        {
+            return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+        }
+        // Get intermediate version ranges, in order
+        let mut segments: Vec<ComposeSegment> = Vec::new();
+        let mut intermediate_size = 0;
+        for (position, chunk_delta) in first_delta.chunk_delta_list.iter().enumerate() {
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (*index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (*start_index, *count),
+                ChunkDelta::Diff(differences) => {
+                    if !differences.is_empty() {
+                        segments.push(ComposeSegment {
+                            offset: intermediate_size,
+                            length: differences.len() as u64,
+                            source: ComposeSource::Literal(position),
+                        });
+                        intermediate_size += differences.len() as u64;
+                    }
+                    continue;
+                }
+            };
+            if count == 0 {
+                continue;
+            }
+            let (start_offset, end_offset) = first_geometry.get_chunk_range(start_index, count)?;
+            segments.push(ComposeSegment {
+                offset: intermediate_size,
+                length: end_offset - start_offset,
+                source: ComposeSource::Basis(start_offset),
+            });
+            intermediate_size += end_offset - start_offset;
+        }
+        if first_delta
+            .header
+            .get_output_size()
+            .is_some_and(|output_size| output_size as u64 != intermediate_size)
+        {
+            return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+        }
+        // Second delta basis is the intermediate version
+        if second_geometry.get_size() != intermediate_size
+            || second_delta
+                .header
+                .get_basis()
+                .is_some_and(|basis| basis.get_size() as u64 != intermediate_size)
+        {
+            return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+        }
+        // Second delta basis digest is the first delta patched data digest
+        if let (Some(basis), Some(digest), Some(first_id), Some(second_id)) = (
+            second_delta.header.get_basis(),
+            first_delta.header.get_digest(),
+            first_delta.header.get_strong_hash_id(),
+            second_delta.header.get_strong_hash_id(),
+        ) {
+            if first_id == second_id && basis.get_digest() != digest {
+                return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+            }
+        }
+        // Resolve second delta matches through the intermediate version
+        let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+        let mut output_size = 0;
+        let mut buffer: Vec<u8> = vec![0; READ_BLOCK_SIZE];
+        for chunk_delta in second_delta.chunk_delta_list.iter() {
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (*index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (*start_index, *count),
+                ChunkDelta::Diff(differences) => {
+                    Delta::push_literal(&mut chunk_delta_list, differences);
+                    output_size += differences.len() as u64;
+                    continue;
+                }
+            };
+            if count == 0 {
+                continue;
+            }
+            let (start_offset, end_offset) = second_geometry.get_chunk_range(start_index, count)?;
+            let first_position =
+                segments.partition_point(|segment| segment.offset + segment.length <= start_offset);
+            for segment in segments[first_position..]
+                .iter()
+                .take_while(|segment| segment.offset < end_offset)
+            {
+                let piece_start = start_offset.max(segment.offset) - segment.offset;
+                let piece_end = end_offset.min(segment.offset + segment.length) - segment.offset;
+                match segment.source {
+                    ComposeSource::Literal(position) => {
+                        if let ChunkDelta::Diff(differences) =
+                            &first_delta.chunk_delta_list[position]
+                        {
+                            Delta::push_literal(
+                                &mut chunk_delta_list,
+                                &differences[piece_start as usize..piece_end as usize],
+                            );
+                        }
+                    }
+                    ComposeSource::Basis(basis_offset) => Delta::push_basis_range(
+                        &mut chunk_delta_list,
+                        basis.as_deref_mut(),
+                        first_geometry,
+                        basis_offset + piece_start,
+                        basis_offset + piece_end,
+                        &mut buffer,
+                    )?,
+                }
+            }
+            output_size += end_offset - start_offset;
+        }
+        if header.get_output_size() != Some(output_size as usize) {
+            return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+        }
+        Ok(Delta::new(header, chunk_delta_list))
+    }
+
+    // Compose delta files in rdiff format, the chunk geometries are found by
+    // chunking the basis file and the intermediate version read from it
+    pub fn create_composed_delta_file(
+        basis_file_name: &str,
+        first_delta_file_name: &str,
+        second_delta_file_name: &str,
+        delta_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        let mut basis_file =
+            File::open(basis_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let first_delta = Delta::get_delta_from_file(first_delta_file_name)?;
+        let second_delta = Delta::get_delta_from_file(second_delta_file_name)?;
+        // Basis file is checked against first delta header
+        DeltaPatcher::new(&first_delta.header, &mut basis_file, None)?;
+        let basis_size = basis_file
+            .seek(SeekFrom::End(0))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        basis_file
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let first_geometry = ChunkGeometry::new_with_rdiff_file(
+            RdiffFile::new_with_reader(basis_file_name, &mut basis_file, basis_size),
+            &first_delta.header,
+        )?;
+        // Intermediate version is only read when its chunks are content defined
+        let second_geometry = match (
+            second_delta.header.get_fastcdc_parameters(),
+            second_delta.header.get_chunk_size(),
+            first_delta.header.get_output_size(),
+        ) {
+            (None, Some(chunk_size), Some(intermediate_size)) => {
+                ChunkGeometry::new_with_chunk_size(chunk_size, intermediate_size as u64)?
+            }
+            _ => {
+                let mut patched_reader = PatchedReader::new(&mut basis_file, first_delta.clone())?;
+                let intermediate_size = patched_reader.get_size();
+                ChunkGeometry::new_with_rdiff_file(
+                    RdiffFile::new_with_reader(
+                        first_delta_file_name,
+                        &mut patched_reader,
+                        intermediate_size,
+                    ),
+                    &second_delta.header,
+                )?
+            }
+        };
+        let delta = Delta::compose(
+            &mut basis_file,
+            &first_delta,
+            &first_geometry,
+            &second_delta,
+            &second_geometry,
+        )?;
+        Delta::write_composed_delta_file(&delta, delta_file_name)
+    }
+
+    // Compose delta files in rdiff format without basis file, both deltas
+    // must have fixed size chunks and the first delta must record basis size
+    pub fn create_composed_delta_file_without_basis(
+        first_delta_file_name: &str,
+        second_delta_file_name: &str,
+        delta_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        let first_delta = Delta::get_delta_from_file(first_delta_file_name)?;
+        let second_delta = Delta::get_delta_from_file(second_delta_file_name)?;
+        let first_geometry = match (
+            first_delta.header.get_fastcdc_parameters(),
+            first_delta.header.get_chunk_size(),
+            first_delta.header.get_basis(),
+        ) {
+            (None, Some(chunk_size), Some(basis)) => {
+                ChunkGeometry::new_with_chunk_size(chunk_size, basis.get_size() as u64)?
+            }
+            _ => return Err(RollingHashError::new(DELTA_GEOMETRY_UNKNOWN)),
+        };
+        let second_geometry = match (
+            second_delta.header.get_fastcdc_parameters(),
+            second_delta.header.get_chunk_size(),
+            first_delta.header.get_output_size(),
+        ) {
+            (None, Some(chunk_size), Some(intermediate_size)) => {
+                ChunkGeometry::new_with_chunk_size(chunk_size, intermediate_size as u64)?
+            }
+            _ => return Err(RollingHashError::new(DELTA_GEOMETRY_UNKNOWN)),
+        };
+        let delta = Delta::compose_without_basis(
+            &first_delta,
+            &first_geometry,
+            &second_delta,
+            &second_geometry,
+        )?;
+        Delta::write_composed_delta_file(&delta, delta_file_name)
+    }
+
+    fn write_composed_delta_file(
+        delta: &Delta,
+        delta_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Write composed delta to delta file
+        let delta_file =
+            File::create(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let result = {
+            let mut delta_writer = BufWriter::new(delta_file);
+            delta.write_delta(&mut delta_writer).and_then(|_| {
+                delta_writer
+                    .flush()
+                    .map_err(|e| RollingHashError::from(Box::new(e)))
+            })
+        };
+        // Do not leave an incomplete delta file behind
+        if result.is_err() {
+            let _ = fs::remove_file(delta_file_name);
+        }
+        result
+    }
+
+    fn new_composed_header(
+        first_header: &DeltaHeader,
+        first_geometry: &ChunkGeometry,
+        second_header: &DeltaHeader,
+    ) -> Result<DeltaHeader, RollingHashError> {
+        // Composed delta has the chunk geometry of the first delta basis
+        let chunk_size = match (
+            first_header.get_chunk_size(),
+            first_geometry.get_fixed_chunk_size(),
+        ) {
+            (Some(chunk_size), _) | (None, Some(chunk_size)) => chunk_size,
+            (None, None) => return Err(RollingHashError::new(DELTA_GEOMETRY_UNKNOWN)),
+        };
+        let chunk_count = first_geometry.get_chunk_count();
+        let last_chunk_size = match chunk_count {
+            0 => 0,
+            _ => {
+                (first_geometry.get_size() - first_geometry.get_chunk_start(chunk_count - 1))
+                    as usize
+            }
+        };
+        let output_size = second_header
+            .get_output_size()
+            .ok_or_else(|| RollingHashError::new(DELTA_GEOMETRY_UNKNOWN))?;
+        // Basis fingerprint and patched data digest use the same strong hash
+        let mut header = match (
+            first_header.get_basis(),
+            first_header.get_strong_hash_id(),
+            second_header.get_strong_hash_id(),
+            second_header.get_digest(),
+        ) {
+            (Some(_), Some(first_id), Some(second_id), _) if first_id != second_id => {
+                return Err(RollingHashError::new(COMPOSE_HASH_MISMATCH));
+            }
+            (basis, _, Some(strong_hash_id), Some(digest)) => DeltaHeader::new(
+                chunk_size,
+                last_chunk_size,
+                output_size,
+                strong_hash_id,
+                digest.clone(),
+                basis.cloned(),
+            ),
+            _ => DeltaHeader::new_without_digest(chunk_size, last_chunk_size, output_size),
+        };
+        header.set_fastcdc_parameters(first_header.get_fastcdc_parameters().copied());
+        header.set_compression_id(
+            second_header
+                .get_compression_id()
+                .or(first_header.get_compression_id()),
+        );
+        Ok(header)
+    }
+
+    // Push basis byte range as matches of whole basis chunks, and as
+    // differences read from basis for parts of basis chunks
+    pub(crate) fn push_basis_range<B: Read + Seek>(
+        chunk_delta_list: &mut Vec<ChunkDelta>,
+        mut basis: Option<&mut B>,
+        geometry: &ChunkGeometry,
+        start_offset: u64,
+        end_offset: u64,
+        buffer: &mut [u8],
+    ) -> Result<(), RollingHashError> {
+        let mut offset = start_offset;
+        while offset < end_offset {
+            let position = geometry.get_chunk_position(offset);
+            let chunk_start = geometry.get_chunk_start(position);
+            let chunk_end = geometry.get_chunk_start(position + 1);
+            if chunk_start == offset && chunk_end <= end_offset {
+                let index = u32::try_from(position + 1)
+                    .map_err(|_| RollingHashError::new(INVALID_CHUNK_INDEX))?;
+                Delta::push_match(chunk_delta_list, ChunkDelta::Match(index));
+                offset = chunk_end;
+                continue;
+            }
+            let piece_end = chunk_end.min(end_offset);
+            let basis = basis
+                .as_deref_mut()
+                .ok_or_else(|| RollingHashError::new(COMPOSE_BASIS_REQUIRED))?;
+            basis
+                .seek(SeekFrom::Start(offset))
+                .map_err(|e| RollingHashError::from(Box::new(e)))?;
+            while offset < piece_end {
+                let size = (piece_end - offset).min(buffer.len() as u64) as usize;
+                basis
+                    .read_exact(&mut buffer[..size])
+                    .map_err(|e| RollingHashError::from(Box::new(e)))?;
+                Delta::push_literal(chunk_delta_list, &buffer[..size]);
+                offset += size as u64;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
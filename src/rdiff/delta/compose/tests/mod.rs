@@ -0,0 +1,293 @@
+use std::{fs, io::Cursor};
+
+use crate::rdiff::{
+    chunk::iterator::fastcdc::FastCdcParameters,
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{COMPOSE_BASIS_REQUIRED, COMPOSE_HASH_MISMATCH, DELTA_BASIS_MISMATCH},
+        RollingHashError,
+    },
+    hash::{
+        strong::{rdiff_blake3::RdiffBlake3, rdiff_sha1::RdiffSha1},
+        weak::rdiff_addler::RdiffAddler,
+    },
+    io::RdiffFile,
+    signature::Signature,
+    util::get_edited_data,
+};
+
+use super::ChunkGeometry;
+
+fn get_versions() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    // Change, insert and remove data of first version twice
+    let first_data = fs::read("resources/poem.txt").unwrap();
+    let second_data = get_edited_data(&first_data);
+    let mut third_data = second_data.clone();
+    third_data.splice(0..0, b"prefix".iter().copied());
+    third_data[700..720].copy_from_slice(b"abcdefghijklmnopqrst");
+    third_data.drain(300..340);
+    (first_data, second_data, third_data)
+}
+
+fn create_fixed_signature(data: &[u8]) -> Signature {
+    Signature::create_signature_from_bytes(data, RdiffAddler::new_ptr(), RdiffSha1::new_ptr())
+        .unwrap()
+}
+
+fn create_fastcdc_signature(data: &[u8]) -> Signature {
+    Signature::create_signature_from_rdiff_file_with_fastcdc(
+        RdiffFile::new_with_bytes("data", data),
+        FastCdcParameters::new(32, 64, 256).unwrap(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap()
+}
+
+fn compose_versions(create_signature: fn(&[u8]) -> Signature) {
+    // Get deltas from first to second and from second to third version
+    let (first_data, second_data, third_data) = get_versions();
+    let first_signature = create_signature(&first_data);
+    let first_geometry = ChunkGeometry::new_with_signature(&first_signature).unwrap();
+    let first_delta = Delta::generate_delta_from_bytes(&second_data, first_signature).unwrap();
+    let second_signature = create_signature(&second_data);
+    let second_geometry = ChunkGeometry::new_with_signature(&second_signature).unwrap();
+    let second_delta = Delta::generate_delta_from_bytes(&third_data, second_signature).unwrap();
+
+    // Compose deltas into a delta from first to third version
+    let delta = Delta::compose(
+        &mut Cursor::new(first_data.as_slice()),
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    )
+    .unwrap();
+
+    // Verify computed values
+    assert_eq!(delta.get_header().get_output_size(), Some(third_data.len()));
+    assert!(delta
+        .get_chunk_delta_list()
+        .iter()
+        .any(|chunk_delta| !matches!(chunk_delta, ChunkDelta::Diff(_))));
+    let mut output: Vec<u8> = Vec::new();
+    delta
+        .apply_to_basis(&mut Cursor::new(first_data.as_slice()), &mut output)
+        .unwrap();
+    assert_eq!(output, third_data);
+}
+
+#[test]
+fn test_delta_compose_case1() {
+    compose_versions(create_fixed_signature);
+}
+
+#[test]
+fn test_delta_compose_fastcdc_case1() {
+    compose_versions(create_fastcdc_signature);
+}
+
+#[test]
+fn test_delta_compose_basis_mismatch_case1() {
+    let (first_data, second_data, third_data) = get_versions();
+    let first_signature = create_fixed_signature(&first_data);
+    let first_geometry = ChunkGeometry::new_with_signature(&first_signature).unwrap();
+    let first_delta = Delta::generate_delta_from_bytes(&second_data, first_signature).unwrap();
+    // Second delta is not created from second version
+    let second_signature = create_fixed_signature(&first_data);
+    let second_geometry = ChunkGeometry::new_with_signature(&second_signature).unwrap();
+    let second_delta = Delta::generate_delta_from_bytes(&third_data, second_signature).unwrap();
+    let result = Delta::compose(
+        &mut Cursor::new(first_data.as_slice()),
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    );
+    assert_eq!(result, Err(RollingHashError::new(DELTA_BASIS_MISMATCH)));
+}
+
+#[test]
+fn test_delta_compose_hash_mismatch_case1() {
+    let (first_data, second_data, third_data) = get_versions();
+    let first_signature = create_fixed_signature(&first_data);
+    let first_geometry = ChunkGeometry::new_with_signature(&first_signature).unwrap();
+    let first_delta = Delta::generate_delta_from_bytes(&second_data, first_signature).unwrap();
+    // Second delta digests use another strong hash
+    let second_signature = Signature::create_signature_from_bytes(
+        &second_data,
+        RdiffAddler::new_ptr(),
+        RdiffBlake3::new_ptr(),
+    )
+    .unwrap();
+    let second_geometry = ChunkGeometry::new_with_signature(&second_signature).unwrap();
+    let second_delta = Delta::generate_delta_from_bytes(&third_data, second_signature).unwrap();
+    let result = Delta::compose(
+        &mut Cursor::new(first_data.as_slice()),
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    );
+    assert_eq!(result, Err(RollingHashError::new(COMPOSE_HASH_MISMATCH)));
+}
+
+#[test]
+fn test_delta_compose_partial_chunks_case1() {
+    // Basis abcdef with chunk size 2, intermediate version is xabcdef
+    let first_header = DeltaHeader::new_without_digest(2, 2, 7);
+    let first_delta = Delta::new(
+        first_header,
+        vec![
+            ChunkDelta::Diff(b"x".to_vec()),
+            ChunkDelta::CopyRange {
+                start_index: 1,
+                count: 3,
+            },
+        ],
+    );
+    let first_geometry = ChunkGeometry::new_with_chunk_size(2, 6).unwrap();
+    // Intermediate chunks are xa, bc, de and f, new version is bcdefxa
+    let second_header = DeltaHeader::new_without_digest(2, 1, 7);
+    let second_delta = Delta::new(
+        second_header,
+        vec![
+            ChunkDelta::CopyRange {
+                start_index: 2,
+                count: 3,
+            },
+            ChunkDelta::Match(1),
+        ],
+    );
+    let second_geometry = ChunkGeometry::new_with_chunk_size(2, 7).unwrap();
+    let delta = Delta::compose(
+        &mut Cursor::new(b"abcdef".as_slice()),
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    )
+    .unwrap();
+
+    // Parts of basis chunks are read from basis
+    assert_eq!(
+        delta.get_chunk_delta_list(),
+        &vec![
+            ChunkDelta::Diff(b"b".to_vec()),
+            ChunkDelta::CopyRange {
+                start_index: 2,
+                count: 2,
+            },
+            ChunkDelta::Diff(b"xa".to_vec()),
+        ]
+    );
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(2));
+}
+
+#[test]
+fn test_delta_compose_without_basis_case1() {
+    // Basis abcdef with chunk size 2, intermediate version is xyabcdef
+    let first_header = DeltaHeader::new_without_digest(2, 2, 8);
+    let first_delta = Delta::new(
+        first_header,
+        vec![
+            ChunkDelta::Diff(b"xy".to_vec()),
+            ChunkDelta::CopyRange {
+                start_index: 1,
+                count: 3,
+            },
+        ],
+    );
+    let first_geometry = ChunkGeometry::new_with_chunk_size(2, 6).unwrap();
+    // Intermediate chunks are xy, ab, cd and ef, new version is abcdefxy
+    let second_header = DeltaHeader::new_without_digest(2, 2, 8);
+    let second_delta = Delta::new(
+        second_header,
+        vec![
+            ChunkDelta::CopyRange {
+                start_index: 2,
+                count: 3,
+            },
+            ChunkDelta::Match(1),
+        ],
+    );
+    let second_geometry = ChunkGeometry::new_with_chunk_size(2, 8).unwrap();
+    let delta = Delta::compose_without_basis(
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    )
+    .unwrap();
+
+    // Intermediate chunks are whole basis chunks, so basis is not read
+    assert_eq!(
+        delta.get_chunk_delta_list(),
+        &vec![
+            ChunkDelta::CopyRange {
+                start_index: 1,
+                count: 3,
+            },
+            ChunkDelta::Diff(b"xy".to_vec()),
+        ]
+    );
+    let mut output: Vec<u8> = Vec::new();
+    delta
+        .apply_to_basis(&mut Cursor::new(b"abcdef".as_slice()), &mut output)
+        .unwrap();
+    assert_eq!(output, b"abcdefxy");
+}
+
+#[test]
+fn test_delta_compose_without_basis_case2() {
+    // Intermediate version xabcdef copies parts of basis chunks
+    let first_header = DeltaHeader::new_without_digest(2, 2, 7);
+    let first_delta = Delta::new(
+        first_header,
+        vec![
+            ChunkDelta::Diff(b"x".to_vec()),
+            ChunkDelta::CopyRange {
+                start_index: 1,
+                count: 3,
+            },
+        ],
+    );
+    let first_geometry = ChunkGeometry::new_with_chunk_size(2, 6).unwrap();
+    let second_header = DeltaHeader::new_without_digest(2, 1, 2);
+    let second_delta = Delta::new(second_header, vec![ChunkDelta::Match(2)]);
+    let second_geometry = ChunkGeometry::new_with_chunk_size(2, 7).unwrap();
+    let result = Delta::compose_without_basis(
+        &first_delta,
+        &first_geometry,
+        &second_delta,
+        &second_geometry,
+    );
+    assert_eq!(result, Err(RollingHashError::new(COMPOSE_BASIS_REQUIRED)));
+}
+
+#[test]
+fn test_chunk_geometry_new_with_rdiff_file_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+
+    // Content defined chunks are the signature ones
+    let signature = create_fastcdc_signature(&data);
+    let geometry = ChunkGeometry::new_with_signature(&signature).unwrap();
+    let delta = Delta::generate_delta_from_bytes(&data, signature).unwrap();
+    let rdiff_file = RdiffFile::new_with_unsized_reader("data", data.as_slice());
+    assert_eq!(
+        ChunkGeometry::new_with_rdiff_file(rdiff_file, delta.get_header()).unwrap(),
+        geometry
+    );
+
+    // Fixed size chunks of data of unknown size
+    let signature = create_fixed_signature(&data);
+    let chunk_size = signature.get_chunk_size();
+    let delta = Delta::generate_delta_from_bytes(&data, signature).unwrap();
+    let rdiff_file = RdiffFile::new_with_unsized_reader("data", data.as_slice());
+    let geometry = ChunkGeometry::new_with_rdiff_file(rdiff_file, delta.get_header()).unwrap();
+    assert_eq!(
+        geometry,
+        ChunkGeometry::new_with_chunk_size(chunk_size, data.len() as u64).unwrap()
+    );
+    assert_eq!(geometry.get_chunk_count(), data.len().div_ceil(chunk_size));
+}
@@ -0,0 +1,216 @@
+use std::io::{BufRead, Read};
+
+use bincode::deserialize_from;
+use serde::{Deserialize, Serialize};
+
+use crate::rdiff::{
+    chunk::{iterator::fastcdc::FastCdcParameters, RdiffDigest},
+    constants::{
+        DELTA_LEGACY_VERSION, DELTA_MAGIC, DELTA_VERSION, DELTA_VERSION_1, DELTA_VERSION_2,
+        DELTA_VERSION_3, DELTA_VERSION_5, DELTA_VERSION_6,
+    },
+    error::{
+        messages::{INVALID_DELTA_FILE, UNSUPPORTED_DELTA_VERSION},
+        RollingHashError,
+    },
+    signature::header::BasisFingerprint,
+};
+
+// Delta file header, it is stored before the chunk delta list and records
+// the chunk geometry used by match chunk deltas, the patched data size,
+// the patched data digest, the basis file fingerprint, the compression
+// used by differences and the content defined chunking of the basis, if any
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaHeader {
+    magic: u32,
+    version: u32,
+    chunk_size: usize,
+    last_chunk_size: usize,
+    output_size: usize,
+    strong_hash_id: u32,
+    digest: RdiffDigest,
+    basis: Option<BasisFingerprint>,
+    compression_id: u32,
+    fastcdc_parameters: Option<FastCdcParameters>,
+}
+
+// Compression id of delta files with uncompressed differences
+const NO_COMPRESSION_ID: u32 = 0;
+// Strong hash id of delta files without patched data digest
+const NO_STRONG_HASH_ID: u32 = 0;
+
+impl DeltaHeader {
+    pub fn new(
+        chunk_size: usize,
+        last_chunk_size: usize,
+        output_size: usize,
+        strong_hash_id: u32,
+        digest: RdiffDigest,
+        basis: Option<BasisFingerprint>,
+    ) -> DeltaHeader {
+        DeltaHeader {
+            magic: DELTA_MAGIC,
+            version: DELTA_VERSION,
+            chunk_size,
+            last_chunk_size,
+            output_size,
+            strong_hash_id,
+            digest,
+            basis,
+            compression_id: NO_COMPRESSION_ID,
+            fastcdc_parameters: None,
+        }
+    }
+
+    // Header of delta files imported from formats without patched data digest
+    pub fn new_without_digest(
+        chunk_size: usize,
+        last_chunk_size: usize,
+        output_size: usize,
+    ) -> DeltaHeader {
+        DeltaHeader::new(
+            chunk_size,
+            last_chunk_size,
+            output_size,
+            NO_STRONG_HASH_ID,
+            Vec::new(),
+            None,
+        )
+    }
+
+    // Header of delta files created before delta files had a header,
+    // nothing is known about them
+    pub fn legacy() -> DeltaHeader {
+        DeltaHeader {
+            magic: DELTA_MAGIC,
+            version: DELTA_LEGACY_VERSION,
+            chunk_size: 0,
+            last_chunk_size: 0,
+            output_size: 0,
+            strong_hash_id: 0,
+            digest: Vec::new(),
+            basis: None,
+            compression_id: NO_COMPRESSION_ID,
+            fastcdc_parameters: None,
+        }
+    }
+
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.version == DELTA_LEGACY_VERSION
+    }
+
+    pub fn get_chunk_size(&self) -> Option<usize> {
+        (!self.is_legacy()).then_some(self.chunk_size)
+    }
+
+    pub fn get_last_chunk_size(&self) -> Option<usize> {
+        (!self.is_legacy()).then_some(self.last_chunk_size)
+    }
+
+    pub fn get_output_size(&self) -> Option<usize> {
+        (!self.is_legacy()).then_some(self.output_size)
+    }
+
+    // Version 1 delta files and imported delta files have no patched data digest
+    pub fn get_strong_hash_id(&self) -> Option<u32> {
+        (self.version >= DELTA_VERSION_2 && self.strong_hash_id != NO_STRONG_HASH_ID)
+            .then_some(self.strong_hash_id)
+    }
+
+    pub fn get_digest(&self) -> Option<&RdiffDigest> {
+        self.get_strong_hash_id().map(|_| &self.digest)
+    }
+
+    // Version 1 and 2 delta files, and delta files created from version 1
+    // signature files, have no basis fingerprint
+    pub fn get_basis(&self) -> Option<&BasisFingerprint> {
+        self.basis.as_ref()
+    }
+
+    // Delta files created before version 5 have uncompressed differences
+    pub fn get_compression_id(&self) -> Option<u32> {
+        (self.compression_id != NO_COMPRESSION_ID).then_some(self.compression_id)
+    }
+
+    pub fn set_compression_id(&mut self, compression_id: Option<u32>) {
+        self.compression_id = compression_id.unwrap_or(NO_COMPRESSION_ID);
+    }
+
+    // Delta files created before version 6 have fixed size basis chunks
+    pub fn get_fastcdc_parameters(&self) -> Option<&FastCdcParameters> {
+        self.fastcdc_parameters.as_ref()
+    }
+
+    pub fn set_fastcdc_parameters(&mut self, fastcdc_parameters: Option<FastCdcParameters>) {
+        self.fastcdc_parameters = fastcdc_parameters;
+    }
+
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<DeltaHeader, RollingHashError> {
+        // Legacy delta files start with the chunk delta list length,
+        // so the magic number is checked without consuming it
+        let buffer = reader
+            .fill_buf()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if buffer.len() < 4 || buffer[..4] != DELTA_MAGIC.to_le_bytes() {
+            return Ok(DeltaHeader::legacy());
+        }
+        // Get magic number and version
+        let (magic, version): (u32, u32) = DeltaHeader::read_field(reader)?;
+        if !(DELTA_VERSION_1..=DELTA_VERSION_6).contains(&version) {
+            return Err(RollingHashError::new(UNSUPPORTED_DELTA_VERSION));
+        }
+        // Get chunk geometry and patched data size
+        let (chunk_size, last_chunk_size, output_size): (usize, usize, usize) =
+            DeltaHeader::read_field(reader)?;
+        let mut header = DeltaHeader {
+            magic,
+            version,
+            chunk_size,
+            last_chunk_size,
+            output_size,
+            strong_hash_id: 0,
+            digest: Vec::new(),
+            basis: None,
+            compression_id: NO_COMPRESSION_ID,
+            fastcdc_parameters: None,
+        };
+        // Get patched data digest
+        if version >= DELTA_VERSION_2 {
+            let (strong_hash_id, digest): (u32, RdiffDigest) = DeltaHeader::read_field(reader)?;
+            header.strong_hash_id = strong_hash_id;
+            header.digest = digest;
+        }
+        // Get basis fingerprint
+        if version >= DELTA_VERSION_3 {
+            header.basis = DeltaHeader::read_field(reader)?;
+        }
+        // Get differences compression
+        if version >= DELTA_VERSION_5 {
+            header.compression_id = DeltaHeader::read_field(reader)?;
+        }
+        // Get basis content defined chunking
+        if version >= DELTA_VERSION_6 {
+            let fastcdc_parameters: Option<FastCdcParameters> = DeltaHeader::read_field(reader)?;
+            if let Some(fastcdc_parameters) = fastcdc_parameters.as_ref() {
+                fastcdc_parameters
+                    .validate()
+                    .map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))?;
+            }
+            header.fastcdc_parameters = fastcdc_parameters;
+        }
+        Ok(header)
+    }
+
+    fn read_field<R: Read, T: for<'de> Deserialize<'de>>(
+        reader: &mut R,
+    ) -> Result<T, RollingHashError> {
+        deserialize_from(reader).map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,211 @@
+use std::io::Cursor;
+
+use bincode::serialize;
+
+use crate::rdiff::{
+    chunk::{iterator::fastcdc::FastCdcParameters, RdiffDigest},
+    compression::rdiff_deflate::RDIFF_DEFLATE_ID,
+    constants::{
+        BLOCK_SIZE, DELTA_MAGIC, DELTA_VERSION, DELTA_VERSION_1, DELTA_VERSION_2, DELTA_VERSION_3,
+        DELTA_VERSION_4, DELTA_VERSION_5,
+    },
+    delta::ChunkDelta,
+    error::{
+        messages::{INVALID_DELTA_FILE, UNSUPPORTED_DELTA_VERSION},
+        RollingHashError,
+    },
+    hash::strong::rdiff_sha1::RDIFF_SHA1_ID,
+    signature::header::BasisFingerprint,
+};
+
+use super::DeltaHeader;
+
+#[test]
+fn test_delta_header_read_from_case1() {
+    let digest: RdiffDigest = vec![1; 20];
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        3 * BLOCK_SIZE - 1,
+        RDIFF_SHA1_ID,
+        digest.clone(),
+        Some(BasisFingerprint::new(2 * BLOCK_SIZE, vec![2; 20])),
+    );
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header, header);
+    assert_eq!(computed_header.get_version(), DELTA_VERSION);
+    assert_eq!(computed_header.get_chunk_size(), Some(BLOCK_SIZE));
+    assert_eq!(computed_header.get_last_chunk_size(), Some(BLOCK_SIZE - 1));
+    assert_eq!(computed_header.get_output_size(), Some(3 * BLOCK_SIZE - 1));
+    assert_eq!(computed_header.get_strong_hash_id(), Some(RDIFF_SHA1_ID));
+    assert_eq!(computed_header.get_digest(), Some(&digest));
+    assert_eq!(
+        computed_header.get_basis(),
+        Some(&BasisFingerprint::new(2 * BLOCK_SIZE, vec![2; 20]))
+    );
+}
+
+#[test]
+fn test_delta_header_read_from_version_3_case1() {
+    // Version 4 delta headers are version 3 ones, the version changed so
+    // readers that do not know copy ranges reject their chunk delta lists
+    let digest: RdiffDigest = vec![1; 20];
+    let basis = Some(BasisFingerprint::new(2 * BLOCK_SIZE, vec![2; 20]));
+    for version in [DELTA_VERSION_3, DELTA_VERSION_4] {
+        let data = serialize(&(
+            (DELTA_MAGIC, version),
+            (BLOCK_SIZE, 1_usize, 10_usize),
+            (RDIFF_SHA1_ID, digest.clone()),
+            basis.clone(),
+        ))
+        .unwrap();
+        let mut reader = Cursor::new(data);
+        let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+        assert_eq!(computed_header.get_version(), version);
+        assert_eq!(computed_header.get_output_size(), Some(10));
+        assert_eq!(computed_header.get_digest(), Some(&digest));
+        assert_eq!(computed_header.get_basis(), basis.as_ref());
+    }
+}
+
+#[test]
+fn test_delta_header_read_from_version_2_case1() {
+    // Version 2 delta headers have no basis fingerprint
+    let digest: RdiffDigest = vec![1; 20];
+    let data = serialize(&(
+        (DELTA_MAGIC, DELTA_VERSION_2),
+        (BLOCK_SIZE, 1_usize, 10_usize),
+        (RDIFF_SHA1_ID, digest.clone()),
+    ))
+    .unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header.get_version(), DELTA_VERSION_2);
+    assert_eq!(computed_header.get_output_size(), Some(10));
+    assert_eq!(computed_header.get_digest(), Some(&digest));
+    assert_eq!(computed_header.get_basis(), None);
+}
+
+#[test]
+fn test_delta_header_read_from_version_1_case1() {
+    // Version 1 delta headers have no patched data digest
+    let data = serialize(&(DELTA_MAGIC, DELTA_VERSION_1, BLOCK_SIZE, 1_usize, 10_usize)).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header.get_version(), DELTA_VERSION_1);
+    assert_eq!(computed_header.get_chunk_size(), Some(BLOCK_SIZE));
+    assert_eq!(computed_header.get_last_chunk_size(), Some(1));
+    assert_eq!(computed_header.get_output_size(), Some(10));
+    assert_eq!(computed_header.get_strong_hash_id(), None);
+    assert_eq!(computed_header.get_digest(), None);
+    assert_eq!(computed_header.get_basis(), None);
+    assert_eq!(computed_header.get_compression_id(), None);
+}
+
+#[test]
+fn test_delta_header_compression_id_case1() {
+    let mut header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        RDIFF_SHA1_ID,
+        Vec::new(),
+        None,
+    );
+    assert_eq!(header.get_compression_id(), None);
+    header.set_compression_id(Some(RDIFF_DEFLATE_ID));
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header, header);
+    assert_eq!(computed_header.get_compression_id(), Some(RDIFF_DEFLATE_ID));
+    header.set_compression_id(None);
+    assert_eq!(header.get_compression_id(), None);
+}
+
+#[test]
+fn test_delta_header_fastcdc_parameters_case1() {
+    let mut header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        RDIFF_SHA1_ID,
+        Vec::new(),
+        None,
+    );
+    assert_eq!(header.get_fastcdc_parameters(), None);
+    let fastcdc_parameters =
+        FastCdcParameters::new(BLOCK_SIZE / 2, BLOCK_SIZE, 2 * BLOCK_SIZE).unwrap();
+    header.set_fastcdc_parameters(Some(fastcdc_parameters));
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header, header);
+    assert_eq!(
+        computed_header.get_fastcdc_parameters(),
+        Some(&fastcdc_parameters)
+    );
+}
+
+#[test]
+fn test_delta_header_read_from_version_5_case1() {
+    // Version 5 delta headers have fixed size basis chunks
+    let data = serialize(&(
+        (DELTA_MAGIC, DELTA_VERSION_5),
+        (BLOCK_SIZE, 1_usize, 10_usize),
+        (RDIFF_SHA1_ID, vec![1_u8; 20]),
+        None::<BasisFingerprint>,
+        RDIFF_DEFLATE_ID,
+    ))
+    .unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header.get_version(), DELTA_VERSION_5);
+    assert_eq!(computed_header.get_compression_id(), Some(RDIFF_DEFLATE_ID));
+    assert_eq!(computed_header.get_fastcdc_parameters(), None);
+}
+
+#[test]
+fn test_delta_header_read_from_legacy_case1() {
+    // Legacy delta files only contain the chunk delta list
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Match(1)];
+    let data = serialize(&chunk_delta_list).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = DeltaHeader::read_from(&mut reader).unwrap();
+    assert!(computed_header.is_legacy());
+    assert_eq!(computed_header.get_chunk_size(), None);
+    // Nothing is consumed from legacy delta files
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn test_delta_header_read_from_error_case1() {
+    let mut header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        RDIFF_SHA1_ID,
+        Vec::new(),
+        None,
+    );
+    header.version = DELTA_VERSION + 1;
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let error = DeltaHeader::read_from(&mut reader).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_DELTA_VERSION));
+
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        BLOCK_SIZE,
+        RDIFF_SHA1_ID,
+        Vec::new(),
+        None,
+    );
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data[..12].to_vec());
+    let error = DeltaHeader::read_from(&mut reader).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+}
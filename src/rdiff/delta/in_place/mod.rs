@@ -0,0 +1,666 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use bincode::{deserialize_from, serialize_into, serialized_size};
+use serde::{Deserialize, Serialize};
+
+use crate::rdiff::{
+    chunk::RdiffDigest,
+    constants::{
+        IN_PLACE_BLOCK_SIZE, JOURNAL_FILE_SUFFIX, JOURNAL_MAGIC, JOURNAL_VERSION, RDIFF_FORMAT,
+    },
+    error::{
+        messages::{
+            DELTA_DIGEST_MISMATCH, DELTA_OUTPUT_SIZE_ERROR, INVALID_JOURNAL_FILE,
+            IN_PLACE_RDIFF_FORMAT_REQUIRED, IN_PLACE_ROLLBACK_UNAVAILABLE,
+        },
+        RollingHashError,
+    },
+    hash::strong::new_strong_hash_ptr,
+};
+
+use super::{patch::DeltaPatcher, reader::DeltaReader, ChunkDelta, Delta};
+
+// Journal progress is stored right after journal magic and version
+const JOURNAL_PROGRESS_OFFSET: u64 = 8;
+
+// Copy of a basis byte range to its offset in the patched file
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct InPlaceCopy {
+    source_offset: u64,
+    target_offset: u64,
+    length: u64,
+}
+
+impl InPlaceCopy {
+    fn source_end(&self) -> u64 {
+        self.source_offset + self.length
+    }
+
+    fn target_end(&self) -> u64 {
+        self.target_offset + self.length
+    }
+
+    // Copy overwrites part of its own source, it is done one staged block at a time
+    fn is_self_overlapping(&self) -> bool {
+        self.source_offset != self.target_offset
+            && self.source_offset < self.target_end()
+            && self.target_offset < self.source_end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum InPlaceStep {
+    // Copy basis byte range, no later step overwrites it before it is read
+    Copy {
+        source_offset: u64,
+        target_offset: u64,
+        length: u64,
+    },
+    // Copy basis byte range saved in journal before patching started
+    Buffered {
+        data_offset: u64,
+        target_offset: u64,
+        length: u64,
+    },
+    // Write delta differences, they do not read the basis so they are written last
+    Literals,
+    // Truncate or extend basis file to patched file size
+    SetLength(u64),
+}
+
+// Patch plan, written once when the journal is created
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct InPlacePlan {
+    block_size: u64,
+    delta_output_size: Option<u64>,
+    delta_digest: Option<RdiffDigest>,
+    strong_hash_id: Option<u32>,
+    output_size: u64,
+    literal_offsets: Vec<u64>,
+    steps: Vec<InPlaceStep>,
+}
+
+// Patch progress, rewritten in place after each step and each staged block
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct InPlaceProgress {
+    basis_changed: u64,
+    step: u64,
+    done_size: u64,
+    staged: u64,
+}
+
+// Rewrites a basis file into the patched file, following a plan stored in a
+// journal next to the basis. Journal layout is magic, version, progress, plan
+// size, plan, a staging block and the data of buffered copies
+struct InPlacePatcher {
+    basis: File,
+    journal_file: File,
+    plan: InPlacePlan,
+    progress: InPlaceProgress,
+    staging_offset: u64,
+    buffer: Vec<u8>,
+}
+
+impl InPlacePatcher {
+    fn create(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        journal_file_name: &str,
+        chunk_size: Option<usize>,
+        block_size: usize,
+    ) -> Result<InPlacePatcher, RollingHashError> {
+        let mut basis = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(basis_file_name)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut delta_reader = DeltaReader::new(delta_file)?;
+        let header = delta_reader.get_header();
+        let delta_output_size = header.get_output_size().map(|size| size as u64);
+        let delta_digest = header.get_digest().cloned();
+        let strong_hash_id = header.get_strong_hash_id();
+        // Basis is checked against delta header before anything is written
+        let delta_patcher = DeltaPatcher::new(header, &mut basis, chunk_size)?;
+        // Find basis ranges copied to the patched file and where differences go
+        let mut copies: Vec<InPlaceCopy> = Vec::new();
+        let mut literal_offsets: Vec<u64> = Vec::new();
+        let mut output_size = 0;
+        while let Some(chunk_delta) = delta_reader.next_chunk_delta()? {
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (start_index, count),
+                ChunkDelta::Diff(differences) => {
+                    literal_offsets.push(output_size);
+                    output_size += differences.len() as u64;
+                    continue;
+                }
+            };
+            if count == 0 {
+                continue;
+            }
+            let (start_offset, end_offset) = delta_patcher.get_chunk_range(start_index, count)?;
+            let copy = InPlaceCopy {
+                source_offset: start_offset,
+                target_offset: output_size,
+                length: end_offset - start_offset,
+            };
+            output_size += copy.length;
+            // Contiguous copies are merged into one copy
+            match copies.last_mut() {
+                Some(last_copy)
+                    if last_copy.source_end() == copy.source_offset
+                        && last_copy.target_end() == copy.target_offset =>
+                {
+                    last_copy.length += copy.length;
+                }
+                _ => copies.push(copy),
+            }
+        }
+        if delta_output_size.is_some_and(|delta_output_size| delta_output_size != output_size) {
+            return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+        }
+        // Copies to the same offset leave the basis unchanged
+        copies.retain(|copy| copy.source_offset != copy.target_offset);
+        let (ordered_copies, buffered_copies) = order_copies(&copies);
+        let mut steps: Vec<InPlaceStep> = ordered_copies
+            .iter()
+            .map(|copy| InPlaceStep::Copy {
+                source_offset: copy.source_offset,
+                target_offset: copy.target_offset,
+                length: copy.length,
+            })
+            .collect();
+        let mut data_offset = 0;
+        for copy in buffered_copies.iter() {
+            steps.push(InPlaceStep::Buffered {
+                data_offset,
+                target_offset: copy.target_offset,
+                length: copy.length,
+            });
+            data_offset += copy.length;
+        }
+        if !literal_offsets.is_empty() {
+            steps.push(InPlaceStep::Literals);
+        }
+        steps.push(InPlaceStep::SetLength(output_size));
+        let plan = InPlacePlan {
+            block_size: block_size as u64,
+            delta_output_size,
+            delta_digest,
+            strong_hash_id,
+            output_size,
+            literal_offsets,
+            steps,
+        };
+        // Journal is written to a temporary file and renamed once complete,
+        // so an interrupted journal creation leaves no journal behind
+        let temporary_file_name = format!("{}.tmp", journal_file_name);
+        let journal_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&temporary_file_name)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut patcher = InPlacePatcher {
+            basis,
+            journal_file,
+            plan,
+            progress: InPlaceProgress::default(),
+            staging_offset: 0,
+            buffer: vec![0; block_size],
+        };
+        let result = patcher.write_journal(&buffered_copies).and_then(|_| {
+            fs::rename(&temporary_file_name, journal_file_name)
+                .map_err(|e| RollingHashError::from(Box::new(e)))
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary_file_name);
+        }
+        result.map(|_| patcher)
+    }
+
+    // Resume an interrupted patch from its journal
+    fn open(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        journal_file_name: &str,
+    ) -> Result<InPlacePatcher, RollingHashError> {
+        let mut journal_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(journal_file_name)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let progress = read_journal_progress(&mut journal_file)?;
+        let plan_size: u64 = deserialize_from(&mut journal_file)
+            .map_err(|_| RollingHashError::new(INVALID_JOURNAL_FILE))?;
+        let plan: InPlacePlan = deserialize_from(BufReader::new(&mut journal_file))
+            .map_err(|_| RollingHashError::new(INVALID_JOURNAL_FILE))?;
+        if progress.step > plan.steps.len() as u64 || plan.block_size == 0 {
+            return Err(RollingHashError::new(INVALID_JOURNAL_FILE));
+        }
+        // Journal must have been created for this delta
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let delta_reader = DeltaReader::new(delta_file)?;
+        let header = delta_reader.get_header();
+        if header.get_output_size().map(|size| size as u64) != plan.delta_output_size
+            || header.get_digest() != plan.delta_digest.as_ref()
+        {
+            return Err(RollingHashError::new(INVALID_JOURNAL_FILE));
+        }
+        let basis = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(basis_file_name)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        Ok(InPlacePatcher {
+            basis,
+            journal_file,
+            buffer: vec![0; plan.block_size as usize],
+            plan,
+            progress,
+            staging_offset: get_staging_offset(plan_size),
+        })
+    }
+
+    fn write_journal(&mut self, buffered_copies: &[InPlaceCopy]) -> Result<(), RollingHashError> {
+        let plan_size =
+            serialized_size(&self.plan).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.staging_offset = get_staging_offset(plan_size);
+        let mut writer = BufWriter::new(&mut self.journal_file);
+        serialize_into(
+            &mut writer,
+            &(JOURNAL_MAGIC, JOURNAL_VERSION, self.progress),
+        )
+        .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut writer, &plan_size).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut writer, &self.plan).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        writer
+            .flush()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        drop(writer);
+        // Buffered copies are saved after the staging block, before the basis changes
+        let mut data_offset = self.staging_offset + self.plan.block_size;
+        for copy in buffered_copies.iter() {
+            let mut done_size = 0;
+            while done_size < copy.length {
+                let size = (copy.length - done_size).min(self.plan.block_size) as usize;
+                read_at(
+                    &mut self.basis,
+                    copy.source_offset + done_size,
+                    &mut self.buffer[..size],
+                )?;
+                write_at(&mut self.journal_file, data_offset, &self.buffer[..size])?;
+                data_offset += size as u64;
+                done_size += size as u64;
+            }
+        }
+        self.journal_file
+            .sync_all()
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    // Run next step of the plan, returns false once every step is done
+    fn run_step(&mut self, delta_file_name: &str) -> Result<bool, RollingHashError> {
+        let step = match self.plan.steps.get(self.progress.step as usize) {
+            Some(step) => step.clone(),
+            None => return Ok(false),
+        };
+        // Record the basis is about to change, it can not be rolled back afterwards
+        if self.progress.basis_changed == 0 {
+            self.progress.basis_changed = 1;
+            self.write_progress()?;
+        }
+        match step {
+            InPlaceStep::Copy {
+                source_offset,
+                target_offset,
+                length,
+            } => self.copy_basis_range(InPlaceCopy {
+                source_offset,
+                target_offset,
+                length,
+            })?,
+            InPlaceStep::Buffered {
+                data_offset,
+                target_offset,
+                length,
+            } => self.copy_buffered_data(data_offset, target_offset, length)?,
+            InPlaceStep::Literals => self.write_literals(delta_file_name)?,
+            InPlaceStep::SetLength(size) => self
+                .basis
+                .set_len(size)
+                .map_err(|e| RollingHashError::from(Box::new(e)))?,
+        }
+        // Step data must be on disk before the step is recorded as done
+        self.sync_basis()?;
+        self.progress = InPlaceProgress {
+            basis_changed: 1,
+            step: self.progress.step + 1,
+            ..InPlaceProgress::default()
+        };
+        self.write_progress()?;
+        Ok(true)
+    }
+
+    fn copy_basis_range(&mut self, copy: InPlaceCopy) -> Result<(), RollingHashError> {
+        let self_overlapping = copy.is_self_overlapping();
+        // Copies moving data forward are done from their end, so each block source
+        // is read before an earlier block overwrites it
+        let backward = copy.target_offset > copy.source_offset;
+        let mut done_size = self.progress.done_size;
+        while done_size < copy.length {
+            let size = (copy.length - done_size).min(self.plan.block_size) as usize;
+            let offset = if backward {
+                copy.length - done_size - size as u64
+            } else {
+                done_size
+            };
+            if !self_overlapping {
+                read_at(
+                    &mut self.basis,
+                    copy.source_offset + offset,
+                    &mut self.buffer[..size],
+                )?;
+            } else if self.progress.staged == 0 {
+                // Block target overlaps its source, stage the block in journal first
+                read_at(
+                    &mut self.basis,
+                    copy.source_offset + offset,
+                    &mut self.buffer[..size],
+                )?;
+                write_at(
+                    &mut self.journal_file,
+                    self.staging_offset,
+                    &self.buffer[..size],
+                )?;
+                self.progress.staged = 1;
+                self.write_progress()?;
+            } else {
+                read_at(
+                    &mut self.journal_file,
+                    self.staging_offset,
+                    &mut self.buffer[..size],
+                )?;
+            }
+            write_at(
+                &mut self.basis,
+                copy.target_offset + offset,
+                &self.buffer[..size],
+            )?;
+            done_size += size as u64;
+            // Blocks of self overlapping copies can not be copied twice
+            if self_overlapping {
+                self.sync_basis()?;
+                self.progress.done_size = done_size;
+                self.progress.staged = 0;
+                self.write_progress()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_buffered_data(
+        &mut self,
+        data_offset: u64,
+        target_offset: u64,
+        length: u64,
+    ) -> Result<(), RollingHashError> {
+        let journal_offset = self.staging_offset + self.plan.block_size + data_offset;
+        let mut done_size = 0;
+        while done_size < length {
+            let size = (length - done_size).min(self.plan.block_size) as usize;
+            read_at(
+                &mut self.journal_file,
+                journal_offset + done_size,
+                &mut self.buffer[..size],
+            )?;
+            write_at(
+                &mut self.basis,
+                target_offset + done_size,
+                &self.buffer[..size],
+            )?;
+            done_size += size as u64;
+        }
+        Ok(())
+    }
+
+    fn write_literals(&mut self, delta_file_name: &str) -> Result<(), RollingHashError> {
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut delta_reader = DeltaReader::new(delta_file)?;
+        let mut literal_offsets = self.plan.literal_offsets.iter();
+        while let Some(chunk_delta) = delta_reader.next_chunk_delta()? {
+            if let ChunkDelta::Diff(differences) = chunk_delta {
+                let literal_offset = literal_offsets
+                    .next()
+                    .ok_or_else(|| RollingHashError::new(INVALID_JOURNAL_FILE))?;
+                write_at(&mut self.basis, *literal_offset, &differences)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Check patched file size and digest are the delta ones
+    fn check_output(&mut self) -> Result<(), RollingHashError> {
+        let size = self
+            .basis
+            .metadata()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?
+            .len();
+        if size != self.plan.output_size {
+            return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+        }
+        if let (Some(strong_hash_id), Some(digest)) =
+            (self.plan.strong_hash_id, self.plan.delta_digest.as_ref())
+        {
+            let mut hasher = new_strong_hash_ptr(strong_hash_id)?.hasher();
+            self.basis
+                .seek(SeekFrom::Start(0))
+                .map_err(|e| RollingHashError::from(Box::new(e)))?;
+            loop {
+                let size = self
+                    .basis
+                    .read(&mut self.buffer)
+                    .map_err(|e| RollingHashError::from(Box::new(e)))?;
+                if size == 0 {
+                    break;
+                }
+                hasher.update(&self.buffer[..size]);
+            }
+            if hasher.finalize() != *digest {
+                return Err(RollingHashError::new(DELTA_DIGEST_MISMATCH));
+            }
+        }
+        Ok(())
+    }
+
+    fn sync_basis(&mut self) -> Result<(), RollingHashError> {
+        self.basis
+            .sync_data()
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    fn write_progress(&mut self) -> Result<(), RollingHashError> {
+        self.journal_file
+            .seek(SeekFrom::Start(JOURNAL_PROGRESS_OFFSET))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut self.journal_file, &self.progress)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.journal_file
+            .sync_data()
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+}
+
+impl Delta {
+    // Rewrite basis file into the patched file without a second copy, an
+    // interrupted patch is resumed when it is run again with the same delta
+    pub fn patch_file_in_place(
+        basis_file_name: &str,
+        delta_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        Delta::patch_file_in_place_with_block_size(
+            basis_file_name,
+            delta_file_name,
+            None,
+            IN_PLACE_BLOCK_SIZE,
+        )
+    }
+
+    // Chunk size is only needed for delta files that do not record it
+    pub fn patch_file_in_place_with_chunk_size(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        chunk_size: usize,
+    ) -> Result<(), RollingHashError> {
+        Delta::patch_file_in_place_with_block_size(
+            basis_file_name,
+            delta_file_name,
+            Some(chunk_size),
+            IN_PLACE_BLOCK_SIZE,
+        )
+    }
+
+    // Remove the journal of an interrupted in place patch, only possible before
+    // the first step overwrites basis file data, as the journal does not keep it
+    pub fn rollback_in_place_patch(basis_file_name: &str) -> Result<(), RollingHashError> {
+        let journal_file_name = get_journal_file_name(basis_file_name);
+        let mut journal_file =
+            File::open(&journal_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if read_journal_progress(&mut journal_file)?.basis_changed != 0 {
+            return Err(RollingHashError::new(IN_PLACE_ROLLBACK_UNAVAILABLE));
+        }
+        fs::remove_file(journal_file_name).map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    fn patch_file_in_place_with_block_size(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        chunk_size: Option<usize>,
+        block_size: usize,
+    ) -> Result<(), RollingHashError> {
+        // Delta file is read twice, differences are written after basis copies
+        if Delta::get_delta_file_format(delta_file_name)? != RDIFF_FORMAT {
+            return Err(RollingHashError::new(IN_PLACE_RDIFF_FORMAT_REQUIRED));
+        }
+        let journal_file_name = get_journal_file_name(basis_file_name);
+        let mut patcher = if Path::new(&journal_file_name).exists() {
+            InPlacePatcher::open(basis_file_name, delta_file_name, &journal_file_name)?
+        } else {
+            InPlacePatcher::create(
+                basis_file_name,
+                delta_file_name,
+                &journal_file_name,
+                chunk_size,
+                block_size,
+            )?
+        };
+        while patcher.run_step(delta_file_name)? {}
+        // Journal is kept when the patched file does not match the delta
+        patcher.check_output()?;
+        fs::remove_file(&journal_file_name).map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+}
+
+// Order copies so no copy reads a basis range after another copy overwrote it.
+// Copy a must run before copy b when a source overlaps b target, copies left
+// in a cycle are buffered, starting with the smallest one
+fn order_copies(copies: &[InPlaceCopy]) -> (Vec<InPlaceCopy>, Vec<InPlaceCopy>) {
+    // Targets do not overlap, so sorting them by offset also sorts their ends
+    let mut target_order: Vec<usize> = (0..copies.len()).collect();
+    target_order.sort_by_key(|index| copies[*index].target_offset);
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); copies.len()];
+    let mut predecessor_counts = vec![0; copies.len()];
+    for (index, copy) in copies.iter().enumerate() {
+        let first_position = target_order.partition_point(|target_index| {
+            copies[*target_index].target_end() <= copy.source_offset
+        });
+        for target_index in target_order[first_position..]
+            .iter()
+            .take_while(|target_index| copies[**target_index].target_offset < copy.source_end())
+        {
+            // Self overlapping copies are ordered block by block
+            if *target_index != index {
+                successors[index].push(*target_index);
+                predecessor_counts[*target_index] += 1;
+            }
+        }
+    }
+    let mut ready: Vec<usize> = (0..copies.len())
+        .filter(|index| predecessor_counts[*index] == 0)
+        .collect();
+    let mut done = vec![false; copies.len()];
+    let mut ordered_copies: Vec<InPlaceCopy> = Vec::new();
+    let mut buffered_copies: Vec<InPlaceCopy> = Vec::new();
+    while ordered_copies.len() + buffered_copies.len() < copies.len() {
+        // Buffered copies no longer read the basis, so they no longer have to
+        // run before the copies overwriting their source
+        let (index, buffered) = match ready.pop() {
+            Some(index) => (index, false),
+            None => match (0..copies.len())
+                .filter(|index| !done[*index])
+                .min_by_key(|index| copies[*index].length)
+            {
+                Some(index) => (index, true),
+                None => break,
+            },
+        };
+        done[index] = true;
+        for successor in successors[index].iter() {
+            predecessor_counts[*successor] -= 1;
+            if predecessor_counts[*successor] == 0 && !done[*successor] {
+                ready.push(*successor);
+            }
+        }
+        if buffered {
+            buffered_copies.push(copies[index]);
+        } else {
+            ordered_copies.push(copies[index]);
+        }
+    }
+    (ordered_copies, buffered_copies)
+}
+
+fn get_journal_file_name(basis_file_name: &str) -> String {
+    format!("{}{}", basis_file_name, JOURNAL_FILE_SUFFIX)
+}
+
+// Staging block comes after magic, version, progress, plan size and plan
+fn get_staging_offset(plan_size: u64) -> u64 {
+    JOURNAL_PROGRESS_OFFSET + 4 * 8 + 8 + plan_size
+}
+
+fn read_journal_progress(journal_file: &mut File) -> Result<InPlaceProgress, RollingHashError> {
+    let (magic, version, progress): (u32, u32, InPlaceProgress) =
+        deserialize_from(&mut *journal_file)
+            .map_err(|_| RollingHashError::new(INVALID_JOURNAL_FILE))?;
+    if magic != JOURNAL_MAGIC || version != JOURNAL_VERSION {
+        return Err(RollingHashError::new(INVALID_JOURNAL_FILE));
+    }
+    Ok(progress)
+}
+
+fn read_at(file: &mut File, offset: u64, buffer: &mut [u8]) -> Result<(), RollingHashError> {
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| RollingHashError::from(Box::new(e)))?;
+    file.read_exact(buffer)
+        .map_err(|e| RollingHashError::from(Box::new(e)))
+}
+
+fn write_at(file: &mut File, offset: u64, buffer: &[u8]) -> Result<(), RollingHashError> {
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| RollingHashError::from(Box::new(e)))?;
+    file.write_all(buffer)
+        .map_err(|e| RollingHashError::from(Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests;
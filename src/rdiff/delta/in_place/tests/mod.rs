@@ -0,0 +1,352 @@
+use std::{fs, path::Path};
+
+use crate::rdiff::{
+    delta::Delta,
+    error::{
+        messages::{DELTA_BASIS_MISMATCH, DELTA_DIGEST_MISMATCH, IN_PLACE_ROLLBACK_UNAVAILABLE},
+        RollingHashError,
+    },
+    hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
+    io::RdiffFile,
+    signature::Signature,
+    util::now_as_millis,
+};
+
+use super::{
+    get_journal_file_name, order_copies, write_at, InPlaceCopy, InPlacePatcher, InPlaceProgress,
+    InPlaceStep,
+};
+
+fn get_old_data() -> Vec<u8> {
+    // Pseudo random data, so chunks of different offsets do not match
+    let mut state: u32 = 12345;
+    (0..8192)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as u8
+        })
+        .collect()
+}
+
+fn get_new_data(old_data: &[u8]) -> Vec<u8> {
+    // Insert data at the start, so most chunks move forward
+    let mut new_data = b"inserted data".to_vec();
+    new_data.extend_from_slice(&old_data[..2048]);
+    // Swap two ranges, so their copies form a cycle
+    new_data.extend_from_slice(&old_data[4096..6144]);
+    new_data.extend_from_slice(&old_data[2048..4096]);
+    // Remove data at the end
+    new_data.extend_from_slice(&old_data[6144..8000]);
+    new_data
+}
+
+fn create_files(name: &str, old_data: &[u8], new_data: &[u8]) -> (String, String) {
+    let now = now_as_millis();
+    let basis_file_name = format!("resources/test_in_place_{}_basis_{}.txt", name, now);
+    let delta_file_name = format!("resources/test_in_place_{}_delta_{}.txt", name, now);
+    fs::write(&basis_file_name, old_data).unwrap();
+    let signature = Signature::create_signature_from_bytes(
+        old_data,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let mut delta_file = fs::File::create(&delta_file_name).unwrap();
+    Delta::write_delta_from_rdiff_file(
+        RdiffFile::new_with_bytes("new", new_data),
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+        None,
+        &mut delta_file,
+    )
+    .unwrap();
+    (basis_file_name, delta_file_name)
+}
+
+#[test]
+fn test_delta_patch_file_in_place_case1() {
+    let old_data = get_old_data();
+    let new_data = get_new_data(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("case1", &old_data, &new_data);
+
+    // Rewrite basis into the new version
+    Delta::patch_file_in_place(&basis_file_name, &delta_file_name).unwrap();
+
+    // Verify computed values
+    assert_eq!(fs::read(&basis_file_name).unwrap(), new_data);
+    assert!(!Path::new(&get_journal_file_name(&basis_file_name)).exists());
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_patch_file_in_place_case2() {
+    // New data is longer than basis
+    let old_data = get_old_data();
+    let mut new_data = old_data[1024..].to_vec();
+    new_data.extend_from_slice(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("case2", &old_data, &new_data);
+
+    // Small blocks, so moved copies are staged one block at a time
+    Delta::patch_file_in_place_with_block_size(&basis_file_name, &delta_file_name, None, 100)
+        .unwrap();
+
+    // Verify computed values
+    assert_eq!(fs::read(&basis_file_name).unwrap(), new_data);
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_patch_file_in_place_resume_case1() {
+    let old_data = get_old_data();
+    let new_data = get_new_data(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("resume", &old_data, &new_data);
+    let journal_file_name = get_journal_file_name(&basis_file_name);
+    let step_count = {
+        let patcher = InPlacePatcher::create(
+            &basis_file_name,
+            &delta_file_name,
+            &journal_file_name,
+            None,
+            100,
+        )
+        .unwrap();
+        // Swapped ranges are buffered
+        assert!(patcher
+            .plan
+            .steps
+            .iter()
+            .any(|step| matches!(step, InPlaceStep::Buffered { .. })));
+        patcher.plan.steps.len()
+    };
+    fs::remove_file(&journal_file_name).unwrap();
+
+    // Interrupt the patch after each step and resume it
+    for interrupted_step in 0..step_count {
+        fs::write(&basis_file_name, &old_data).unwrap();
+        let mut patcher = InPlacePatcher::create(
+            &basis_file_name,
+            &delta_file_name,
+            &journal_file_name,
+            None,
+            100,
+        )
+        .unwrap();
+        for _ in 0..interrupted_step {
+            assert!(patcher.run_step(&delta_file_name).unwrap());
+        }
+        drop(patcher);
+        Delta::patch_file_in_place(&basis_file_name, &delta_file_name).unwrap();
+
+        // Verify computed values
+        assert_eq!(fs::read(&basis_file_name).unwrap(), new_data);
+        assert!(!Path::new(&journal_file_name).exists());
+    }
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_patch_file_in_place_resume_case2() {
+    // Every block of the copy moved forward overlaps its own source
+    let old_data = get_old_data();
+    let mut new_data = b"x".to_vec();
+    new_data.extend_from_slice(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("resume2", &old_data, &new_data);
+    let journal_file_name = get_journal_file_name(&basis_file_name);
+    let mut patcher = InPlacePatcher::create(
+        &basis_file_name,
+        &delta_file_name,
+        &journal_file_name,
+        None,
+        100,
+    )
+    .unwrap();
+    let length = old_data.len();
+    assert_eq!(
+        patcher.plan.steps[0],
+        InPlaceStep::Copy {
+            source_offset: 0,
+            target_offset: 1,
+            length: length as u64,
+        }
+    );
+
+    // Interrupt the copy while writing its fourth block, once it is staged,
+    // blocks are copied from the end of the copy
+    let mut basis_data = old_data.clone();
+    basis_data.push(0);
+    basis_data[1 + length - 300..].copy_from_slice(&new_data[1 + length - 300..]);
+    basis_data[1 + length - 400..1 + length - 350].fill(0xFF);
+    fs::write(&basis_file_name, &basis_data).unwrap();
+    let staging_offset = patcher.staging_offset;
+    write_at(
+        &mut patcher.journal_file,
+        staging_offset,
+        &old_data[length - 400..length - 300],
+    )
+    .unwrap();
+    patcher.progress = InPlaceProgress {
+        basis_changed: 1,
+        step: 0,
+        done_size: 300,
+        staged: 1,
+    };
+    patcher.write_progress().unwrap();
+    drop(patcher);
+
+    // Resume from the staged block
+    Delta::patch_file_in_place(&basis_file_name, &delta_file_name).unwrap();
+
+    // Verify computed values
+    assert_eq!(fs::read(&basis_file_name).unwrap(), new_data);
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_rollback_in_place_patch_case1() {
+    let old_data = get_old_data();
+    let new_data = get_new_data(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("rollback", &old_data, &new_data);
+    let journal_file_name = get_journal_file_name(&basis_file_name);
+
+    // Journal can be removed while basis is unchanged
+    let patcher = InPlacePatcher::create(
+        &basis_file_name,
+        &delta_file_name,
+        &journal_file_name,
+        None,
+        100,
+    )
+    .unwrap();
+    drop(patcher);
+    Delta::rollback_in_place_patch(&basis_file_name).unwrap();
+    assert!(!Path::new(&journal_file_name).exists());
+    assert_eq!(fs::read(&basis_file_name).unwrap(), old_data);
+
+    // Once basis changed the patch can only be resumed
+    let mut patcher = InPlacePatcher::create(
+        &basis_file_name,
+        &delta_file_name,
+        &journal_file_name,
+        None,
+        100,
+    )
+    .unwrap();
+    assert!(patcher.run_step(&delta_file_name).unwrap());
+    drop(patcher);
+    assert_eq!(
+        Delta::rollback_in_place_patch(&basis_file_name),
+        Err(RollingHashError::new(IN_PLACE_ROLLBACK_UNAVAILABLE))
+    );
+    Delta::patch_file_in_place(&basis_file_name, &delta_file_name).unwrap();
+    assert_eq!(fs::read(&basis_file_name).unwrap(), new_data);
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_patch_file_in_place_basis_mismatch_case1() {
+    let old_data = get_old_data();
+    let new_data = get_new_data(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("mismatch", &old_data, &new_data);
+
+    // Basis is checked before anything is written
+    let mut other_data = old_data.clone();
+    other_data[10] ^= 1;
+    fs::write(&basis_file_name, &other_data).unwrap();
+    let result = Delta::patch_file_in_place(&basis_file_name, &delta_file_name);
+    assert_eq!(result, Err(RollingHashError::new(DELTA_BASIS_MISMATCH)));
+    assert_eq!(fs::read(&basis_file_name).unwrap(), other_data);
+    assert!(!Path::new(&get_journal_file_name(&basis_file_name)).exists());
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_patch_file_in_place_digest_mismatch_case1() {
+    let old_data = get_old_data();
+    let new_data = get_new_data(&old_data);
+    let (basis_file_name, delta_file_name) = create_files("digest", &old_data, &new_data);
+    let journal_file_name = get_journal_file_name(&basis_file_name);
+
+    // Change basis data copied after the journal is created
+    let patcher = InPlacePatcher::create(
+        &basis_file_name,
+        &delta_file_name,
+        &journal_file_name,
+        None,
+        100,
+    )
+    .unwrap();
+    drop(patcher);
+    let mut other_data = old_data.clone();
+    other_data[7000] ^= 1;
+    fs::write(&basis_file_name, &other_data).unwrap();
+
+    // Journal is kept when the patched file does not match the delta
+    let result = Delta::patch_file_in_place(&basis_file_name, &delta_file_name);
+    assert_eq!(result, Err(RollingHashError::new(DELTA_DIGEST_MISMATCH)));
+    assert!(Path::new(&journal_file_name).exists());
+
+    // Remove test files
+    fs::remove_file(basis_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(journal_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_order_copies_case1() {
+    // Copy reading a range must run before the copy overwriting it
+    let first_copy = InPlaceCopy {
+        source_offset: 0,
+        target_offset: 100,
+        length: 100,
+    };
+    let second_copy = InPlaceCopy {
+        source_offset: 100,
+        target_offset: 200,
+        length: 100,
+    };
+    let (ordered_copies, buffered_copies) = order_copies(&[first_copy, second_copy]);
+    assert_eq!(ordered_copies, vec![second_copy, first_copy]);
+    assert!(buffered_copies.is_empty());
+}
+
+#[test]
+fn test_delta_order_copies_case2() {
+    // Swapped ranges form cycles, smallest copies are buffered until none is left
+    let first_copy = InPlaceCopy {
+        source_offset: 100,
+        target_offset: 0,
+        length: 100,
+    };
+    let second_copy = InPlaceCopy {
+        source_offset: 0,
+        target_offset: 100,
+        length: 50,
+    };
+    let third_copy = InPlaceCopy {
+        source_offset: 50,
+        target_offset: 150,
+        length: 50,
+    };
+    let (ordered_copies, buffered_copies) = order_copies(&[first_copy, second_copy, third_copy]);
+    assert_eq!(ordered_copies, vec![first_copy]);
+    assert_eq!(buffered_copies, vec![second_copy, third_copy]);
+}
@@ -0,0 +1,222 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::rdiff::{
+    constants::{LIBRSYNC_DELTA_MAGIC, READ_BLOCK_SIZE},
+    error::{messages::INVALID_DELTA_FILE, RollingHashError},
+};
+
+use super::{header::DeltaHeader, ByteOperation, ChunkDelta, Delta};
+
+// librsync delta operations, numbers are big endian and 1, 2, 4 or 8 bytes long
+const LIBRSYNC_OP_END: u8 = 0x00;
+// Literals up to 64 bytes long have their length in the operation code
+const LIBRSYNC_OP_LITERAL_1: u8 = 0x01;
+const LIBRSYNC_OP_LITERAL_64: u8 = 0x40;
+// Longer literals are followed by their length
+const LIBRSYNC_OP_LITERAL_N1: u8 = 0x41;
+const LIBRSYNC_OP_LITERAL_N8: u8 = 0x44;
+// Copies are followed by basis offset and length,
+// operation code is LIBRSYNC_OP_COPY_N1_N1 + 4 * offset size index + length size index
+const LIBRSYNC_OP_COPY_N1_N1: u8 = 0x45;
+const LIBRSYNC_OP_COPY_N8_N8: u8 = 0x54;
+const LIBRSYNC_INT_SIZES: [usize; 4] = [1, 2, 4, 8];
+
+// librsync delta file format: magic number followed by literal and copy
+// operations with basis byte offsets, and an end operation
+impl Delta {
+    pub fn is_librsync_delta(data: &[u8]) -> bool {
+        data.len() >= 4 && data[..4] == LIBRSYNC_DELTA_MAGIC.to_be_bytes()
+    }
+
+    pub fn write_librsync_delta<W: Write>(&self, writer: &mut W) -> Result<(), RollingHashError> {
+        // Chunk geometry is needed to get basis byte offsets from chunk indexes
+        let byte_operations = self.get_byte_operations()?;
+        Delta::write_librsync_data(writer, &LIBRSYNC_DELTA_MAGIC.to_be_bytes())?;
+        // Init operation data
+        let mut data: Vec<u8> = Vec::new();
+        for byte_operation in byte_operations {
+            data.clear();
+            match byte_operation {
+                ByteOperation::Literal(differences) => {
+                    // Literal operation is followed by the differences
+                    Delta::push_librsync_literal(&mut data, differences.len() as u64);
+                    Delta::write_librsync_data(writer, &data)?;
+                    Delta::write_librsync_data(writer, differences)?;
+                }
+                ByteOperation::Copy { offset, length } => {
+                    Delta::push_librsync_copy(&mut data, offset as u64, length as u64);
+                    Delta::write_librsync_data(writer, &data)?;
+                }
+            }
+        }
+        Delta::write_librsync_data(writer, &[LIBRSYNC_OP_END])
+    }
+
+    fn write_librsync_data<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), RollingHashError> {
+        writer
+            .write_all(data)
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    fn push_librsync_literal(data: &mut Vec<u8>, length: u64) {
+        if length <= LIBRSYNC_OP_LITERAL_64 as u64 {
+            data.push(LIBRSYNC_OP_LITERAL_1 + length as u8 - 1);
+        } else {
+            let size_index = Delta::get_librsync_int_size_index(length);
+            data.push(LIBRSYNC_OP_LITERAL_N1 + size_index as u8);
+            Delta::push_librsync_int(data, length, size_index);
+        }
+    }
+
+    fn push_librsync_copy(data: &mut Vec<u8>, offset: u64, length: u64) {
+        let offset_size_index = Delta::get_librsync_int_size_index(offset);
+        let length_size_index = Delta::get_librsync_int_size_index(length);
+        data.push(LIBRSYNC_OP_COPY_N1_N1 + (4 * offset_size_index + length_size_index) as u8);
+        Delta::push_librsync_int(data, offset, offset_size_index);
+        Delta::push_librsync_int(data, length, length_size_index);
+    }
+
+    fn get_librsync_int_size_index(value: u64) -> usize {
+        // Smallest number size able to store the value
+        LIBRSYNC_INT_SIZES
+            .iter()
+            .position(|size| *size == 8 || value >> (8 * size) == 0)
+            .unwrap()
+    }
+
+    fn push_librsync_int(data: &mut Vec<u8>, value: u64, size_index: usize) {
+        let size = LIBRSYNC_INT_SIZES[size_index];
+        data.extend_from_slice(&value.to_be_bytes()[8 - size..]);
+    }
+
+    // Copies of basis byte ranges are split in chunks of chunk size, so they
+    // must start at a chunk and only the basis last chunk may be shorter
+    pub fn read_librsync_delta<R: Read>(
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        Delta::read_librsync_delta_with_optional_basis(reader, None::<&mut io::Empty>, chunk_size)
+    }
+
+    // Copies of basis byte ranges are split in chunks of chunk size, parts of
+    // chunks they start or end in are read from basis as differences
+    pub fn read_librsync_delta_with_basis<R: Read, B: Read + Seek>(
+        reader: &mut R,
+        basis: &mut B,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        Delta::read_librsync_delta_with_optional_basis(reader, Some(basis), chunk_size)
+    }
+
+    fn read_librsync_delta_with_optional_basis<R: Read, B: Read + Seek>(
+        reader: &mut R,
+        basis: Option<&mut B>,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        // Check magic number
+        let mut magic = [0_u8; 4];
+        Delta::read_librsync_bytes(reader, &mut magic)?;
+        if !Delta::is_librsync_delta(&magic) || chunk_size == 0 {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        // Init chunk delta list, patched data size and basis last chunk size.
+        // Without basis, last chunk size is only known when the basis last chunk is copied
+        let mut basis = match basis {
+            Some(basis) => {
+                let basis_size = basis
+                    .seek(SeekFrom::End(0))
+                    .map_err(|e| RollingHashError::from(Box::new(e)))?;
+                Some((basis, basis_size))
+            }
+            None => None,
+        };
+        let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+        let mut output_size: usize = 0;
+        let mut last_chunk_size: usize = match basis {
+            Some((_, basis_size)) => Delta::get_basis_last_chunk_size(basis_size, chunk_size),
+            None => 0,
+        };
+        let mut buffer: Vec<u8> = vec![0; READ_BLOCK_SIZE];
+        loop {
+            let mut op = [0_u8; 1];
+            Delta::read_librsync_bytes(reader, &mut op)?;
+            match op[0] {
+                LIBRSYNC_OP_END => break,
+                LIBRSYNC_OP_LITERAL_1..=LIBRSYNC_OP_LITERAL_N8 => {
+                    // Get literal length
+                    let length = if op[0] <= LIBRSYNC_OP_LITERAL_64 {
+                        (op[0] - LIBRSYNC_OP_LITERAL_1) as usize + 1
+                    } else {
+                        let size_index = (op[0] - LIBRSYNC_OP_LITERAL_N1) as usize;
+                        Delta::read_librsync_int(reader, size_index)?
+                    };
+                    // Get literal
+                    let mut differences: Vec<u8> = Vec::new();
+                    reader
+                        .take(length as u64)
+                        .read_to_end(&mut differences)
+                        .map_err(|e| RollingHashError::from(Box::new(e)))?;
+                    if differences.len() != length {
+                        return Err(RollingHashError::new(INVALID_DELTA_FILE));
+                    }
+                    Delta::push_literal(&mut chunk_delta_list, &differences);
+                    output_size += length;
+                }
+                LIBRSYNC_OP_COPY_N1_N1..=LIBRSYNC_OP_COPY_N8_N8 => {
+                    // Get basis offset and length
+                    let size_indexes = (op[0] - LIBRSYNC_OP_COPY_N1_N1) as usize;
+                    let offset = Delta::read_librsync_int(reader, size_indexes / 4)?;
+                    let length = Delta::read_librsync_int(reader, size_indexes % 4)?;
+                    match basis.as_mut() {
+                        Some((basis, basis_size)) => Delta::push_basis_copy(
+                            &mut chunk_delta_list,
+                            *basis,
+                            *basis_size,
+                            offset,
+                            length,
+                            chunk_size,
+                            &mut buffer,
+                        )?,
+                        None => Delta::push_byte_copy(
+                            &mut chunk_delta_list,
+                            offset,
+                            length,
+                            chunk_size,
+                            &mut last_chunk_size,
+                        )?,
+                    }
+                    output_size += length;
+                }
+                _ => return Err(RollingHashError::new(INVALID_DELTA_FILE)),
+            }
+        }
+        let header = DeltaHeader::new_without_digest(chunk_size, last_chunk_size, output_size);
+        Ok(Delta {
+            header,
+            chunk_delta_list,
+        })
+    }
+
+    fn read_librsync_int<R: Read>(
+        reader: &mut R,
+        size_index: usize,
+    ) -> Result<usize, RollingHashError> {
+        let size = LIBRSYNC_INT_SIZES[size_index];
+        let mut data = [0_u8; 8];
+        Delta::read_librsync_bytes(reader, &mut data[8 - size..])?;
+        usize::try_from(u64::from_be_bytes(data))
+            .map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))
+    }
+
+    fn read_librsync_bytes<R: Read>(
+        reader: &mut R,
+        buffer: &mut [u8],
+    ) -> Result<(), RollingHashError> {
+        reader
+            .read_exact(buffer)
+            .map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,245 @@
+use std::io::Cursor;
+
+use crate::rdiff::{
+    chunk::iterator::BufferedRdiffChunkIterator,
+    constants::LIBRSYNC_DELTA_MAGIC,
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{
+            DELTA_BASIS_MISMATCH, DELTA_GEOMETRY_UNKNOWN, INVALID_CHUNK_INDEX, INVALID_DELTA_FILE,
+            UNALIGNED_DELTA_COPY,
+        },
+        RollingHashError,
+    },
+    io::RdiffFile,
+    signature::header::BasisFingerprint,
+};
+
+#[test]
+fn test_delta_write_librsync_delta_case1() {
+    // Create delta with short and long literals and copies
+    let long_differences = vec![b'x'; 300];
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Diff(vec![b'a', b'b']),
+        ChunkDelta::Match(1),
+        ChunkDelta::Diff(long_differences.clone()),
+        ChunkDelta::CopyRange {
+            start_index: 101,
+            count: 2,
+        },
+    ];
+    let basis = BasisFingerprint::new(101 * 4 + 3, vec![1; 20]);
+    let header = DeltaHeader::new(4, 3, 2 + 4 + 300 + 7, 0, Vec::new(), Some(basis));
+    let delta = Delta::new(header, chunk_delta_list);
+
+    // Write delta in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    delta.write_librsync_delta(&mut data).unwrap();
+
+    // Verify computed values, last copy is shorter because of the basis last chunk
+    let mut expected_data: Vec<u8> = Vec::new();
+    expected_data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    expected_data.extend_from_slice(&[0x02, b'a', b'b']);
+    expected_data.extend_from_slice(&[0x45, 0x00, 0x04]);
+    expected_data.extend_from_slice(&[0x42, 0x01, 0x2c]);
+    expected_data.extend_from_slice(&long_differences);
+    expected_data.extend_from_slice(&[0x49, 0x01, 0x90, 0x07]);
+    expected_data.push(0x00);
+    assert_eq!(data, expected_data);
+    assert!(Delta::is_librsync_delta(&data));
+}
+
+#[test]
+fn test_delta_write_librsync_delta_case2() {
+    // Delta without chunk geometry can not be written
+    let delta = Delta::new(DeltaHeader::legacy(), vec![ChunkDelta::Match(1)]);
+    let mut data: Vec<u8> = Vec::new();
+    let error = delta.write_librsync_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_GEOMETRY_UNKNOWN));
+}
+
+#[test]
+fn test_delta_write_librsync_delta_case3() {
+    // Create composed delta copying the basis last chunk before other chunks
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(102),
+        ChunkDelta::Diff(vec![b'a', b'b']),
+        ChunkDelta::Match(1),
+    ];
+    let basis = BasisFingerprint::new(101 * 4 + 3, vec![1; 20]);
+    let header = DeltaHeader::new(4, 3, 3 + 2 + 4, 0, Vec::new(), Some(basis));
+    let delta = Delta::new(header, chunk_delta_list);
+
+    // Write delta in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    delta.write_librsync_delta(&mut data).unwrap();
+
+    // Verify only the copy of the basis last chunk is shorter
+    let mut expected_data: Vec<u8> = Vec::new();
+    expected_data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    expected_data.extend_from_slice(&[0x49, 0x01, 0x94, 0x03]);
+    expected_data.extend_from_slice(&[0x02, b'a', b'b']);
+    expected_data.extend_from_slice(&[0x45, 0x00, 0x04]);
+    expected_data.push(0x00);
+    assert_eq!(data, expected_data);
+}
+
+#[test]
+fn test_delta_write_librsync_delta_case4() {
+    // Copies past the basis last chunk can not be written
+    let basis = BasisFingerprint::new(101 * 4 + 3, vec![1; 20]);
+    let header = DeltaHeader::new(4, 3, 8, 0, Vec::new(), Some(basis));
+    let delta = Delta::new(
+        header,
+        vec![ChunkDelta::CopyRange {
+            start_index: 102,
+            count: 2,
+        }],
+    );
+    let mut data: Vec<u8> = Vec::new();
+    let error = delta.write_librsync_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_INDEX));
+
+    // Copies can not be written when the basis last chunk is shorter but
+    // the basis size is unknown
+    let header = DeltaHeader::new_without_digest(4, 3, 4);
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    let mut data: Vec<u8> = Vec::new();
+    let error = delta.write_librsync_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_GEOMETRY_UNKNOWN));
+}
+
+#[test]
+fn test_delta_read_librsync_delta_case1() {
+    // Create delta with split literals and copies of a basis with
+    // chunk size 2 and last chunk size 1
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x01, b'x']);
+    data.extend_from_slice(&[0x41, 0x01, b'y']);
+    data.extend_from_slice(&[0x45, 0x00, 0x02]);
+    data.extend_from_slice(&[0x45, 0x02, 0x01]);
+    data.push(0x00);
+
+    // Read delta
+    let delta = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap();
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Diff(vec![b'x', b'y']),
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 2,
+        },
+    ];
+    assert_eq!(delta.get_chunk_delta_list(), &expected_chunk_delta_list);
+    assert_eq!(delta.get_header().get_chunk_size(), Some(2));
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(1));
+    assert_eq!(delta.get_header().get_output_size(), Some(5));
+    assert_eq!(delta.get_header().get_strong_hash_id(), None);
+
+    // Apply delta
+    let rdiff_file = RdiffFile::new("resources/buffered_rdiff_chunk_iterator_file1.txt").unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply(&mut iterator, &mut output).unwrap();
+    assert_eq!(output, b"xyaaa");
+}
+
+#[test]
+fn test_delta_read_librsync_delta_case2() {
+    // Write delta in librsync format and read it back
+    let get_chunk_delta_list = || -> Vec<ChunkDelta> {
+        vec![
+            ChunkDelta::CopyRange {
+                start_index: 2,
+                count: 3,
+            },
+            ChunkDelta::Diff(vec![b'z'; 100]),
+            ChunkDelta::Match(1),
+        ]
+    };
+    let header = DeltaHeader::new_without_digest(8, 8, 3 * 8 + 100 + 8);
+    let delta = Delta::new(header, get_chunk_delta_list());
+    let mut data: Vec<u8> = Vec::new();
+    delta.write_librsync_delta(&mut data).unwrap();
+    let delta = Delta::read_librsync_delta(&mut Cursor::new(data), 8).unwrap();
+
+    // Verify computed values, full last chunk is not detected
+    assert_eq!(delta.get_chunk_delta_list(), &get_chunk_delta_list());
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(0));
+    assert_eq!(delta.get_header().get_output_size(), Some(132));
+}
+
+#[test]
+fn test_delta_read_librsync_delta_with_basis_case1() {
+    // Create delta copying bcd from basis abcdef and adding x, copy is not aligned
+    // to chunk size 2
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x45, 0x01, 0x03, 0x01, b'x', 0x00]);
+
+    // Parts of basis chunks are read from basis
+    let mut basis = Cursor::new(b"abcdef".to_vec());
+    let delta =
+        Delta::read_librsync_delta_with_basis(&mut Cursor::new(data.clone()), &mut basis, 2)
+            .unwrap();
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Diff(b"b".to_vec()),
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(b"x".to_vec()),
+    ];
+    assert_eq!(delta.get_chunk_delta_list(), &expected_chunk_delta_list);
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(2));
+    assert_eq!(delta.get_header().get_output_size(), Some(4));
+
+    // Apply delta
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply_to_basis(&mut basis, &mut output).unwrap();
+    assert_eq!(output, b"bcdx");
+
+    // Copy past the end of basis
+    let mut basis = Cursor::new(b"abc".to_vec());
+    let error =
+        Delta::read_librsync_delta_with_basis(&mut Cursor::new(data), &mut basis, 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+}
+
+#[test]
+fn test_delta_read_librsync_delta_errors_case1() {
+    // Bad magic number
+    let data = vec![0x72, 0x73, 0x01, 0x36, 0x00];
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+
+    // Copy not aligned to chunk size
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x45, 0x01, 0x02, 0x00]);
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNALIGNED_DELTA_COPY));
+
+    // Copies with different last chunk sizes
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x45, 0x00, 0x05, 0x45, 0x00, 0x06, 0x00]);
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 4).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNALIGNED_DELTA_COPY));
+
+    // Truncated literal and missing end operation
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x03, b'a']);
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.extend_from_slice(&[0x01, b'a']);
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+
+    // Unknown operation
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+    data.push(0x55);
+    let error = Delta::read_librsync_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+}
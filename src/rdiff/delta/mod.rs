@@ -1,31 +1,93 @@
-use std::{fs::File, io::BufWriter};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Seek, Write},
+};
 
 use bincode::serialize_into;
 use serde::{Deserialize, Serialize};
 
 use super::chunk::iterator::RdiffChunkIterator;
 use super::{
-    chunk::iterator::BufferedRdiffChunkIterator,
-    error::{messages::DELTA_PROCESSED_DATA_SIZE_ERROR, RollingHashError},
-    hash::{strong::StrongHashPtr, weak::WeakHashPtr},
-    io::RdiffFile,
+    chunk::{
+        iterator::{fastcdc::FastCdcChunkIterator, BufferedRdiffChunkIterator},
+        RdiffChecksum, RdiffChunk, RdiffDigest,
+    },
+    compression::{new_compression_ptr, CompressionPtr},
+    constants::{LIBRSYNC_FORMAT, MAX_DIFF_SIZE, RDIFF_FORMAT, STDIN_FILE_NAME, VCDIFF_FORMAT},
+    error::{
+        messages::{
+            DELTA_BASIS_MISMATCH, DELTA_CHUNK_SIZE_MISMATCH, DELTA_DIGEST_MISMATCH,
+            DELTA_GEOMETRY_UNKNOWN, DELTA_OUTPUT_SIZE_ERROR, DELTA_PROCESSED_DATA_SIZE_ERROR,
+            FIXED_SIZE_CHUNKS_REQUIRED, INVALID_CHUNK_INDEX, INVALID_DELTA_FILE,
+            UNALIGNED_DELTA_COPY,
+        },
+        RollingHashError,
+    },
+    hash::{
+        strong::{new_strong_hash_ptr, StrongHashPtr, StrongHasherPtr},
+        weak::{RollingWeakHashPtr, WeakHashPtr},
+    },
+    io::{window::RdiffWindow, RdiffFile},
     signature::Signature,
 };
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+use self::{
+    compose::ChunkGeometry,
+    header::DeltaHeader,
+    patch::DeltaPatcher,
+    reader::DeltaReader,
+    stats::DeltaStats,
+    writer::{DeltaSink, DeltaWriter, RdiffDeltaSink},
+};
+
+// Copy ranges are contiguous ascending match chunk deltas,
+// from chunk index start_index to chunk index start_index + count - 1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ChunkDelta {
     Match(u32),
     Diff(Vec<u8>),
+    CopyRange { start_index: u32, count: u32 },
+}
+
+// Patched data operations with basis byte offsets instead of chunk indexes,
+// used by delta formats without chunks
+#[derive(Debug, PartialEq)]
+enum ByteOperation<'a> {
+    Literal(&'a [u8]),
+    Copy { offset: usize, length: usize },
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub mod compose;
+pub mod header;
+pub mod in_place;
+pub mod librsync;
+pub mod patch;
+pub mod patched_reader;
+pub mod reader;
+pub mod stats;
+pub mod vcdiff;
+pub mod writer;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Delta {
+    header: DeltaHeader,
     chunk_delta_list: Vec<ChunkDelta>,
 }
 
 impl Delta {
-    pub fn new(chunk_delta_list: Vec<ChunkDelta>) -> Delta {
-        Delta { chunk_delta_list }
+    pub fn new(header: DeltaHeader, chunk_delta_list: Vec<ChunkDelta>) -> Delta {
+        Delta {
+            header,
+            chunk_delta_list,
+        }
+    }
+
+    pub fn get_header(&self) -> &DeltaHeader {
+        &self.header
+    }
+
+    pub fn get_chunk_delta_list(&self) -> &Vec<ChunkDelta> {
+        &self.chunk_delta_list
     }
 
     pub fn create_delta_file(
@@ -37,15 +99,506 @@ impl Delta {
     ) -> Result<(), RollingHashError> {
         // Get signature
         let signature = Signature::get_signature_from_file(signature_file_name)?;
-        // Get delta
-        let delta = Delta::generate_delta(file_name, signature, weak_hash_ptr, strong_hash_ptr)?;
-        // Write serialized delta to file
+        // Write delta while it is generated
+        Delta::write_delta_file_from_signature(
+            file_name,
+            delta_file_name,
+            signature,
+            weak_hash_ptr,
+            strong_hash_ptr,
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn create_delta_file_from_signature(
+        file_name: &str,
+        delta_file_name: &str,
+        signature_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Get signature
+        let signature = Signature::get_signature_from_file(signature_file_name)?;
+        // Get hash functions used to create the signature
+        let weak_hash_ptr = signature.get_header().get_weak_hash_ptr()?;
+        let strong_hash_ptr = signature.get_header().get_strong_hash_ptr()?;
+        // Write delta while it is generated
+        Delta::write_delta_file_from_signature(
+            file_name,
+            delta_file_name,
+            signature,
+            weak_hash_ptr,
+            strong_hash_ptr,
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn create_delta_file_from_signature_with_compression(
+        file_name: &str,
+        delta_file_name: &str,
+        signature_file_name: &str,
+        compression_ptr: CompressionPtr,
+    ) -> Result<DeltaStats, RollingHashError> {
+        // Get signature
+        let signature = Signature::get_signature_from_file(signature_file_name)?;
+        // Get hash functions used to create the signature
+        let weak_hash_ptr = signature.get_header().get_weak_hash_ptr()?;
+        let strong_hash_ptr = signature.get_header().get_strong_hash_ptr()?;
+        // Write delta while it is generated, with compressed differences
+        Delta::write_delta_file_from_signature(
+            file_name,
+            delta_file_name,
+            signature,
+            weak_hash_ptr,
+            strong_hash_ptr,
+            Some(compression_ptr),
+        )
+    }
+
+    fn write_delta_file_from_signature(
+        file_name: &str,
+        delta_file_name: &str,
+        signature: Signature,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+        compression_ptr: Option<CompressionPtr>,
+    ) -> Result<DeltaStats, RollingHashError> {
+        // Check hash functions and new file before creating delta file
+        signature
+            .get_header()
+            .validate_hashes(&weak_hash_ptr, &strong_hash_ptr)?;
+        let rdiff_file = Delta::get_rdiff_file(file_name)?;
+        let delta_file =
+            File::create(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let result = Delta::write_delta_from_rdiff_file(
+            rdiff_file,
+            signature,
+            weak_hash_ptr,
+            strong_hash_ptr,
+            compression_ptr,
+            BufWriter::new(delta_file),
+        );
+        // Do not leave an incomplete delta file behind
+        if result.is_err() {
+            let _ = fs::remove_file(delta_file_name);
+        }
+        result
+    }
+
+    pub fn create_librsync_delta_file_from_signature(
+        file_name: &str,
+        delta_file_name: &str,
+        signature_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Get delta and write it to file in librsync format
+        let delta = Delta::generate_delta_from_signature_file(file_name, signature_file_name)?;
         let delta_file =
-            File::create(delta_file_name).or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
-        let mut delta_writer = BufWriter::new(delta_file);
-        serialize_into(&mut delta_writer, &delta)
-            .or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
+            File::create(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let result = {
+            let mut delta_writer = BufWriter::new(delta_file);
+            delta.write_librsync_delta(&mut delta_writer).and_then(|_| {
+                delta_writer
+                    .flush()
+                    .map_err(|e| RollingHashError::from(Box::new(e)))
+            })
+        };
+        // Do not leave an incomplete delta file behind
+        if result.is_err() {
+            let _ = fs::remove_file(delta_file_name);
+        }
+        result
+    }
+
+    pub fn create_vcdiff_delta_file_from_signature(
+        file_name: &str,
+        delta_file_name: &str,
+        signature_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Get delta and write it to file in VCDIFF format
+        let delta = Delta::generate_delta_from_signature_file(file_name, signature_file_name)?;
+        let delta_file =
+            File::create(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let result = {
+            let mut delta_writer = BufWriter::new(delta_file);
+            delta.write_vcdiff_delta(&mut delta_writer).and_then(|_| {
+                delta_writer
+                    .flush()
+                    .map_err(|e| RollingHashError::from(Box::new(e)))
+            })
+        };
+        // Do not leave an incomplete delta file behind
+        if result.is_err() {
+            let _ = fs::remove_file(delta_file_name);
+        }
+        result
+    }
+
+    fn generate_delta_from_signature_file(
+        file_name: &str,
+        signature_file_name: &str,
+    ) -> Result<Delta, RollingHashError> {
+        // Get signature
+        let signature = Signature::get_signature_from_file(signature_file_name)?;
+        // Get hash functions used to create the signature
+        let weak_hash_ptr = signature.get_header().get_weak_hash_ptr()?;
+        let strong_hash_ptr = signature.get_header().get_strong_hash_ptr()?;
+        Delta::generate_delta(file_name, signature, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn write_delta<W: Write>(&self, writer: &mut W) -> Result<DeltaStats, RollingHashError> {
+        // Get differences compression recorded in delta header, if any
+        let compression_ptr = match self.header.get_compression_id() {
+            Some(compression_id) => Some(new_compression_ptr(compression_id)?),
+            None => None,
+        };
+        // Write serialized delta header
+        serialize_into(&mut *writer, &self.header)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        // Chunk delta list is written one chunk delta at a time, with the same
+        // layout as a serialized list, so differences are compressed as they are written.
+        // Compressed differences are split in MAX_DIFF_SIZE parts, the size
+        // readers accept once decompressed, empty ones are not written
+        let chunk_delta_count = match compression_ptr {
+            Some(_) => self
+                .chunk_delta_list
+                .iter()
+                .map(|chunk_delta| match chunk_delta {
+                    ChunkDelta::Diff(differences) => differences.len().div_ceil(MAX_DIFF_SIZE),
+                    _ => 1,
+                })
+                .sum(),
+            None => self.chunk_delta_list.len(),
+        };
+        serialize_into(&mut *writer, &(chunk_delta_count as u64))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut stats = DeltaStats::default();
+        for chunk_delta in self.chunk_delta_list.iter() {
+            match (chunk_delta, compression_ptr.as_ref()) {
+                (ChunkDelta::Diff(differences), Some(compression_ptr)) => differences
+                    .chunks(MAX_DIFF_SIZE)
+                    .try_for_each(|differences| {
+                        let compressed_differences = compression_ptr.compress(differences);
+                        stats.add_literal(differences.len(), compressed_differences.len());
+                        serialize_into(&mut *writer, &ChunkDelta::Diff(compressed_differences))
+                    }),
+                (ChunkDelta::Diff(differences), None) => {
+                    stats.add_literal(differences.len(), differences.len());
+                    serialize_into(&mut *writer, chunk_delta)
+                }
+                _ => serialize_into(&mut *writer, chunk_delta),
+            }
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        }
+        Ok(stats)
+    }
+
+    pub fn get_delta_from_file(delta_file_name: &str) -> Result<Delta, RollingHashError> {
+        // Get delta file
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        Delta::get_delta_from_reader(delta_file)
+    }
+
+    pub fn get_delta_from_reader<R: Read>(reader: R) -> Result<Delta, RollingHashError> {
+        // Get delta header and chunk delta list, differences are decompressed
+        // with the compression recorded in delta header, if any
+        let mut delta_reader = DeltaReader::new(reader)?;
+        let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+        while let Some(chunk_delta) = delta_reader.next_chunk_delta()? {
+            chunk_delta_list.push(chunk_delta);
+        }
+        Ok(Delta {
+            header: delta_reader.into_header(),
+            chunk_delta_list,
+        })
+    }
+
+    pub fn get_delta_from_librsync_file(
+        delta_file_name: &str,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        // Get delta file in librsync format, copies are split in chunks of chunk size
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut delta_reader = BufReader::new(delta_file);
+        Delta::read_librsync_delta(&mut delta_reader, chunk_size)
+    }
+
+    pub fn get_delta_from_vcdiff_file(
+        delta_file_name: &str,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        // Get delta file in VCDIFF format, copies are split in chunks of chunk size
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut delta_reader = BufReader::new(delta_file);
+        Delta::read_vcdiff_delta(&mut delta_reader, chunk_size)
+    }
+
+    pub fn get_delta_file_format(delta_file_name: &str) -> Result<&str, RollingHashError> {
+        // Delta files in librsync and VCDIFF formats are identified by their magic number
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut magic: Vec<u8> = Vec::new();
+        delta_file
+            .take(4)
+            .read_to_end(&mut magic)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if Delta::is_librsync_delta(&magic) {
+            Ok(LIBRSYNC_FORMAT)
+        } else if Delta::is_vcdiff_delta(&magic) {
+            Ok(VCDIFF_FORMAT)
+        } else {
+            Ok(RDIFF_FORMAT)
+        }
+    }
+
+    fn get_delta_from_file_with_basis<B: Read + Seek>(
+        delta_file_name: &str,
+        basis: &mut B,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        // Copies of delta formats without chunks are split in chunks of chunk size,
+        // parts of chunks are read from basis
+        let delta_format = Delta::get_delta_file_format(delta_file_name)?;
+        if delta_format == RDIFF_FORMAT {
+            return Delta::get_delta_from_file(delta_file_name);
+        }
+        let delta_file =
+            File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut delta_reader = BufReader::new(delta_file);
+        match delta_format {
+            LIBRSYNC_FORMAT => {
+                Delta::read_librsync_delta_with_basis(&mut delta_reader, basis, chunk_size)
+            }
+            _ => Delta::read_vcdiff_delta_with_basis(&mut delta_reader, basis, chunk_size),
+        }
+    }
+
+    pub fn create_patched_file(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        output_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Legacy deltas do not record chunk size, so it is computed the same way
+        // it was computed when the signature was created
+        Delta::write_patched_file(basis_file_name, delta_file_name, output_file_name, None)
+    }
+
+    pub fn create_patched_file_with_chunk_size(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        output_file_name: &str,
+        chunk_size: usize,
+    ) -> Result<(), RollingHashError> {
+        // Chunk size must be the one recorded in the delta, if any
+        Delta::write_patched_file(
+            basis_file_name,
+            delta_file_name,
+            output_file_name,
+            Some(chunk_size),
+        )
+    }
+
+    fn write_patched_file(
+        basis_file_name: &str,
+        delta_file_name: &str,
+        output_file_name: &str,
+        chunk_size: Option<usize>,
+    ) -> Result<(), RollingHashError> {
+        let mut basis_file =
+            File::open(basis_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        // Delta files in rdiff format are read one chunk delta at a time,
+        // the basis file is checked before creating the output file
+        if Delta::get_delta_file_format(delta_file_name)? == RDIFF_FORMAT {
+            let delta_file =
+                File::open(delta_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+            let mut delta_reader = DeltaReader::new(BufReader::new(delta_file))?;
+            let delta_patcher =
+                DeltaPatcher::new(delta_reader.get_header(), &mut basis_file, chunk_size)?;
+            return Delta::write_patched_output_file(
+                delta_patcher,
+                &mut || delta_reader.next_chunk_delta(),
+                output_file_name,
+            );
+        }
+        // librsync and VCDIFF delta files do not record chunk size, parts of chunks
+        // are read from basis, so it is computed as for delta files without header,
+        // unless given
+        let chunk_size = match chunk_size {
+            Some(chunk_size) => chunk_size,
+            None => {
+                let basis_size = basis_file
+                    .metadata()
+                    .map_err(|e| RollingHashError::from(Box::new(e)))?
+                    .len();
+                BufferedRdiffChunkIterator::compute_legacy_chunk_size(basis_size as usize)
+            }
+        };
+        let delta =
+            Delta::get_delta_from_file_with_basis(delta_file_name, &mut basis_file, chunk_size)?;
+        let delta_patcher = DeltaPatcher::new(&delta.header, &mut basis_file, Some(chunk_size))?;
+        let mut chunk_deltas = delta.chunk_delta_list.into_iter();
+        Delta::write_patched_output_file(
+            delta_patcher,
+            &mut || Ok(chunk_deltas.next()),
+            output_file_name,
+        )
+    }
+
+    fn write_patched_output_file<B: Read + Seek>(
+        mut delta_patcher: DeltaPatcher<B>,
+        next_chunk_delta: &mut dyn FnMut() -> Result<Option<ChunkDelta>, RollingHashError>,
+        output_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Write patched data to output file
+        let output_file =
+            File::create(output_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut output_writer = BufWriter::new(output_file);
+        let result =
+            Delta::write_chunk_deltas(&mut delta_patcher, next_chunk_delta, &mut output_writer)
+                .and_then(|_| delta_patcher.finish())
+                .and_then(|_| {
+                    output_writer
+                        .flush()
+                        .map_err(|e| RollingHashError::from(Box::new(e)))
+                });
+        // Do not leave an invalid patched file behind
+        if result.is_err() {
+            drop(output_writer);
+            let _ = fs::remove_file(output_file_name);
+        }
+        result
+    }
+
+    fn write_chunk_deltas<B: Read + Seek, W: Write>(
+        delta_patcher: &mut DeltaPatcher<B>,
+        next_chunk_delta: &mut dyn FnMut() -> Result<Option<ChunkDelta>, RollingHashError>,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        while let Some(chunk_delta) = next_chunk_delta()? {
+            delta_patcher.write_chunk_delta(&chunk_delta, writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn apply<W: Write>(
+        &self,
+        basis_iterator: &mut dyn RdiffChunkIterator,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        let basis_chunks = self.load_basis_chunks(basis_iterator)?;
+        self.write_patched_data(&basis_chunks, writer)
+    }
+
+    fn load_basis_chunks(
+        &self,
+        basis_iterator: &mut dyn RdiffChunkIterator,
+    ) -> Result<Vec<RdiffChunk>, RollingHashError> {
+        // Check basis chunk size and content defined chunking are the delta ones
+        if let Some(chunk_size) = self.header.get_chunk_size() {
+            if basis_iterator.get_chunk_size() != chunk_size {
+                return Err(RollingHashError::new(DELTA_CHUNK_SIZE_MISMATCH));
+            }
+        }
+        if basis_iterator.get_fastcdc_parameters() != self.header.get_fastcdc_parameters() {
+            return Err(RollingHashError::new(DELTA_CHUNK_SIZE_MISMATCH));
+        }
+        // Get basis chunks, chunk index i is stored at position i - 1.
+        // The last chunk keeps its own size, which is the signature last chunk size
+        // Basis size and digest are computed when the delta has a basis fingerprint
+        let mut basis_size: usize = 0;
+        let mut basis_hasher = match (self.header.get_basis(), self.header.get_strong_hash_id()) {
+            (Some(_), Some(strong_hash_id)) => Some(new_strong_hash_ptr(strong_hash_id)?.hasher()),
+            _ => None,
+        };
+        let mut basis_chunks: Vec<RdiffChunk> = Vec::new();
+        while let Some(chunk) = basis_iterator.next_chunk()? {
+            basis_size += chunk.len();
+            if let Some(basis_hasher) = basis_hasher.as_mut() {
+                basis_hasher.update(chunk.as_slice());
+            }
+            basis_chunks.push(chunk);
+        }
+        // Check basis last chunk size is the delta one, if it is known
+        if let Some(last_chunk_size) = self
+            .header
+            .get_last_chunk_size()
+            .filter(|last_chunk_size| *last_chunk_size != 0)
+        {
+            let basis_last_chunk_size = basis_chunks.last().map_or(0, |chunk| chunk.len());
+            if basis_last_chunk_size != last_chunk_size {
+                return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+            }
+        }
+        // Check basis size and digest are the delta basis fingerprint ones
+        if let (Some(basis), Some(basis_hasher)) = (self.header.get_basis(), basis_hasher) {
+            if basis_size != basis.get_size() || basis_hasher.finalize() != *basis.get_digest() {
+                return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+            }
+        }
+        Ok(basis_chunks)
+    }
 
+    fn write_patched_data<W: Write>(
+        &self,
+        basis_chunks: &[RdiffChunk],
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        // Init output size
+        let mut output_size: usize = 0;
+        // Init patched data hasher with the strong hash used by the delta, if any
+        let mut output_hasher = match self.header.get_strong_hash_id() {
+            Some(strong_hash_id) => Some(new_strong_hash_ptr(strong_hash_id)?.hasher()),
+            None => None,
+        };
+        // Rebuild new file from basis chunks and differences
+        for chunk_delta in self.chunk_delta_list.iter() {
+            // Get chunk index range to copy from basis chunks
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (*index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (*start_index, *count),
+                ChunkDelta::Diff(differences) => {
+                    Delta::write_data(writer, &mut output_hasher, &mut output_size, differences)?;
+                    continue;
+                }
+            };
+            for offset in 0..count {
+                let data = (start_index as usize + offset as usize)
+                    .checked_sub(1)
+                    .and_then(|position| basis_chunks.get(position))
+                    .ok_or_else(|| RollingHashError::new(INVALID_CHUNK_INDEX))?;
+                Delta::write_data(writer, &mut output_hasher, &mut output_size, data)?;
+            }
+        }
+        // Check patched data size is the delta one
+        if let Some(expected_output_size) = self.header.get_output_size() {
+            if output_size != expected_output_size {
+                return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+            }
+        }
+        // Check patched data digest is the delta one
+        if let (Some(output_hasher), Some(digest)) = (output_hasher, self.header.get_digest()) {
+            if output_hasher.finalize() != *digest {
+                return Err(RollingHashError::new(DELTA_DIGEST_MISMATCH));
+            }
+        }
+        Ok(())
+    }
+
+    fn write_data<W: Write>(
+        writer: &mut W,
+        output_hasher: &mut Option<StrongHasherPtr>,
+        output_size: &mut usize,
+        data: &[u8],
+    ) -> Result<(), RollingHashError> {
+        writer
+            .write_all(data)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        *output_size += data.len();
+        if let Some(output_hasher) = output_hasher.as_mut() {
+            output_hasher.update(data);
+        }
         Ok(())
     }
 
@@ -55,174 +608,437 @@ impl Delta {
         weak_hash_ptr: WeakHashPtr,
         strong_hash_ptr: StrongHashPtr,
     ) -> Result<Delta, RollingHashError> {
-        // Get rdiff file from input file given by filename
-        let rdiff_file = RdiffFile::new(file_name)?;
-        // Get file size
-        let file_size = rdiff_file.size();
-        // Get rdiff chunk iterator
-        let mut iterator: BufferedRdiffChunkIterator =
-            BufferedRdiffChunkIterator::new_with_chunk_size(
-                signature.get_chunk_size(),
-                rdiff_file,
-            )?;
-        // Init chunk delta list
+        let rdiff_file = Delta::get_rdiff_file(file_name)?;
+        Delta::generate_delta_from_rdiff_file(rdiff_file, signature, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    fn get_rdiff_file(file_name: &str) -> Result<RdiffFile<'static>, RollingHashError> {
+        // Get rdiff file from input file given by filename, or from standard input
+        if file_name == STDIN_FILE_NAME {
+            Ok(RdiffFile::new_with_unsized_reader(file_name, io::stdin()))
+        } else {
+            RdiffFile::new(file_name)
+        }
+    }
+
+    pub fn generate_delta_from_bytes(
+        data: &[u8],
+        signature: Signature,
+    ) -> Result<Delta, RollingHashError> {
+        // Get rdiff file over memory buffer
+        let rdiff_file = RdiffFile::new_with_bytes("", data);
+        Delta::generate_delta_from_rdiff_file_with_signature_hashes(rdiff_file, signature)
+    }
+
+    pub fn generate_delta_from_reader<R: Read>(
+        reader: R,
+        signature: Signature,
+    ) -> Result<Delta, RollingHashError> {
+        // Get rdiff file over reader, its size is not needed as data is read
+        // until the reader returns no more bytes
+        let rdiff_file = RdiffFile::new_with_unsized_reader("", reader);
+        Delta::generate_delta_from_rdiff_file_with_signature_hashes(rdiff_file, signature)
+    }
+
+    fn generate_delta_from_rdiff_file_with_signature_hashes(
+        rdiff_file: RdiffFile,
+        signature: Signature,
+    ) -> Result<Delta, RollingHashError> {
+        // Get hash functions used to create the signature
+        let weak_hash_ptr = signature.get_header().get_weak_hash_ptr()?;
+        let strong_hash_ptr = signature.get_header().get_strong_hash_ptr()?;
+        Delta::generate_delta_from_rdiff_file(rdiff_file, signature, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn generate_delta_from_rdiff_file(
+        rdiff_file: RdiffFile,
+        signature: Signature,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Delta, RollingHashError> {
+        // Keep chunk deltas in memory
         let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
-        // Init memory buffer
-        let mut buffer: Vec<u8> = Vec::new();
+        let header = Delta::generate_delta_into(
+            rdiff_file,
+            &signature,
+            &weak_hash_ptr,
+            &strong_hash_ptr,
+            &mut chunk_delta_list,
+        )?;
+        Ok(Delta {
+            header,
+            chunk_delta_list,
+        })
+    }
+
+    // Write delta in rdiff format while it is generated, without keeping chunk deltas
+    // in memory, and return the delta statistics
+    pub fn write_delta_from_rdiff_file<W: Write + Seek>(
+        rdiff_file: RdiffFile,
+        signature: Signature,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+        compression_ptr: Option<CompressionPtr>,
+        writer: W,
+    ) -> Result<DeltaStats, RollingHashError> {
+        // Header is written first with the digest size of the new file digest,
+        // output size and digest are set once the delta is finished
+        let compression_id = compression_ptr.as_ref().map(|ptr| ptr.get_id());
+        let mut header = Delta::new_header(
+            &signature,
+            &strong_hash_ptr,
+            0,
+            strong_hash_ptr.hasher().finalize(),
+        );
+        header.set_compression_id(compression_id);
+        let mut sink = RdiffDeltaSink::new(writer, &header, compression_ptr)?;
+        let mut header = Delta::generate_delta_into(
+            rdiff_file,
+            &signature,
+            &weak_hash_ptr,
+            &strong_hash_ptr,
+            &mut sink,
+        )?;
+        header.set_compression_id(compression_id);
+        sink.finish(&header)
+    }
+
+    // Generate delta of rdiff file against signature, chunk deltas are given to
+    // the sink as they are found and delta header is returned
+    pub fn generate_delta_into(
+        rdiff_file: RdiffFile,
+        signature: &Signature,
+        weak_hash_ptr: &WeakHashPtr,
+        strong_hash_ptr: &StrongHashPtr,
+        sink: &mut dyn DeltaSink,
+    ) -> Result<DeltaHeader, RollingHashError> {
+        // Check hash functions are the ones used to create the signature
+        signature
+            .get_header()
+            .validate_hashes(weak_hash_ptr, strong_hash_ptr)?;
+        // Content defined chunks are found by chunking the new file the same way
+        if let Some(fastcdc_parameters) = signature.get_header().get_fastcdc_parameters() {
+            let iterator = FastCdcChunkIterator::new(*fastcdc_parameters, rdiff_file);
+            return Delta::generate_content_defined_delta(
+                iterator,
+                signature,
+                weak_hash_ptr,
+                strong_hash_ptr,
+                sink,
+            );
+        }
+        // Get file size, if known, the end of file is found by reading it
+        let file_size = rdiff_file.size();
         // Set chunk size using the signature chunk size
         let chunk_size = signature.get_chunk_size();
-        // Init processed data size
-        let mut processed_data_size: usize = 0;
-        // Loop until the input file has been processed
-        'chunk: loop {
-            // Get next chunk from iterator
-            let rdiff_chunk_result = iterator.next_chunk()?;
-            if let Some(mut chunk) = rdiff_chunk_result {
-                // If there data more data to process update memory buffer
-                buffer.append(&mut chunk);
+        // Get sliding window over input file
+        let mut window = RdiffWindow::new(chunk_size, rdiff_file)?;
+        // Init delta writer, it merges matches and bounds differences
+        let mut delta_writer = DeltaWriter::new(sink);
+        // Init rolling checksum, it is only set while the window slides
+        // one byte at a time
+        let mut rolling_checksum: Option<RollingWeakHashPtr> = None;
+        // Init new file hasher, every byte is hashed when it leaves the window
+        let mut file_hasher = strong_hash_ptr.hasher();
+        // Loop while the window contains a whole chunk
+        while window.has_chunk() {
+            // Get chunk checksum, either rolled or computed from the chunk
+            let checksum = match rolling_checksum.as_ref() {
+                Some(rolling_checksum) => rolling_checksum.checksum(),
+                None => {
+                    let new_rolling_checksum =
+                        weak_hash_ptr.rolling_checksum(window.peek(chunk_size));
+                    let checksum = new_rolling_checksum.checksum();
+                    rolling_checksum = Some(new_rolling_checksum);
+                    checksum
+                }
+            };
+            // If the chunk is a chunk delta match
+            let chunk = window.peek(chunk_size);
+            if let Some(chunk_delta) = Delta::get_chunk_delta_match_by_checksum(
+                signature,
+                checksum,
+                strong_hash_ptr,
+                chunk,
+            ) {
+                // Add chunk as a match chunk delta, after pending differences
+                delta_writer.push_match(chunk_delta, chunk_size)?;
+                file_hasher.update(chunk);
+                // Move window to the next chunk, checksum must be computed again
+                window.consume(chunk_size)?;
+                rolling_checksum = None;
+                continue;
             }
-            // If there is more data to process
-            if buffer.len() > 0 {
-                // If it is the last chunk
-                if buffer.len() < chunk_size {
-                    // Check for last chunk match
-                    if buffer.len() == signature.get_last_chunk_size() {
-                        let last_chunk = &buffer[..];
-                        // If last chunk is a match
-                        if let Some(chunk_delta) = Delta::get_chunk_delta_match(
-                            &signature,
-                            &weak_hash_ptr,
-                            &strong_hash_ptr,
-                            last_chunk,
-                        ) {
-                            // Add last chunk as a chunk delta match
-                            chunk_delta_list.push(chunk_delta);
-                            // Update processed data size
-                            processed_data_size += last_chunk.len();
-                            // No more data to process, the chunk' loop stops
-                            break;
-                        }
-                    }
-                    // If there is not chunk delta match,
-                    // Update processed data size
-                    processed_data_size += buffer.len();
-                    // Update chunk delta list with buffer as chunk delta differences
-                    chunk_delta_list.push(ChunkDelta::Diff(buffer));
-                    // Chunk' loop stops
-                    break;
-                } else {
-                    // Get chunk from the memory buffer
-                    let chunk = &buffer[..chunk_size];
-                    // If the chunk is a chunk delta match
-                    if let Some(chunk_delta) = Delta::get_chunk_delta_match(
-                        &signature,
-                        &weak_hash_ptr,
-                        &strong_hash_ptr,
-                        chunk,
-                    ) {
-                        // Add chunk as a match chunk delta
-                        chunk_delta_list.push(chunk_delta);
-                        // Update processed data size
-                        processed_data_size += chunk.len();
-                        // Update buffer by removing that chunk
-                        buffer.drain(..chunk_size);
-                        // Get the next chunk, continue 'chunk loop
-                        continue;
-                    }
-                    // If there is not a chunk delta match
-                    // Search for longest byte sequence until either a chunk delta match
-                    // or an eof is found
-                    // Init byte difference list
-                    let mut differences: Vec<u8> = Vec::new();
-                    loop {
-                        // Get next byte difference
-                        let diff_byte = buffer.remove(0);
-                        // Update byte differences list
-                        differences.push(diff_byte);
-                        // If buffer is not big enough and there is more data
-                        // to be buffered
-                        if (buffer.len() < chunk_size)
-                            && (processed_data_size + differences.len() + buffer.len() < file_size)
-                        {
-                            // Get next chunk from iterator
-                            let rdiff_chunk_result = iterator.next_chunk()?;
-                            if let Some(mut chunk) = rdiff_chunk_result {
-                                // If there is more data to process.
-                                // update memory buffer
-                                buffer.append(&mut chunk);
-                            }
-                        }
-                        // Find a chunk delta match
-                        if buffer.len() >= chunk_size {
-                            // Get chunk from the memory buffer
-                            let chunk = &buffer[..chunk_size];
-                            // If the chunk is a chunk delta match
-                            if let Some(chunk_delta) = Delta::get_chunk_delta_match(
-                                &signature,
-                                &weak_hash_ptr,
-                                &strong_hash_ptr,
-                                chunk,
-                            ) {
-                                // Update processed data size with differences size
-                                processed_data_size += differences.len();
-                                // Add differences to chunck delta list
-                                chunk_delta_list.push(ChunkDelta::Diff(differences));
-                                // Update processed data size with chunk size
-                                processed_data_size += chunk.len();
-                                // Add chunk delta match to chunk delta list
-                                chunk_delta_list.push(chunk_delta);
-                                // Update buffer by removing that chunk
-                                buffer.drain(..chunk_size);
-                                // Get next chunk, continue to loop 'chunk
-                                continue 'chunk;
-                            }
-                            // If there is not a chunk delta match,
-                            // Get next first byte in buffer, continue to loop
-                        } else {
-                            // if there are no more chunks
-                            // Check for last chunk
-                            if buffer.len() == signature.get_last_chunk_size() {
-                                let last_chunk = &buffer[..signature.get_last_chunk_size()];
-                                // If last chunk is a chunk delta match
-                                if let Some(chunk_delta) = Delta::get_chunk_delta_match(
-                                    &signature,
-                                    &weak_hash_ptr,
-                                    &strong_hash_ptr,
-                                    last_chunk,
-                                ) {
-                                    // Update processed data size with differences size
-                                    processed_data_size += differences.len();
-                                    // Add differences to chunck delta list
-                                    chunk_delta_list.push(ChunkDelta::Diff(differences));
-                                    // Update processed data size with last chunk size
-                                    processed_data_size += last_chunk.len();
-                                    // Add chunk delta match to chunk delta list
-                                    chunk_delta_list.push(chunk_delta);
-                                    // No more data to process, loop 'chunk stops
-                                    break 'chunk;
-                                }
-                            }
-                            // If there is no match with last chunk
-                            // Update difference list with buffer
-                            differences.append(&mut buffer);
-                            // Update processed data size with differences size
-                            processed_data_size += differences.len();
-                            // Add differences to chunck delta list
-                            chunk_delta_list.push(ChunkDelta::Diff(differences));
-                            // Loop 'chunk stops
-                            break 'chunk;
-                        }
-                    }
+            // If there is not a chunk delta match
+            // Move window one byte forward and update byte differences
+            let diff_byte = window.pop_front()?;
+            delta_writer.push_difference(diff_byte)?;
+            file_hasher.update(&[diff_byte]);
+            // Roll out the byte difference and roll in the chunk last byte
+            if window.has_chunk() {
+                if let Some(rolling_checksum) = rolling_checksum.as_mut() {
+                    rolling_checksum.roll(diff_byte, window.get(chunk_size - 1));
                 }
-            } else {
-                // If there is no more data process, loop stops
-                break;
             }
         }
-        if processed_data_size != file_size {
+        // There are no more whole chunks, check for last chunk match
+        if !window.is_empty() {
+            let last_chunk_size = window.len();
+            let last_chunk = window.peek(last_chunk_size);
+            file_hasher.update(last_chunk);
+            match Delta::get_chunk_delta_match(
+                signature,
+                weak_hash_ptr,
+                strong_hash_ptr,
+                last_chunk,
+            ) {
+                // Add last chunk as a chunk delta match
+                Some(chunk_delta) => delta_writer.push_match(chunk_delta, last_chunk_size)?,
+                // Update differences with last chunk
+                None => delta_writer.push_differences(last_chunk)?,
+            }
+            window.consume(last_chunk_size)?;
+        }
+        // Add remaining chunk deltas
+        let processed_data_size = delta_writer.finish()?;
+        // Every byte read must have been processed, and all of a file of known size read
+        if processed_data_size != window.get_read_size()
+            || file_size.is_some_and(|file_size| processed_data_size != file_size)
+        {
             return Err(RollingHashError::new(DELTA_PROCESSED_DATA_SIZE_ERROR));
         }
-        Ok(Delta { chunk_delta_list })
+        Ok(Delta::new_header(
+            signature,
+            strong_hash_ptr,
+            processed_data_size,
+            file_hasher.finalize(),
+        ))
+    }
+
+    fn generate_content_defined_delta(
+        mut iterator: FastCdcChunkIterator,
+        signature: &Signature,
+        weak_hash_ptr: &WeakHashPtr,
+        strong_hash_ptr: &StrongHashPtr,
+        sink: &mut dyn DeltaSink,
+    ) -> Result<DeltaHeader, RollingHashError> {
+        // Init delta writer and new file hasher
+        let mut delta_writer = DeltaWriter::new(sink);
+        let mut file_hasher = strong_hash_ptr.hasher();
+        // Boundaries of unchanged data are found again after a change,
+        // so every new file chunk is either a match or differences
+        while let Some(chunk) = iterator.next_chunk()? {
+            file_hasher.update(chunk.as_slice());
+            match Delta::get_chunk_delta_match(
+                signature,
+                weak_hash_ptr,
+                strong_hash_ptr,
+                chunk.as_slice(),
+            ) {
+                Some(chunk_delta) => delta_writer.push_match(chunk_delta, chunk.len())?,
+                None => delta_writer.push_differences(chunk.as_slice())?,
+            }
+        }
+        let processed_data_size = delta_writer.finish()?;
+        Ok(Delta::new_header(
+            signature,
+            strong_hash_ptr,
+            processed_data_size,
+            file_hasher.finalize(),
+        ))
+    }
+
+    // Record chunk geometry, new file size, new file digest, basis fingerprint
+    // and basis content defined chunking, if any, in delta header
+    fn new_header(
+        signature: &Signature,
+        strong_hash_ptr: &StrongHashPtr,
+        output_size: usize,
+        digest: RdiffDigest,
+    ) -> DeltaHeader {
+        let mut header = DeltaHeader::new(
+            signature.get_chunk_size(),
+            signature.get_last_chunk_size(),
+            output_size,
+            strong_hash_ptr.get_id(),
+            digest,
+            signature.get_header().get_basis().cloned(),
+        );
+        header.set_fastcdc_parameters(signature.get_header().get_fastcdc_parameters().copied());
+        header
+    }
+
+    fn push_match(chunk_delta_list: &mut Vec<ChunkDelta>, chunk_delta: ChunkDelta) {
+        // A match following the previous match chunk index is merged with it
+        if let (ChunkDelta::Match(index), Some(last_chunk_delta)) =
+            (&chunk_delta, chunk_delta_list.last_mut())
+        {
+            if Delta::extend_match(last_chunk_delta, *index) {
+                return;
+            }
+        }
+        chunk_delta_list.push(chunk_delta);
+    }
+
+    // Merge match of chunk index with last chunk delta when it is the match or
+    // copy range of the previous chunk index, and return whether it was merged
+    fn extend_match(last_chunk_delta: &mut ChunkDelta, index: u32) -> bool {
+        match last_chunk_delta {
+            ChunkDelta::Match(last_index) if last_index.checked_add(1) == Some(index) => {
+                *last_chunk_delta = ChunkDelta::CopyRange {
+                    start_index: *last_index,
+                    count: 2,
+                };
+                true
+            }
+            ChunkDelta::CopyRange { start_index, count }
+                if start_index.checked_add(*count) == Some(index) =>
+            {
+                *count += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn get_byte_operations(&self) -> Result<Vec<ByteOperation<'_>>, RollingHashError> {
+        // Chunk geometry is needed to get basis byte offsets from chunk indexes
+        let (chunk_size, output_size) =
+            match (self.header.get_chunk_size(), self.header.get_output_size()) {
+                (Some(chunk_size), Some(output_size)) => (chunk_size, output_size),
+                _ => return Err(RollingHashError::new(DELTA_GEOMETRY_UNKNOWN)),
+            };
+        if self.header.get_fastcdc_parameters().is_some() {
+            return Err(RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+        }
+        // Get basis last chunk index and size when it is shorter, from basis size
+        let last_chunk = match (self.header.get_last_chunk_size(), self.header.get_basis()) {
+            (Some(last_chunk_size), Some(basis))
+                if last_chunk_size != 0 && last_chunk_size != chunk_size =>
+            {
+                Some((basis.get_size().div_ceil(chunk_size), last_chunk_size))
+            }
+            (Some(last_chunk_size), None)
+                if last_chunk_size != 0 && last_chunk_size != chunk_size =>
+            {
+                return Err(RollingHashError::new(DELTA_GEOMETRY_UNKNOWN));
+            }
+            _ => None,
+        };
+        // Init byte operations and patched data size
+        let mut byte_operations: Vec<ByteOperation> = Vec::new();
+        let mut written_size: usize = 0;
+        for chunk_delta in self.chunk_delta_list.iter() {
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (*index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (*start_index, *count),
+                ChunkDelta::Diff(differences) => {
+                    if !differences.is_empty() {
+                        byte_operations.push(ByteOperation::Literal(differences));
+                        written_size += differences.len();
+                    }
+                    continue;
+                }
+            };
+            let offset = (start_index as usize)
+                .checked_sub(1)
+                .ok_or_else(|| RollingHashError::new(INVALID_DELTA_FILE))?
+                * chunk_size;
+            // Only the basis last chunk is shorter, copies can not go past it
+            let end_index = start_index as usize + count as usize - 1;
+            let mut length = count as usize * chunk_size;
+            if let Some((last_index, last_chunk_size)) = last_chunk {
+                if count != 0 && end_index > last_index {
+                    return Err(RollingHashError::new(INVALID_CHUNK_INDEX));
+                }
+                if count != 0 && end_index == last_index {
+                    length -= chunk_size - last_chunk_size;
+                }
+            }
+            if length != 0 {
+                byte_operations.push(ByteOperation::Copy { offset, length });
+                written_size += length;
+            }
+        }
+        if written_size != output_size {
+            return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+        }
+        Ok(byte_operations)
+    }
+
+    fn push_literal(chunk_delta_list: &mut Vec<ChunkDelta>, data: &[u8]) {
+        // Consecutive literals are added to the same differences
+        if data.is_empty() {
+            return;
+        }
+        match chunk_delta_list.last_mut() {
+            Some(ChunkDelta::Diff(differences)) => differences.extend_from_slice(data),
+            _ => chunk_delta_list.push(ChunkDelta::Diff(data.to_vec())),
+        }
+    }
+
+    fn push_byte_copy(
+        chunk_delta_list: &mut Vec<ChunkDelta>,
+        offset: usize,
+        length: usize,
+        chunk_size: usize,
+        last_chunk_size: &mut usize,
+    ) -> Result<(), RollingHashError> {
+        // Copies must start at a chunk, a shorter last chunk is the basis last chunk.
+        // Last chunk size is only known when the basis last chunk is copied
+        if !offset.is_multiple_of(chunk_size) {
+            return Err(RollingHashError::new(UNALIGNED_DELTA_COPY));
+        }
+        let remaining_size = length % chunk_size;
+        if remaining_size != 0 {
+            if *last_chunk_size != 0 && *last_chunk_size != remaining_size {
+                return Err(RollingHashError::new(UNALIGNED_DELTA_COPY));
+            }
+            *last_chunk_size = remaining_size;
+        }
+        let start_index = offset / chunk_size + 1;
+        let count = length.div_ceil(chunk_size);
+        for index in start_index..start_index + count {
+            let index =
+                u32::try_from(index).map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))?;
+            Delta::push_match(chunk_delta_list, ChunkDelta::Match(index));
+        }
+        Ok(())
+    }
+
+    // Push basis byte range as matches of whole chunks of chunk size, parts of
+    // chunks it starts or ends in are read from basis as differences
+    fn push_basis_copy<B: Read + Seek>(
+        chunk_delta_list: &mut Vec<ChunkDelta>,
+        basis: &mut B,
+        basis_size: u64,
+        offset: usize,
+        length: usize,
+        chunk_size: usize,
+        buffer: &mut [u8],
+    ) -> Result<(), RollingHashError> {
+        // Copies past the basis end are from another basis
+        let end_offset = offset
+            .checked_add(length)
+            .filter(|end_offset| *end_offset as u64 <= basis_size)
+            .ok_or_else(|| RollingHashError::new(DELTA_BASIS_MISMATCH))?;
+        let geometry = ChunkGeometry::new_with_chunk_size(chunk_size, basis_size)?;
+        Delta::push_basis_range(
+            chunk_delta_list,
+            Some(basis),
+            &geometry,
+            offset as u64,
+            end_offset as u64,
+            buffer,
+        )
+    }
+
+    // Basis last chunk size, basis of chunks of chunk size read with basis
+    fn get_basis_last_chunk_size(basis_size: u64, chunk_size: usize) -> usize {
+        match basis_size as usize % chunk_size {
+            0 if basis_size > 0 => chunk_size,
+            last_chunk_size => last_chunk_size,
+        }
     }
 
     fn get_chunk_delta_match(
@@ -231,17 +1047,30 @@ impl Delta {
         strong_hash_ptr: &StrongHashPtr,
         chunk: &[u8],
     ) -> Option<ChunkDelta> {
-        // Check if chunk has same weak hash and strong hash that one in signature
         // Get chunk checksum
         let checksum = weak_hash_ptr.checksum(chunk);
+        Delta::get_chunk_delta_match_by_checksum(signature, checksum, strong_hash_ptr, chunk)
+    }
+
+    fn get_chunk_delta_match_by_checksum(
+        signature: &Signature,
+        checksum: RdiffChecksum,
+        strong_hash_ptr: &StrongHashPtr,
+        chunk: &[u8],
+    ) -> Option<ChunkDelta> {
+        // Check if chunk has same weak hash and strong hash that one in signature
         // If the checksum exists in chunk table
         if let Some(chunk_digest_list) =
             signature.get_rdiff_chunk_table().chunk_table.get(&checksum)
         {
-            // Get chunk digest
-            let digest = strong_hash_ptr.digest(chunk);
-            // If chunk digest exists in chunk table
-            if let Some(chunk_digest) = chunk_digest_list.iter().find(|c| c.digest == digest) {
+            // Get chunk digest, truncated as the signature ones
+            let mut digest = strong_hash_ptr.digest(chunk);
+            digest.truncate(signature.get_header().get_digest_size());
+            // If chunk digest exists in chunk table for a chunk of the same length
+            if let Some(chunk_digest) = chunk_digest_list
+                .iter()
+                .find(|c| c.length == chunk.len() && c.digest == digest)
+            {
                 // Return it as a match chunk delta
                 let chunk_delta = ChunkDelta::Match(chunk_digest.index);
                 return Some(chunk_delta);
@@ -0,0 +1,288 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::rdiff::{
+    chunk::{
+        iterator::{fastcdc::FastCdcChunkIterator, BufferedRdiffChunkIterator, RdiffChunkIterator},
+        RdiffDigest,
+    },
+    constants::READ_BLOCK_SIZE,
+    error::{
+        messages::{
+            DELTA_BASIS_MISMATCH, DELTA_CHUNK_SIZE_MISMATCH, DELTA_DIGEST_MISMATCH,
+            DELTA_OUTPUT_SIZE_ERROR, INVALID_CHUNK_INDEX,
+        },
+        RollingHashError,
+    },
+    hash::strong::{new_strong_hash_ptr, StrongHasherPtr},
+    io::RdiffFile,
+};
+
+use super::{header::DeltaHeader, reader::DeltaReader, ChunkDelta, Delta};
+
+// Writes patched data one chunk delta at a time, match chunk deltas are read
+// from a seekable basis at their chunk offset, so neither the basis nor the
+// chunk delta list is kept in memory.
+// Offsets of fixed size chunks are computed from chunk size, offsets of content
+// defined chunks are found by chunking the basis once before patching
+pub struct DeltaPatcher<'a, B: Read + Seek> {
+    basis: &'a mut B,
+    basis_size: u64,
+    chunk_size: usize,
+    chunk_offsets: Option<Vec<u64>>,
+    output_size: usize,
+    output_hasher: Option<StrongHasherPtr>,
+    expected_output_size: Option<usize>,
+    expected_digest: Option<RdiffDigest>,
+    buffer: Vec<u8>,
+}
+
+impl<'a, B: Read + Seek> DeltaPatcher<'a, B> {
+    // Chunk size is needed for delta files that do not record it, otherwise it
+    // must be the recorded one. The basis is checked against delta header
+    pub fn new(
+        header: &DeltaHeader,
+        basis: &'a mut B,
+        chunk_size: Option<usize>,
+    ) -> Result<DeltaPatcher<'a, B>, RollingHashError> {
+        let basis_size = basis
+            .seek(SeekFrom::End(0))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        // Get chunk size recorded in delta, given or computed the same way it was
+        // computed when signatures of delta files without header were created
+        let chunk_size = match (header.get_chunk_size(), chunk_size) {
+            (Some(delta_chunk_size), Some(chunk_size)) if delta_chunk_size != chunk_size => {
+                return Err(RollingHashError::new(DELTA_CHUNK_SIZE_MISMATCH));
+            }
+            (Some(chunk_size), _) | (None, Some(chunk_size)) => chunk_size,
+            (None, None) => {
+                BufferedRdiffChunkIterator::compute_legacy_chunk_size(basis_size as usize)
+            }
+        };
+        BufferedRdiffChunkIterator::validate_chunk_size(chunk_size)?;
+        let mut delta_patcher = DeltaPatcher {
+            basis,
+            basis_size,
+            chunk_size,
+            chunk_offsets: None,
+            output_size: 0,
+            output_hasher: match header.get_strong_hash_id() {
+                Some(strong_hash_id) => Some(new_strong_hash_ptr(strong_hash_id)?.hasher()),
+                None => None,
+            },
+            expected_output_size: header.get_output_size(),
+            expected_digest: header.get_digest().cloned(),
+            buffer: vec![0; READ_BLOCK_SIZE],
+        };
+        delta_patcher.check_basis(header)?;
+        Ok(delta_patcher)
+    }
+
+    pub fn get_output_size(&self) -> usize {
+        self.output_size
+    }
+
+    pub fn write_chunk_delta<W: Write>(
+        &mut self,
+        chunk_delta: &ChunkDelta,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        // Get chunk index range to copy from basis
+        let (start_index, count) = match chunk_delta {
+            ChunkDelta::Match(index) => (*index, 1),
+            ChunkDelta::CopyRange { start_index, count } => (*start_index, *count),
+            ChunkDelta::Diff(differences) => {
+                return Delta::write_data(
+                    writer,
+                    &mut self.output_hasher,
+                    &mut self.output_size,
+                    differences,
+                );
+            }
+        };
+        // Empty copy ranges copy nothing
+        if count == 0 {
+            return Ok(());
+        }
+        let (start_offset, end_offset) = self.get_chunk_range(start_index, count)?;
+        self.copy_basis_data(start_offset, end_offset, writer)
+    }
+
+    // Check patched data size and digest are the delta ones
+    pub fn finish(self) -> Result<(), RollingHashError> {
+        if let Some(expected_output_size) = self.expected_output_size {
+            if self.output_size != expected_output_size {
+                return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+            }
+        }
+        if let (Some(output_hasher), Some(digest)) = (self.output_hasher, self.expected_digest) {
+            if output_hasher.finalize() != digest {
+                return Err(RollingHashError::new(DELTA_DIGEST_MISMATCH));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_basis(&mut self, header: &DeltaHeader) -> Result<(), RollingHashError> {
+        // Basis digest is computed when the delta has a basis fingerprint
+        let mut basis_hasher = match (header.get_basis(), header.get_strong_hash_id()) {
+            (Some(_), Some(strong_hash_id)) => Some(new_strong_hash_ptr(strong_hash_id)?.hasher()),
+            _ => None,
+        };
+        self.basis
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let basis_last_chunk_size = if let Some(fastcdc_parameters) =
+            header.get_fastcdc_parameters()
+        {
+            // Find content defined chunk offsets, chunk index i starts at offset i - 1
+            let basis_file = RdiffFile::new_with_reader("basis", &mut *self.basis, self.basis_size);
+            let mut iterator = FastCdcChunkIterator::new(*fastcdc_parameters, basis_file);
+            let mut chunk_offsets: Vec<u64> = vec![0];
+            let mut last_chunk_size = 0;
+            while let Some(chunk) = iterator.next_chunk()? {
+                if let Some(basis_hasher) = basis_hasher.as_mut() {
+                    basis_hasher.update(chunk.as_slice());
+                }
+                last_chunk_size = chunk.len();
+                chunk_offsets.push(chunk_offsets[chunk_offsets.len() - 1] + chunk.len() as u64);
+            }
+            self.chunk_offsets = Some(chunk_offsets);
+            last_chunk_size
+        } else {
+            if let Some(basis_hasher) = basis_hasher.as_mut() {
+                loop {
+                    let size = self
+                        .basis
+                        .read(&mut self.buffer)
+                        .map_err(|e| RollingHashError::from(Box::new(e)))?;
+                    if size == 0 {
+                        break;
+                    }
+                    basis_hasher.update(&self.buffer[..size]);
+                }
+            }
+            match self.basis_size as usize % self.chunk_size {
+                0 if self.basis_size > 0 => self.chunk_size,
+                last_chunk_size => last_chunk_size,
+            }
+        };
+        // Check basis last chunk size is the delta one, if it is known
+        if let Some(last_chunk_size) = header
+            .get_last_chunk_size()
+            .filter(|last_chunk_size| *last_chunk_size != 0)
+        {
+            if basis_last_chunk_size != last_chunk_size {
+                return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+            }
+        }
+        // Check basis size and digest are the delta basis fingerprint ones
+        if let (Some(basis), Some(basis_hasher)) = (header.get_basis(), basis_hasher) {
+            if self.basis_size != basis.get_size() as u64
+                || basis_hasher.finalize() != *basis.get_digest()
+            {
+                return Err(RollingHashError::new(DELTA_BASIS_MISMATCH));
+            }
+        }
+        Ok(())
+    }
+
+    // Get basis byte range of count chunks from chunk index start index
+    pub(crate) fn get_chunk_range(
+        &self,
+        start_index: u32,
+        count: u32,
+    ) -> Result<(u64, u64), RollingHashError> {
+        let first_position = (start_index as u64).checked_sub(1);
+        let end_position = first_position.map(|position| position + count as u64);
+        let range = match (first_position, end_position, self.chunk_offsets.as_ref()) {
+            (Some(first_position), Some(end_position), Some(chunk_offsets)) => {
+                match (
+                    chunk_offsets.get(first_position as usize),
+                    chunk_offsets.get(end_position as usize),
+                ) {
+                    (Some(start_offset), Some(end_offset)) => Some((*start_offset, *end_offset)),
+                    _ => None,
+                }
+            }
+            (Some(first_position), Some(end_position), None) => {
+                let chunk_size = self.chunk_size as u64;
+                let start_offset = first_position * chunk_size;
+                let end_offset = (end_position * chunk_size).min(self.basis_size);
+                // Last chunk of the range must start in the basis
+                ((end_position - 1) * chunk_size < self.basis_size)
+                    .then_some((start_offset, end_offset))
+            }
+            _ => None,
+        };
+        range.ok_or_else(|| RollingHashError::new(INVALID_CHUNK_INDEX))
+    }
+
+    fn copy_basis_data<W: Write>(
+        &mut self,
+        start_offset: u64,
+        end_offset: u64,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        self.basis
+            .seek(SeekFrom::Start(start_offset))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut remaining_size = end_offset - start_offset;
+        while remaining_size > 0 {
+            let size = remaining_size.min(self.buffer.len() as u64) as usize;
+            self.basis
+                .read_exact(&mut self.buffer[..size])
+                .map_err(|e| RollingHashError::from(Box::new(e)))?;
+            Delta::write_data(
+                writer,
+                &mut self.output_hasher,
+                &mut self.output_size,
+                &self.buffer[..size],
+            )?;
+            remaining_size -= size as u64;
+        }
+        Ok(())
+    }
+}
+
+impl Delta {
+    // Apply a delta file in rdiff format read from delta reader to a seekable basis,
+    // chunk deltas are applied as they are read
+    pub fn patch<B: Read + Seek, R: Read, W: Write>(
+        basis: &mut B,
+        delta_reader: R,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        Delta::patch_with_chunk_size(basis, delta_reader, writer, None)
+    }
+
+    // Chunk size is only needed for delta files that do not record it
+    pub fn patch_with_chunk_size<B: Read + Seek, R: Read, W: Write>(
+        basis: &mut B,
+        delta_reader: R,
+        writer: &mut W,
+        chunk_size: Option<usize>,
+    ) -> Result<(), RollingHashError> {
+        let mut delta_reader = DeltaReader::new(delta_reader)?;
+        let mut delta_patcher = DeltaPatcher::new(delta_reader.get_header(), basis, chunk_size)?;
+        while let Some(chunk_delta) = delta_reader.next_chunk_delta()? {
+            delta_patcher.write_chunk_delta(&chunk_delta, writer)?;
+        }
+        delta_patcher.finish()
+    }
+
+    // Apply delta to a seekable basis, basis chunks are read when they are copied
+    pub fn apply_to_basis<B: Read + Seek, W: Write>(
+        &self,
+        basis: &mut B,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        let mut delta_patcher = DeltaPatcher::new(&self.header, basis, None)?;
+        for chunk_delta in self.chunk_delta_list.iter() {
+            delta_patcher.write_chunk_delta(chunk_delta, writer)?;
+        }
+        delta_patcher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use std::{fs, io::Cursor};
+
+use crate::rdiff::{
+    chunk::iterator::fastcdc::FastCdcParameters,
+    compression::rdiff_deflate::RdiffDeflate,
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{DELTA_BASIS_MISMATCH, DELTA_CHUNK_SIZE_MISMATCH, INVALID_CHUNK_INDEX},
+        RollingHashError,
+    },
+    hash::{
+        strong::{
+            rdiff_sha1::{RdiffSha1, RDIFF_SHA1_ID},
+            StrongHash,
+        },
+        weak::rdiff_addler::RdiffAddler,
+    },
+    io::RdiffFile,
+    signature::{header::BasisFingerprint, Signature},
+    util::get_edited_data,
+};
+
+use super::DeltaPatcher;
+
+fn get_delta_data(new_data: &[u8], signature: Signature) -> Vec<u8> {
+    let mut delta_data = Cursor::new(Vec::new());
+    Delta::write_delta_from_rdiff_file(
+        RdiffFile::new_with_bytes("new", new_data),
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+        Some(RdiffDeflate::new_ptr()),
+        &mut delta_data,
+    )
+    .unwrap();
+    delta_data.into_inner()
+}
+
+#[test]
+fn test_delta_patch_case1() {
+    // Get old data, new data and delta in memory
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let new_data = get_edited_data(&old_data);
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let delta_data = get_delta_data(&new_data, signature);
+
+    // Patch seekable basis reading one chunk delta at a time
+    let mut basis = Cursor::new(old_data);
+    let mut output: Vec<u8> = Vec::new();
+    Delta::patch(&mut basis, delta_data.as_slice(), &mut output).unwrap();
+
+    // Verify computed values
+    assert_eq!(output, new_data);
+}
+
+#[test]
+fn test_delta_patch_fastcdc_case1() {
+    // Get old data, new data and delta of content defined chunks
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let new_data = get_edited_data(&old_data);
+    let signature = Signature::create_signature_from_rdiff_file_with_fastcdc(
+        RdiffFile::new_with_bytes("old", old_data.as_slice()),
+        FastCdcParameters::new(32, 64, 256).unwrap(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let delta_data = get_delta_data(&new_data, signature);
+
+    // Patch seekable basis, chunk offsets are found by chunking it
+    let mut basis = Cursor::new(old_data);
+    let mut output: Vec<u8> = Vec::new();
+    Delta::patch(&mut basis, delta_data.as_slice(), &mut output).unwrap();
+
+    // Verify computed values
+    assert_eq!(output, new_data);
+}
+
+#[test]
+fn test_delta_patch_chunk_size_mismatch_case1() {
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let new_data = get_edited_data(&old_data);
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let chunk_size = signature.get_chunk_size();
+    let delta_data = get_delta_data(&new_data, signature);
+
+    // Chunk size given must be the delta one
+    let mut basis = Cursor::new(old_data);
+    let mut output: Vec<u8> = Vec::new();
+    let result = Delta::patch_with_chunk_size(
+        &mut basis,
+        delta_data.as_slice(),
+        &mut output,
+        Some(chunk_size + 1),
+    );
+    assert_eq!(
+        result,
+        Err(RollingHashError::new(DELTA_CHUNK_SIZE_MISMATCH))
+    );
+}
+
+#[test]
+fn test_delta_apply_to_basis_case1() {
+    // Get basis with chunk size 2 and last chunk size 1
+    let basis_data = b"abc".to_vec();
+    let basis = BasisFingerprint::new(basis_data.len(), RdiffSha1::new().digest(&basis_data));
+    let header = DeltaHeader::new(
+        2,
+        1,
+        5,
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(b"cxabc"),
+        Some(basis),
+    );
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(vec![b'x']),
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 2,
+        },
+    ];
+    let delta = Delta::new(header, chunk_delta_list);
+
+    // Apply delta to seekable basis
+    let mut output: Vec<u8> = Vec::new();
+    delta
+        .apply_to_basis(&mut Cursor::new(basis_data), &mut output)
+        .unwrap();
+
+    // Verify computed values
+    assert_eq!(output, b"cxabc");
+}
+
+#[test]
+fn test_delta_apply_to_basis_invalid_chunk_index_case1() {
+    // Basis with two chunks
+    let mut output: Vec<u8> = Vec::new();
+    for chunk_delta in [
+        ChunkDelta::Match(0),
+        ChunkDelta::Match(3),
+        ChunkDelta::CopyRange {
+            start_index: 2,
+            count: 2,
+        },
+    ] {
+        let header = DeltaHeader::new_without_digest(2, 1, 2);
+        let delta = Delta::new(header, vec![chunk_delta]);
+        let result = delta.apply_to_basis(&mut Cursor::new(b"abc".to_vec()), &mut output);
+        assert_eq!(result, Err(RollingHashError::new(INVALID_CHUNK_INDEX)));
+    }
+}
+
+#[test]
+fn test_delta_patcher_basis_mismatch_case1() {
+    // Delta created from a basis with the same size and a different digest
+    let basis = BasisFingerprint::new(3, RdiffSha1::new().digest(b"xyz"));
+    let header = DeltaHeader::new(
+        2,
+        1,
+        2,
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(b"ab"),
+        Some(basis),
+    );
+    let mut basis_data = Cursor::new(b"abc".to_vec());
+    let result = DeltaPatcher::new(&header, &mut basis_data, None).map(|_| ());
+    assert_eq!(result, Err(RollingHashError::new(DELTA_BASIS_MISMATCH)));
+
+    // Delta created from a basis with a different last chunk size
+    let header = DeltaHeader::new_without_digest(2, 2, 2);
+    let mut basis_data = Cursor::new(b"abc".to_vec());
+    let result = DeltaPatcher::new(&header, &mut basis_data, None).map(|_| ());
+    assert_eq!(result, Err(RollingHashError::new(DELTA_BASIS_MISMATCH)));
+}
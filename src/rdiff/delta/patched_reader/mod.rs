@@ -0,0 +1,170 @@
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+use crate::rdiff::error::{messages::DELTA_OUTPUT_SIZE_ERROR, RollingHashError};
+
+use super::{patch::DeltaPatcher, ChunkDelta, Delta};
+
+// Where a range of the patched data comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PatchedSource {
+    // Basis byte offset of a match chunk delta range
+    Basis(u64),
+    // Index of the differences of a diff chunk delta
+    Literal(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PatchedSegment {
+    output_offset: u64,
+    length: u64,
+    source: PatchedSource,
+}
+
+// Presents a basis and a delta as the patched data, without writing it.
+// Each patched byte offset is resolved to the chunk delta it comes from, so
+// any byte range is read from basis chunks and delta differences.
+// The basis is checked against delta header when the reader is created, the
+// patched data digest can not be checked since it may never be read whole
+pub struct PatchedReader<B: Read + Seek> {
+    basis: B,
+    segments: Vec<PatchedSegment>,
+    literals: Vec<Vec<u8>>,
+    size: u64,
+    position: u64,
+}
+
+impl<B: Read + Seek> PatchedReader<B> {
+    pub fn new(basis: B, delta: Delta) -> Result<PatchedReader<B>, RollingHashError> {
+        PatchedReader::new_with_optional_chunk_size(basis, delta, None)
+    }
+
+    // Chunk size is only needed for deltas that do not record it
+    pub fn new_with_chunk_size(
+        basis: B,
+        delta: Delta,
+        chunk_size: usize,
+    ) -> Result<PatchedReader<B>, RollingHashError> {
+        PatchedReader::new_with_optional_chunk_size(basis, delta, Some(chunk_size))
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn into_basis(self) -> B {
+        self.basis
+    }
+
+    fn new_with_optional_chunk_size(
+        mut basis: B,
+        delta: Delta,
+        chunk_size: Option<usize>,
+    ) -> Result<PatchedReader<B>, RollingHashError> {
+        let delta_patcher = DeltaPatcher::new(&delta.header, &mut basis, chunk_size)?;
+        // Get patched data range of each chunk delta
+        let mut segments: Vec<PatchedSegment> = Vec::new();
+        let mut literals: Vec<Vec<u8>> = Vec::new();
+        let mut size = 0;
+        for chunk_delta in delta.chunk_delta_list.into_iter() {
+            let (start_index, count) = match chunk_delta {
+                ChunkDelta::Match(index) => (index, 1),
+                ChunkDelta::CopyRange { start_index, count } => (start_index, count),
+                ChunkDelta::Diff(differences) => {
+                    if !differences.is_empty() {
+                        segments.push(PatchedSegment {
+                            output_offset: size,
+                            length: differences.len() as u64,
+                            source: PatchedSource::Literal(literals.len()),
+                        });
+                        size += differences.len() as u64;
+                        literals.push(differences);
+                    }
+                    continue;
+                }
+            };
+            // Empty copy ranges copy nothing
+            if count == 0 {
+                continue;
+            }
+            let (start_offset, end_offset) = delta_patcher.get_chunk_range(start_index, count)?;
+            segments.push(PatchedSegment {
+                output_offset: size,
+                length: end_offset - start_offset,
+                source: PatchedSource::Basis(start_offset),
+            });
+            size += end_offset - start_offset;
+        }
+        if let Some(output_size) = delta.header.get_output_size() {
+            if size != output_size as u64 {
+                return Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+            }
+        }
+        Ok(PatchedReader {
+            basis,
+            segments,
+            literals,
+            size,
+            position: 0,
+        })
+    }
+}
+
+impl<B: Read + Seek> Read for PatchedReader<B> {
+    // Reads at most up to the end of the chunk delta the position is in
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if buffer.is_empty() || self.position >= self.size {
+            return Ok(0);
+        }
+        // Get the segment the position is in, segments are sorted by offset
+        let segment_index = self
+            .segments
+            .partition_point(|segment| segment.output_offset + segment.length <= self.position);
+        let segment = self.segments[segment_index];
+        let segment_offset = self.position - segment.output_offset;
+        let size = (segment.length - segment_offset).min(buffer.len() as u64) as usize;
+        let size = match segment.source {
+            PatchedSource::Basis(basis_offset) => {
+                self.basis
+                    .seek(SeekFrom::Start(basis_offset + segment_offset))?;
+                match self.basis.read(&mut buffer[..size])? {
+                    // Basis is shorter than when the reader was created
+                    0 => return Err(io::Error::from(ErrorKind::UnexpectedEof)),
+                    size => size,
+                }
+            }
+            PatchedSource::Literal(literal_index) => {
+                let literal_offset = segment_offset as usize;
+                buffer[..size].copy_from_slice(
+                    &self.literals[literal_index][literal_offset..literal_offset + size],
+                );
+                size
+            }
+        };
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<B: Read + Seek> Seek for PatchedReader<B> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        // Seeking before the start is an error, seeking after the end is not
+        match position {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
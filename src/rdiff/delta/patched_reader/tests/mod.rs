@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    io::{Cursor, Read, Seek, SeekFrom},
+};
+
+use crate::rdiff::{
+    chunk::iterator::fastcdc::FastCdcParameters,
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{DELTA_BASIS_MISMATCH, DELTA_OUTPUT_SIZE_ERROR},
+        RollingHashError,
+    },
+    hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
+    io::RdiffFile,
+    signature::Signature,
+    util::get_edited_data,
+};
+
+use super::PatchedReader;
+
+#[test]
+fn test_delta_patched_reader_case1() {
+    // Get old data, new data and delta in memory
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let new_data = get_edited_data(&old_data);
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let delta = Delta::generate_delta_from_bytes(new_data.as_slice(), signature).unwrap();
+
+    // Read the whole patched data
+    let mut patched_reader = PatchedReader::new(Cursor::new(old_data), delta).unwrap();
+    assert_eq!(patched_reader.get_size(), new_data.len() as u64);
+    let mut patched_data: Vec<u8> = Vec::new();
+    patched_reader.read_to_end(&mut patched_data).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Read byte ranges across basis chunks and differences
+    for (start, end) in [(0, 1), (95, 115), (490, 520), (880, 1000), (1000, 1014)] {
+        patched_reader.seek(SeekFrom::Start(start)).unwrap();
+        let mut range_data = vec![0; (end - start) as usize];
+        patched_reader.read_exact(&mut range_data).unwrap();
+        assert_eq!(range_data, new_data[start as usize..end as usize]);
+    }
+}
+
+#[test]
+fn test_delta_patched_reader_fastcdc_case1() {
+    // Get delta of content defined chunks
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let new_data = get_edited_data(&old_data);
+    let signature = Signature::create_signature_from_rdiff_file_with_fastcdc(
+        RdiffFile::new_with_bytes("old", old_data.as_slice()),
+        FastCdcParameters::new(32, 64, 256).unwrap(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let delta = Delta::generate_delta_from_bytes(new_data.as_slice(), signature).unwrap();
+
+    // Read patched data from the end
+    let mut patched_reader = PatchedReader::new(Cursor::new(old_data), delta).unwrap();
+    let offset = patched_reader.seek(SeekFrom::End(-300)).unwrap();
+    assert_eq!(offset, new_data.len() as u64 - 300);
+    let mut range_data = vec![0; 100];
+    patched_reader.read_exact(&mut range_data).unwrap();
+    assert_eq!(range_data, new_data[offset as usize..offset as usize + 100]);
+    let offset = patched_reader.seek(SeekFrom::Current(-150)).unwrap();
+    let mut range_data: Vec<u8> = Vec::new();
+    patched_reader.read_to_end(&mut range_data).unwrap();
+    assert_eq!(range_data, new_data[offset as usize..]);
+}
+
+#[test]
+fn test_delta_patched_reader_seek_case1() {
+    // Basis with chunk size 2, patched data is cxabc
+    let header = DeltaHeader::new_without_digest(2, 1, 5);
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(vec![b'x']),
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 2,
+        },
+    ];
+    let delta = Delta::new(header, chunk_delta_list);
+    let mut patched_reader = PatchedReader::new(Cursor::new(b"abc".to_vec()), delta).unwrap();
+
+    // Seeking before the start is an error, after the end reads nothing
+    assert!(patched_reader.seek(SeekFrom::Current(-1)).is_err());
+    assert_eq!(patched_reader.seek(SeekFrom::End(2)).unwrap(), 7);
+    let mut data: Vec<u8> = Vec::new();
+    assert_eq!(patched_reader.read_to_end(&mut data).unwrap(), 0);
+
+    // Reads stop at the end of a chunk delta
+    patched_reader.seek(SeekFrom::Start(1)).unwrap();
+    let mut buffer = [0; 4];
+    assert_eq!(patched_reader.read(&mut buffer).unwrap(), 1);
+    assert_eq!(&buffer[..1], b"x");
+    assert_eq!(patched_reader.read(&mut buffer).unwrap(), 3);
+    assert_eq!(&buffer[..3], b"abc");
+}
+
+#[test]
+fn test_delta_patched_reader_error_case1() {
+    // Basis with a different last chunk size
+    let header = DeltaHeader::new_without_digest(2, 2, 2);
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    let result = PatchedReader::new(Cursor::new(b"abc".to_vec()), delta).map(|_| ());
+    assert_eq!(result, Err(RollingHashError::new(DELTA_BASIS_MISMATCH)));
+
+    // Chunk deltas do not add up to delta output size
+    let header = DeltaHeader::new_without_digest(2, 1, 3);
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    let result = PatchedReader::new(Cursor::new(b"abc".to_vec()), delta).map(|_| ());
+    assert_eq!(result, Err(RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR)));
+}
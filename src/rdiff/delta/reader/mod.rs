@@ -0,0 +1,68 @@
+use std::io::{BufReader, Read};
+
+use bincode::deserialize_from;
+
+use crate::rdiff::{
+    compression::{new_compression_ptr, CompressionPtr},
+    error::{messages::INVALID_DELTA_FILE, RollingHashError},
+};
+
+use super::{header::DeltaHeader, ChunkDelta};
+
+// Reads chunk deltas of a delta file in rdiff format one at a time, so the
+// chunk delta list is never kept in memory. Differences are decompressed
+// with the compression recorded in delta header, if any
+pub struct DeltaReader<R: Read> {
+    reader: BufReader<R>,
+    header: DeltaHeader,
+    compression_ptr: Option<CompressionPtr>,
+    remaining_count: u64,
+}
+
+impl<R: Read> DeltaReader<R> {
+    pub fn new(reader: R) -> Result<DeltaReader<R>, RollingHashError> {
+        let mut reader = BufReader::new(reader);
+        // Get delta header, legacy delta files have no header
+        let header = DeltaHeader::read_from(&mut reader)?;
+        let compression_ptr = match header.get_compression_id() {
+            Some(compression_id) => Some(new_compression_ptr(compression_id)?),
+            None => None,
+        };
+        // Chunk delta list is stored as a serialized list, its length comes first
+        let remaining_count: u64 =
+            deserialize_from(&mut reader).map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))?;
+        Ok(DeltaReader {
+            reader,
+            header,
+            compression_ptr,
+            remaining_count,
+        })
+    }
+
+    pub fn get_header(&self) -> &DeltaHeader {
+        &self.header
+    }
+
+    pub fn into_header(self) -> DeltaHeader {
+        self.header
+    }
+
+    // Get next chunk delta, none when there are no more chunk deltas
+    pub fn next_chunk_delta(&mut self) -> Result<Option<ChunkDelta>, RollingHashError> {
+        if self.remaining_count == 0 {
+            return Ok(None);
+        }
+        let mut chunk_delta: ChunkDelta = deserialize_from(&mut self.reader)
+            .map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))?;
+        self.remaining_count -= 1;
+        if let (ChunkDelta::Diff(differences), Some(compression_ptr)) =
+            (&mut chunk_delta, self.compression_ptr.as_ref())
+        {
+            *differences = compression_ptr.decompress(differences)?;
+        }
+        Ok(Some(chunk_delta))
+    }
+}
+
+#[cfg(test)]
+mod tests;
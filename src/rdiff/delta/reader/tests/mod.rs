@@ -0,0 +1,63 @@
+use bincode::serialize;
+
+use crate::rdiff::{
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{messages::INVALID_DELTA_FILE, RollingHashError},
+};
+
+use super::DeltaReader;
+
+#[test]
+fn test_delta_reader_next_chunk_delta_case1() {
+    // Write delta to memory buffer
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(b"xyz".to_vec()),
+        ChunkDelta::CopyRange {
+            start_index: 4,
+            count: 3,
+        },
+    ];
+    let header = DeltaHeader::new_without_digest(4, 2, 19);
+    let delta = Delta::new(header, chunk_delta_list);
+    let mut delta_data: Vec<u8> = Vec::new();
+    delta.write_delta(&mut delta_data).unwrap();
+
+    // Read chunk deltas one at a time
+    let mut delta_reader = DeltaReader::new(delta_data.as_slice()).unwrap();
+    assert_eq!(delta_reader.get_header(), delta.get_header());
+    let mut read_chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+    while let Some(chunk_delta) = delta_reader.next_chunk_delta().unwrap() {
+        read_chunk_delta_list.push(chunk_delta);
+    }
+
+    // Verify computed values
+    assert_eq!(&read_chunk_delta_list, delta.get_chunk_delta_list());
+}
+
+#[test]
+fn test_delta_reader_legacy_delta_case1() {
+    // Legacy delta files only have a chunk delta list
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Match(1)];
+    let delta_data = serialize(&chunk_delta_list).unwrap();
+    let mut delta_reader = DeltaReader::new(delta_data.as_slice()).unwrap();
+    assert!(delta_reader.get_header().is_legacy());
+    assert_eq!(
+        delta_reader.next_chunk_delta().unwrap(),
+        Some(ChunkDelta::Match(1))
+    );
+    assert_eq!(delta_reader.next_chunk_delta().unwrap(), None);
+}
+
+#[test]
+fn test_delta_reader_truncated_delta_case1() {
+    // Chunk delta count is bigger than the number of chunk deltas in file
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Diff(b"abc".to_vec())];
+    let mut delta_data = serialize(&chunk_delta_list).unwrap();
+    delta_data.truncate(delta_data.len() - 1);
+    let mut delta_reader = DeltaReader::new(delta_data.as_slice()).unwrap();
+    assert_eq!(
+        delta_reader.next_chunk_delta(),
+        Err(RollingHashError::new(INVALID_DELTA_FILE))
+    );
+}
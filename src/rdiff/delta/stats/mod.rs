@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+// Delta file statistics, literal data is the data stored in difference chunk deltas
+#[derive(Debug, Default, PartialEq)]
+pub struct DeltaStats {
+    literal_size: usize,
+    stored_literal_size: usize,
+}
+
+impl DeltaStats {
+    pub fn new(literal_size: usize, stored_literal_size: usize) -> DeltaStats {
+        DeltaStats {
+            literal_size,
+            stored_literal_size,
+        }
+    }
+
+    pub fn get_literal_size(&self) -> usize {
+        self.literal_size
+    }
+
+    pub fn get_stored_literal_size(&self) -> usize {
+        self.stored_literal_size
+    }
+
+    pub fn add_literal(&mut self, literal_size: usize, stored_literal_size: usize) {
+        self.literal_size += literal_size;
+        self.stored_literal_size += stored_literal_size;
+    }
+
+    // Percentage of literal data saved by compression, it is negative
+    // when compressed literal data is bigger than literal data
+    pub fn get_saved_percentage(&self) -> f64 {
+        if self.literal_size == 0 {
+            return 0.0;
+        }
+        let saved_size = self.literal_size as f64 - self.stored_literal_size as f64;
+        saved_size * 100.0 / self.literal_size as f64
+    }
+}
+
+impl Display for DeltaStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Literal data: {} bytes, stored: {} bytes, saved: {:.1}%",
+            self.literal_size,
+            self.stored_literal_size,
+            self.get_saved_percentage()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
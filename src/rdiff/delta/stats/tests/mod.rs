@@ -0,0 +1,26 @@
+use super::DeltaStats;
+
+#[test]
+fn test_delta_stats_case1() {
+    let mut stats = DeltaStats::default();
+    assert_eq!(stats.get_saved_percentage(), 0.0);
+
+    stats.add_literal(300, 100);
+    stats.add_literal(100, 100);
+    assert_eq!(stats, DeltaStats::new(400, 200));
+    assert_eq!(stats.get_saved_percentage(), 50.0);
+    assert_eq!(
+        stats.to_string(),
+        "Literal data: 400 bytes, stored: 200 bytes, saved: 50.0%"
+    );
+}
+
+#[test]
+fn test_delta_stats_bigger_stored_literal_case1() {
+    let stats = DeltaStats::new(10, 15);
+    assert_eq!(stats.get_saved_percentage(), -50.0);
+    assert_eq!(
+        stats.to_string(),
+        "Literal data: 10 bytes, stored: 15 bytes, saved: -50.0%"
+    );
+}
@@ -1,15 +1,32 @@
 use std::{
     fs::{self, File},
-    io::{BufReader, BufWriter, Read, Write},
+    io::{BufReader, BufWriter, Cursor, Read, Write},
 };
 
-use bincode::deserialize_from;
+use bincode::serialize;
 
 use crate::rdiff::{
-    constants::BLOCK_SIZE,
-    delta::{ChunkDelta, Delta},
-    hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
-    signature::Signature,
+    chunk::iterator::{fastcdc::FastCdcParameters, BufferedRdiffChunkIterator},
+    compression::rdiff_deflate::{RdiffDeflate, RDIFF_DEFLATE_ID},
+    constants::{BLOCK_SIZE, MAX_DIFF_SIZE, WINDOW_SIZE},
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{
+            DELTA_BASIS_MISMATCH, DELTA_CHUNK_SIZE_MISMATCH, DELTA_DIGEST_MISMATCH,
+            DELTA_OUTPUT_SIZE_ERROR, DELTA_PROCESSED_DATA_SIZE_ERROR, FIXED_SIZE_CHUNKS_REQUIRED,
+            INVALID_CHUNK_INDEX,
+        },
+        RollingHashError,
+    },
+    hash::{
+        strong::{
+            rdiff_sha1::{RdiffSha1, RDIFF_SHA1_ID},
+            StrongHash,
+        },
+        weak::rdiff_addler::RdiffAddler,
+    },
+    io::RdiffFile,
+    signature::{header::BasisFingerprint, Signature},
     util::now_as_millis,
 };
 
@@ -26,19 +43,12 @@ fn test_delta_generate_delta_equals_files_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x);
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -47,26 +57,25 @@ fn test_delta_generate_delta_equals_files_case1() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // Equals to the old one
     {
-        let old_file = File::open(file_name).unwrap();
+        let old_file = File::open(file_name.as_str()).unwrap();
         let mut reader = BufReader::new(old_file);
         let new_file = File::create(new_file_name.clone()).unwrap();
         let mut writer = BufWriter::new(new_file);
-        let mut buffer: [u8; BLOCK_SIZE as usize] = [0; BLOCK_SIZE as usize];
+        let mut buffer: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
         loop {
             let size = reader.read(&mut buffer).unwrap();
-            writer.write(&buffer[..size]).unwrap();
+            writer.write_all(&buffer[..size]).unwrap();
             if size == 0 {
                 break;
             }
@@ -90,11 +99,25 @@ fn test_delta_generate_delta_equals_files_case1() {
     .unwrap();
 
     // Set expected values
-    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta { chunk_delta_list };
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::CopyRange {
+        start_index: 1,
+        count: 3,
+    }];
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta {
+        header,
+        chunk_delta_list,
+    };
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -122,19 +145,12 @@ fn test_delta_generate_delta_chunk_removed_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -143,14 +159,13 @@ fn test_delta_generate_delta_chunk_removed_case2() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By removing second chunk
@@ -158,16 +173,11 @@ fn test_delta_generate_delta_chunk_removed_case2() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Compute Delta
@@ -188,10 +198,22 @@ fn test_delta_generate_delta_chunk_removed_case2() {
     .unwrap();
 
     // Set expected values
-    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta { chunk_delta_list };
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Match(1), ChunkDelta::Match(3)];
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta {
+        header,
+        chunk_delta_list,
+    };
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -219,19 +241,12 @@ fn test_delta_generate_delta_chunk_changed_case3() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -240,14 +255,13 @@ fn test_delta_generate_delta_chunk_changed_case3() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By changing first chunk
@@ -255,19 +269,12 @@ fn test_delta_generate_delta_chunk_changed_case3() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'd'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Compute Delta
@@ -290,13 +297,27 @@ fn test_delta_generate_delta_chunk_changed_case3() {
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..BLOCK_SIZE {
-        differences.push(b'd');
-    }
+    differences.extend_from_slice(&[b'd'; BLOCK_SIZE]);
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta { chunk_delta_list };
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 2,
+        count: 2,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta {
+        header,
+        chunk_delta_list,
+    };
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -324,19 +345,12 @@ fn test_delta_generate_delta_chunk_shifted_case4() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -345,14 +359,13 @@ fn test_delta_generate_delta_chunk_shifted_case4() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By shifted first chunk
@@ -361,22 +374,13 @@ fn test_delta_generate_delta_chunk_shifted_case4() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..shifted_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend(vec![b'd'; shifted_size]);
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Compute Delta
@@ -399,14 +403,27 @@ fn test_delta_generate_delta_chunk_shifted_case4() {
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..shifted_size {
-        differences.push(b'd');
-    }
+    differences.extend(vec![b'd'; shifted_size]);
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta { chunk_delta_list };
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 1,
+        count: 3,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta {
+        header,
+        chunk_delta_list,
+    };
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -433,19 +450,12 @@ fn test_delta_generate_delta_addition_between_chunks_case5() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -454,14 +464,13 @@ fn test_delta_generate_delta_addition_between_chunks_case5() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By addition between chunks
@@ -470,22 +479,13 @@ fn test_delta_generate_delta_addition_between_chunks_case5() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..addition_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend(vec![b'd'; addition_size]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -509,14 +509,28 @@ fn test_delta_generate_delta_addition_between_chunks_case5() {
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..addition_size {
-        differences.push(b'd');
-    }
+    differences.extend(vec![b'd'; addition_size]);
     chunk_delta_list.push(ChunkDelta::Match(1));
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta { chunk_delta_list };
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 2,
+        count: 2,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta {
+        header,
+        chunk_delta_list,
+    };
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -544,19 +558,12 @@ fn test_delta_create_delta_file_equals_files_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -565,14 +572,13 @@ fn test_delta_create_delta_file_equals_files_case1() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // Equals to the old one
@@ -581,10 +587,10 @@ fn test_delta_create_delta_file_equals_files_case1() {
         let mut reader = BufReader::new(old_file);
         let new_file = File::create(new_file_name.clone()).unwrap();
         let mut writer = BufWriter::new(new_file);
-        let mut buffer: [u8; BLOCK_SIZE as usize] = [0; BLOCK_SIZE as usize];
+        let mut buffer: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
         loop {
             let size = reader.read(&mut buffer).unwrap();
-            writer.write(&buffer[..size]).unwrap();
+            writer.write_all(&buffer[..size]).unwrap();
             if size == 0 {
                 break;
             }
@@ -595,7 +601,7 @@ fn test_delta_create_delta_file_equals_files_case1() {
     let strong_hash_ptr = RdiffSha1::new_ptr();
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
-    let result = Delta::create_delta_file(
+    Delta::create_delta_file(
         new_file_name.as_str(),
         delta_file_name.as_str(),
         signature_file_name.as_str(),
@@ -603,14 +609,9 @@ fn test_delta_create_delta_file_equals_files_case1() {
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-
-    // Get delta from file
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     // Get hash functions for Delta
@@ -658,19 +659,12 @@ fn test_delta_create_delta_file_chunk_removed_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -679,14 +673,13 @@ fn test_delta_create_delta_file_chunk_removed_case2() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By removing second chunk
@@ -694,16 +687,11 @@ fn test_delta_create_delta_file_chunk_removed_case2() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -712,7 +700,7 @@ fn test_delta_create_delta_file_chunk_removed_case2() {
     let strong_hash_ptr = RdiffSha1::new_ptr();
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
-    let result = Delta::create_delta_file(
+    Delta::create_delta_file(
         new_file_name.as_str(),
         delta_file_name.as_str(),
         signature_file_name.as_str(),
@@ -720,13 +708,9 @@ fn test_delta_create_delta_file_chunk_removed_case2() {
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    // Get Delta from file
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     // Get hash functions for Delta
@@ -774,19 +758,12 @@ fn test_delta_create_delta_file_chunk_changed_case3() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -795,14 +772,13 @@ fn test_delta_create_delta_file_chunk_changed_case3() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By changing first chunk
@@ -810,19 +786,12 @@ fn test_delta_create_delta_file_chunk_changed_case3() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'd'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -831,7 +800,7 @@ fn test_delta_create_delta_file_chunk_changed_case3() {
     let strong_hash_ptr = RdiffSha1::new_ptr();
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
-    let result = Delta::create_delta_file(
+    Delta::create_delta_file(
         new_file_name.as_str(),
         delta_file_name.as_str(),
         signature_file_name.as_str(),
@@ -839,13 +808,9 @@ fn test_delta_create_delta_file_chunk_changed_case3() {
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    // Get Delta from file
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     // Get hash functions for Delta
@@ -893,19 +858,12 @@ fn test_delta_create_delta_file_chunk_shifted_case4() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -914,14 +872,13 @@ fn test_delta_create_delta_file_chunk_shifted_case4() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By shifted first chunk
@@ -930,22 +887,13 @@ fn test_delta_create_delta_file_chunk_shifted_case4() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..shifted_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend(vec![b'd'; shifted_size]);
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -954,7 +902,7 @@ fn test_delta_create_delta_file_chunk_shifted_case4() {
     let strong_hash_ptr = RdiffSha1::new_ptr();
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
-    let result = Delta::create_delta_file(
+    Delta::create_delta_file(
         new_file_name.as_str(),
         delta_file_name.as_str(),
         signature_file_name.as_str(),
@@ -962,13 +910,9 @@ fn test_delta_create_delta_file_chunk_shifted_case4() {
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    // Get Delta from file
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     // Get hash functions for Delta
@@ -1016,19 +960,12 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Create signature file
@@ -1037,14 +974,13 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Create new file version
     // By addition between chunks
@@ -1053,22 +989,13 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..addition_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend(vec![b'd'; addition_size]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -1077,7 +1004,7 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
     let strong_hash_ptr = RdiffSha1::new_ptr();
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
-    let result = Delta::create_delta_file(
+    Delta::create_delta_file(
         new_file_name.as_str(),
         delta_file_name.as_str(),
         signature_file_name.as_str(),
@@ -1085,13 +1012,9 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    // Get Delta from file
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     // Get hash functions for Delta
@@ -1124,3 +1047,914 @@ fn test_delta_create_delta_file_addition_between_chunks_case5() {
     fs::remove_file(new_file_name).unwrap();
     fs::remove_file(delta_file_name).unwrap();
 }
+
+#[test]
+fn test_delta_create_patched_file_chunk_shifted_case1() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_case1.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    {
+        let file = File::create(file_name.as_str()).unwrap();
+        let mut writer = BufWriter::new(file);
+        let mut input_data: Vec<u8> = Vec::new();
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+        writer.write_all(&input_data).unwrap();
+    }
+    // Create signature file
+    let strong_hash_ptr = RdiffSha1::new_ptr();
+    let weak_hash_ptr = RdiffAddler::new_ptr();
+    Signature::create_signature_file(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        weak_hash_ptr,
+        strong_hash_ptr,
+    )
+    .unwrap();
+
+    // Create new file version
+    // By shifting chunks and moving the last chunk to the beginning
+    let mut new_data: Vec<u8> = Vec::new();
+    new_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+    new_data.extend_from_slice(&[b'd'; 10]);
+    new_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+    {
+        let file = File::create(new_file_name.as_str()).unwrap();
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&new_data).unwrap();
+    }
+
+    // Create delta file
+    let strong_hash_ptr = RdiffSha1::new_ptr();
+    let weak_hash_ptr = RdiffAddler::new_ptr();
+    Delta::create_delta_file(
+        new_file_name.as_str(),
+        delta_file_name.as_str(),
+        signature_file_name.as_str(),
+        weak_hash_ptr,
+        strong_hash_ptr,
+    )
+    .unwrap();
+
+    // Create patched file
+    Delta::create_patched_file(
+        file_name.as_str(),
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_apply_last_chunk_case1() {
+    // Get basis chunk iterator, chunk size 2 and last chunk size 1
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let basis_data = fs::read(file_name).unwrap();
+
+    // Apply delta
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(vec![b'x']),
+        ChunkDelta::Match(1),
+    ];
+    let header = DeltaHeader::new(
+        2,
+        1,
+        4,
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(b"axaa"),
+        None,
+    );
+    let delta = Delta::new(header, chunk_delta_list);
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply(&mut iterator, &mut output).unwrap();
+
+    // Verify computed values
+    let mut expected_output: Vec<u8> = Vec::new();
+    expected_output.extend_from_slice(&basis_data[2..]);
+    expected_output.push(b'x');
+    expected_output.extend_from_slice(&basis_data[..2]);
+    assert_eq!(output, expected_output);
+}
+
+#[test]
+fn test_delta_apply_invalid_chunk_index_case1() {
+    // Get basis chunk iterator with two chunks
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+
+    // Apply delta
+    let header = DeltaHeader::new(2, 1, 2, RDIFF_SHA1_ID, Vec::new(), None);
+    let delta = Delta::new(header, vec![ChunkDelta::Match(3)]);
+    let mut output: Vec<u8> = Vec::new();
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+
+    // Verify computed values
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_INDEX));
+}
+
+#[test]
+fn test_delta_create_patched_file_large_file_case2() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_case2.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file, bigger than the delta window
+    let mut seed: u32 = 7;
+    let mut next_byte = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) as u8
+    };
+    let old_data: Vec<u8> = (0..(2 * WINDOW_SIZE + 100)).map(|_| next_byte()).collect();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature file
+    let strong_hash_ptr = RdiffSha1::new_ptr();
+    let weak_hash_ptr = RdiffAddler::new_ptr();
+    Signature::create_signature_file(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        weak_hash_ptr,
+        strong_hash_ptr,
+    )
+    .unwrap();
+
+    // Create new file version
+    // By inserting and changing data at several places
+    let mut new_data: Vec<u8> = Vec::new();
+    new_data.extend((0..1000).map(|_| next_byte()));
+    new_data.extend_from_slice(&old_data[..WINDOW_SIZE - 3]);
+    new_data.extend((0..WINDOW_SIZE + 5).map(|_| next_byte()));
+    new_data.extend_from_slice(&old_data[WINDOW_SIZE + 50..]);
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta file
+    let strong_hash_ptr = RdiffSha1::new_ptr();
+    let weak_hash_ptr = RdiffAddler::new_ptr();
+    Delta::create_delta_file(
+        new_file_name.as_str(),
+        delta_file_name.as_str(),
+        signature_file_name.as_str(),
+        weak_hash_ptr,
+        strong_hash_ptr,
+    )
+    .unwrap();
+
+    // Create patched file
+    Delta::create_patched_file(
+        file_name.as_str(),
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_create_patched_file_legacy_delta_case3() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_case3.{}",
+        now_as_millis()
+    );
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+    let file_name = "resources/poem.txt";
+    let basis_data = fs::read(file_name).unwrap();
+
+    // Create legacy delta file, it only contains the chunk delta list
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Diff(vec![b'x'; 3]),
+        ChunkDelta::Match(2),
+        ChunkDelta::Match(1),
+    ];
+    fs::write(
+        delta_file_name.as_str(),
+        serialize(&chunk_delta_list).unwrap(),
+    )
+    .unwrap();
+
+    // Create patched file, chunk size is computed from basis file
+    Delta::create_patched_file(
+        file_name,
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let mut expected_data: Vec<u8> = vec![b'x'; 3];
+    expected_data.extend_from_slice(&basis_data[BLOCK_SIZE..2 * BLOCK_SIZE]);
+    expected_data.extend_from_slice(&basis_data[..BLOCK_SIZE]);
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, expected_data);
+
+    // Clean up verification
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_create_patched_file_legacy_delta_case4() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_case4.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create basis file bigger than 16 KiB, its signature chunk size is now bigger
+    // than block size
+    let basis_data: Vec<u8> = (0..100 * 1024).map(|i| (i % 251) as u8).collect();
+    fs::write(file_name.as_str(), &basis_data).unwrap();
+
+    // Create legacy delta file, its chunks were of block size
+    let chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(800),
+        ChunkDelta::Diff(vec![b'x'; 3]),
+        ChunkDelta::Match(2),
+    ];
+    fs::write(
+        delta_file_name.as_str(),
+        serialize(&chunk_delta_list).unwrap(),
+    )
+    .unwrap();
+
+    // Create patched file, chunk size is computed as for legacy signatures
+    Delta::create_patched_file(
+        file_name.as_str(),
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let mut expected_data: Vec<u8> = basis_data[799 * BLOCK_SIZE..].to_vec();
+    expected_data.extend_from_slice(&[b'x'; 3]);
+    expected_data.extend_from_slice(&basis_data[BLOCK_SIZE..2 * BLOCK_SIZE]);
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, expected_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_apply_basis_mismatch_case1() {
+    // Get basis chunk iterator, chunk size 2 and last chunk size 1
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Match(1)];
+    let mut output: Vec<u8> = Vec::new();
+
+    // Delta created with a different chunk size
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let header = DeltaHeader::new(3, 1, 2, RDIFF_SHA1_ID, Vec::new(), None);
+    let delta = Delta::new(header, chunk_delta_list);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_CHUNK_SIZE_MISMATCH));
+
+    // Delta created with a different basis file
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let header = DeltaHeader::new(2, 2, 2, RDIFF_SHA1_ID, Vec::new(), None);
+    let delta = Delta::new(header, delta.chunk_delta_list);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+
+    // Delta with a different output size
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let header = DeltaHeader::new(2, 1, 3, RDIFF_SHA1_ID, Vec::new(), None);
+    let delta = Delta::new(header, delta.chunk_delta_list);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_OUTPUT_SIZE_ERROR));
+
+    // Delta with a different output digest
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let header = DeltaHeader::new(2, 1, 2, RDIFF_SHA1_ID, RdiffSha1::new().digest(b"ab"), None);
+    let delta = Delta::new(header, delta.chunk_delta_list);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_DIGEST_MISMATCH));
+}
+
+#[test]
+fn test_delta_apply_basis_fingerprint_mismatch_case1() {
+    // Get basis chunk iterator, chunk size 2 and last chunk size 1
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let basis_data = fs::read(file_name).unwrap();
+    let mut output: Vec<u8> = Vec::new();
+
+    // Delta created from the same basis file
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let basis = BasisFingerprint::new(basis_data.len(), RdiffSha1::new().digest(&basis_data));
+    let digest = RdiffSha1::new().digest(&basis_data[..2]);
+    let header = DeltaHeader::new(2, 1, 2, RDIFF_SHA1_ID, digest.clone(), Some(basis));
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    delta.apply(&mut iterator, &mut output).unwrap();
+    assert_eq!(output, basis_data[..2]);
+
+    // Delta created from a basis file with the same size and a different digest
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let basis = BasisFingerprint::new(basis_data.len(), RdiffSha1::new().digest(b"xyz"));
+    let header = DeltaHeader::new(2, 1, 2, RDIFF_SHA1_ID, digest.clone(), Some(basis));
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+
+    // Delta created from a basis file with a different size
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let basis = BasisFingerprint::new(basis_data.len() + 2, RdiffSha1::new().digest(&basis_data));
+    let header = DeltaHeader::new(2, 1, 2, RDIFF_SHA1_ID, digest, Some(basis));
+    let delta = Delta::new(header, vec![ChunkDelta::Match(1)]);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+}
+
+#[test]
+fn test_delta_create_patched_file_basis_mismatch_case4() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_case4.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let other_file_name = format!("{}.other.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file and another file with the same size and chunk geometry
+    let mut old_data: Vec<u8> = Vec::new();
+    old_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+    fs::write(file_name.as_str(), &old_data).unwrap();
+    let mut other_data = old_data.clone();
+    other_data[BLOCK_SIZE + 1] = b'x';
+    fs::write(other_file_name.as_str(), &other_data).unwrap();
+
+    // Create signature file
+    Signature::create_signature_file(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Create new file version and delta file
+    let mut new_data = old_data.clone();
+    new_data.extend_from_slice(b"new data");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+    Delta::create_delta_file(
+        new_file_name.as_str(),
+        delta_file_name.as_str(),
+        signature_file_name.as_str(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Create patched file from the other file
+    let error = Delta::create_patched_file(
+        other_file_name.as_str(),
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap_err();
+
+    // Verify computed values, nothing is written
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+    assert!(fs::metadata(patched_file_name.as_str()).is_err());
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(other_file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_push_match_case1() {
+    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+    for index in [1, 2, 3, 5, 4, 5, 6] {
+        Delta::push_match(&mut chunk_delta_list, ChunkDelta::Match(index));
+    }
+    chunk_delta_list.push(ChunkDelta::Diff(vec![b'x']));
+    Delta::push_match(&mut chunk_delta_list, ChunkDelta::Match(7));
+    Delta::push_match(&mut chunk_delta_list, ChunkDelta::Match(1));
+    Delta::push_match(&mut chunk_delta_list, ChunkDelta::Match(1));
+
+    // Verify computed values
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 3,
+        },
+        ChunkDelta::Match(5),
+        ChunkDelta::CopyRange {
+            start_index: 4,
+            count: 3,
+        },
+        ChunkDelta::Diff(vec![b'x']),
+        ChunkDelta::Match(7),
+        ChunkDelta::Match(1),
+        ChunkDelta::Match(1),
+    ];
+    assert_eq!(chunk_delta_list, expected_chunk_delta_list);
+}
+
+#[test]
+fn test_delta_apply_copy_range_case1() {
+    // Get basis chunk iterator, chunk size 2 and last chunk size 1
+    let file_name = "resources/buffered_rdiff_chunk_iterator_file1.txt";
+    let basis_data = fs::read(file_name).unwrap();
+    let copy_range = ChunkDelta::CopyRange {
+        start_index: 1,
+        count: 2,
+    };
+
+    // Apply delta
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Diff(vec![b'x']), copy_range];
+    let mut expected_output: Vec<u8> = vec![b'x'];
+    expected_output.extend_from_slice(&basis_data);
+    let digest = RdiffSha1::new().digest(&expected_output);
+    let header = DeltaHeader::new(2, 1, 4, RDIFF_SHA1_ID, digest, None);
+    let delta = Delta::new(header, chunk_delta_list);
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply(&mut iterator, &mut output).unwrap();
+    assert_eq!(output, expected_output);
+
+    // Apply delta with a copy range past the last chunk
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let copy_range = ChunkDelta::CopyRange {
+        start_index: 2,
+        count: 2,
+    };
+    let header = DeltaHeader::new(2, 1, 4, RDIFF_SHA1_ID, Vec::new(), None);
+    let delta = Delta::new(header, vec![copy_range]);
+    let error = delta.apply(&mut iterator, &mut output).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_INDEX));
+}
+
+#[test]
+fn test_delta_generate_delta_large_equals_files_case6() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_generate_delta_case6.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+
+    // Create file with many chunks
+    let mut seed: u32 = 11;
+    let data: Vec<u8> = (0..(4 * WINDOW_SIZE))
+        .map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) as u8
+        })
+        .collect();
+    fs::write(file_name.as_str(), &data).unwrap();
+
+    // Create signature and delta of the same file
+    Signature::create_signature_file(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    let chunk_size = signature.get_chunk_size();
+    let delta = Delta::generate_delta(
+        file_name.as_str(),
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Verify computed values, all chunks are in one copy range
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::CopyRange {
+        start_index: 1,
+        count: (data.len() / chunk_size) as u32,
+    }];
+    assert_eq!(delta.chunk_delta_list, expected_chunk_delta_list);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_create_delta_file_with_compression_case1() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_delta_file_compression_case1.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Create old file
+    let mut old_data: Vec<u8> = Vec::new();
+    old_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature file
+    Signature::create_signature_file(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Create new file version
+    // By adding repeated text between chunks
+    let mut new_data: Vec<u8> = Vec::new();
+    new_data.extend_from_slice(&old_data[..BLOCK_SIZE]);
+    new_data.extend(b"new text ".repeat(100));
+    new_data.extend_from_slice(&old_data[BLOCK_SIZE..]);
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create compressed delta file
+    let stats = Delta::create_delta_file_from_signature_with_compression(
+        new_file_name.as_str(),
+        delta_file_name.as_str(),
+        signature_file_name.as_str(),
+        RdiffDeflate::new_ptr(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
+    assert_eq!(delta.header.get_compression_id(), Some(RDIFF_DEFLATE_ID));
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Match(1),
+        ChunkDelta::Diff(b"new text ".repeat(100)),
+        ChunkDelta::CopyRange {
+            start_index: 2,
+            count: 2,
+        },
+    ];
+    assert_eq!(delta.chunk_delta_list, expected_chunk_delta_list);
+    assert_eq!(stats.get_literal_size(), 900);
+    assert!(stats.get_stored_literal_size() < stats.get_literal_size());
+    let delta_file_size = fs::metadata(delta_file_name.as_str()).unwrap().len() as usize;
+    assert!(delta_file_size < stats.get_literal_size());
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_write_delta_with_compression_case2() {
+    // Differences longer than the decompressed size limit
+    let differences: Vec<u8> = (0..(2 * MAX_DIFF_SIZE + 10)).map(|i| i as u8).collect();
+    let mut header = DeltaHeader::new_without_digest(BLOCK_SIZE, BLOCK_SIZE, differences.len());
+    header.set_compression_id(Some(RDIFF_DEFLATE_ID));
+    let delta = Delta::new(header, vec![ChunkDelta::Diff(differences.clone())]);
+    let mut data: Vec<u8> = Vec::new();
+    let stats = delta.write_delta(&mut data).unwrap();
+    assert_eq!(stats.get_literal_size(), differences.len());
+
+    // Differences are written in parts readers can decompress
+    let computed_delta = Delta::get_delta_from_reader(data.as_slice()).unwrap();
+    let expected_chunk_delta_list: Vec<ChunkDelta> = differences
+        .chunks(MAX_DIFF_SIZE)
+        .map(|differences| ChunkDelta::Diff(differences.to_vec()))
+        .collect();
+    assert_eq!(computed_delta.chunk_delta_list, expected_chunk_delta_list);
+}
+
+#[test]
+fn test_delta_create_patched_file_fastcdc_case1() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/test_create_patched_file_fastcdc_case1.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file with pseudo random content
+    let mut state: u32 = 1;
+    let input_data: Vec<u8> = (0..64 * 1024)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as u8
+        })
+        .collect();
+    fs::write(file_name.as_str(), &input_data).unwrap();
+
+    // Create signature file with content defined chunks
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(1024).unwrap();
+    Signature::create_signature_file_with_fastcdc(
+        file_name.as_str(),
+        signature_file_name.as_str(),
+        fastcdc_parameters,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Create new file version by inserting bytes, which shifts every following chunk
+    let mut new_data = input_data.clone();
+    new_data.splice(30000..30000, [b'x'; 10]);
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta file
+    Delta::create_delta_file_from_signature(
+        new_file_name.as_str(),
+        delta_file_name.as_str(),
+        signature_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify delta, only the chunks around the insertion are differences
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
+    assert_eq!(
+        delta.get_header().get_fastcdc_parameters(),
+        Some(&fastcdc_parameters)
+    );
+    let differences_size: usize = delta
+        .get_chunk_delta_list()
+        .iter()
+        .map(|chunk_delta| match chunk_delta {
+            ChunkDelta::Diff(differences) => differences.len(),
+            _ => 0,
+        })
+        .sum();
+    assert!(differences_size <= 2 * 4 * 1024 + 10);
+
+    // Content defined chunks can not be exported to other delta formats
+    let mut data: Vec<u8> = Vec::new();
+    let error = delta.write_librsync_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+    let error = delta.write_vcdiff_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+
+    // Create patched file
+    Delta::create_patched_file(
+        file_name.as_str(),
+        delta_file_name.as_str(),
+        patched_file_name.as_str(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn test_delta_generate_delta_truncated_digests_case7() {
+    // Create signature with chunk digests truncated to 4 bytes
+    let mut signature = Signature::create_signature_with_chunk_size(
+        "resources/poem.txt",
+        16,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    signature.truncate_digests(4).unwrap();
+
+    // Create delta of a new file version
+    let delta = Delta::generate_delta(
+        "resources/poem.v3.txt",
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Verify computed values, chunks are found with the digest prefix
+    // as with full digests
+    let signature = Signature::create_signature_with_chunk_size(
+        "resources/poem.txt",
+        16,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let expected_delta = Delta::generate_delta(
+        "resources/poem.v3.txt",
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    assert_eq!(delta.chunk_delta_list, expected_delta.chunk_delta_list);
+    assert!(delta
+        .chunk_delta_list
+        .iter()
+        .any(|chunk_delta| matches!(chunk_delta, ChunkDelta::CopyRange { .. })));
+
+    // New file digest is not truncated
+    assert_eq!(delta.get_header().get_digest().unwrap().len(), 20);
+}
+
+#[test]
+fn test_delta_generate_delta_from_bytes_case1() {
+    // Get old and new data in memory
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let mut new_data = old_data.clone();
+    new_data[100..110].copy_from_slice(b"0123456789");
+    new_data.extend_from_slice(b"appended line\n");
+
+    // Get signature and delta without touching files
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let chunk_size = signature.get_chunk_size();
+    let delta = Delta::generate_delta_from_bytes(new_data.as_slice(), signature).unwrap();
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let reader_delta =
+        Delta::generate_delta_from_reader(Cursor::new(new_data.clone()), signature).unwrap();
+    assert_eq!(reader_delta, delta);
+
+    // Write delta to memory buffer and read it back
+    let mut delta_data: Vec<u8> = Vec::new();
+    delta.write_delta(&mut delta_data).unwrap();
+    let read_delta = Delta::get_delta_from_reader(delta_data.as_slice()).unwrap();
+    assert_eq!(read_delta, delta);
+
+    // Apply delta over memory basis
+    let basis_file = RdiffFile::new_with_bytes("basis", old_data.as_slice());
+    let mut iterator =
+        BufferedRdiffChunkIterator::new_with_chunk_size(chunk_size, basis_file).unwrap();
+    let mut output: Vec<u8> = Vec::new();
+    read_delta.apply(&mut iterator, &mut output).unwrap();
+
+    // Verify computed values
+    assert_eq!(output, new_data);
+}
+
+// Reader returning at most a few bytes at a time, as a pipe may do
+struct TrickleReader<'a> {
+    data: &'a [u8],
+}
+
+impl Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = buf.len().min(1021).min(self.data.len());
+        buf[..size].copy_from_slice(&self.data[..size]);
+        self.data = &self.data[size..];
+        Ok(size)
+    }
+}
+
+#[test]
+fn test_delta_generate_delta_from_unsized_reader_case8() {
+    // Get old data with many chunks and new data with changes across windows
+    let mut seed: u32 = 17;
+    let old_data: Vec<u8> = (0..(3 * WINDOW_SIZE))
+        .map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) as u8
+        })
+        .collect();
+    let mut new_data = old_data.clone();
+    new_data[WINDOW_SIZE - 3..WINDOW_SIZE + 3].copy_from_slice(b"012345");
+    new_data.splice(
+        2 * WINDOW_SIZE..2 * WINDOW_SIZE,
+        b"inserted".iter().copied(),
+    );
+    new_data.truncate(new_data.len() - 5);
+
+    // Get delta from data of known size and from a stream of unknown size
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let chunk_size = signature.get_chunk_size();
+    let expected_delta = Delta::generate_delta_from_bytes(new_data.as_slice(), signature).unwrap();
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let reader = TrickleReader {
+        data: new_data.as_slice(),
+    };
+    let delta = Delta::generate_delta_from_reader(reader, signature).unwrap();
+
+    // Verify computed values
+    assert_eq!(delta, expected_delta);
+    assert_eq!(delta.get_header().get_output_size(), Some(new_data.len()));
+    let basis_file = RdiffFile::new_with_bytes("basis", old_data.as_slice());
+    let mut iterator =
+        BufferedRdiffChunkIterator::new_with_chunk_size(chunk_size, basis_file).unwrap();
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply(&mut iterator, &mut output).unwrap();
+    assert_eq!(output, new_data);
+}
+
+#[test]
+fn test_delta_generate_delta_processed_data_size_error_case1() {
+    // A file of known size must be read entirely
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let rdiff_file =
+        RdiffFile::new_with_reader("short", old_data.as_slice(), old_data.len() as u64 + 1);
+    let result = Delta::generate_delta_from_rdiff_file(
+        rdiff_file,
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    );
+
+    // Verify computed values
+    assert_eq!(
+        result,
+        Err(RollingHashError::new(DELTA_PROCESSED_DATA_SIZE_ERROR))
+    );
+}
@@ -0,0 +1,612 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::rdiff::{
+    constants::{READ_BLOCK_SIZE, VCDIFF_MAGIC, VCDIFF_MAX_WINDOW_SIZE, VCDIFF_WINDOW_SIZE},
+    error::{
+        messages::{INVALID_DELTA_FILE, UNSUPPORTED_VCDIFF_FEATURE},
+        RollingHashError,
+    },
+};
+
+use super::{header::DeltaHeader, ByteOperation, ChunkDelta, Delta};
+
+// VCDIFF (RFC 3284) header indicator bits
+const VCD_DECOMPRESS: u8 = 0x01;
+const VCD_CODETABLE: u8 = 0x02;
+const VCD_APPHEADER: u8 = 0x04;
+// Window indicator bits, adler32 checksum of the target window is an xdelta3 extension
+const VCD_SOURCE: u8 = 0x01;
+const VCD_TARGET: u8 = 0x02;
+const VCD_ADLER32: u8 = 0x04;
+// Instruction types
+const VCD_NOOP: u8 = 0;
+const VCD_ADD: u8 = 1;
+const VCD_RUN: u8 = 2;
+const VCD_COPY: u8 = 3;
+// Default code table instructions used when writing, ADD with sizes up to 17 and
+// COPY in self mode with sizes from 4 to 18 have their size in the instruction code
+const VCD_ADD_0: u8 = 1;
+const VCD_ADD_MAX_SIZE: usize = 17;
+const VCD_COPY_0: u8 = 19;
+const VCD_COPY_MIN_SIZE: usize = 4;
+const VCD_COPY_MAX_SIZE: usize = 18;
+// Default code table address cache sizes and modes
+const VCD_NEAR_SIZE: usize = 4;
+const VCD_SAME_SIZE: usize = 3;
+const VCD_SELF_MODE: u8 = 0;
+const VCD_HERE_MODE: u8 = 1;
+const VCD_NEAR_MODE: u8 = 2;
+const VCD_SAME_MODE: u8 = VCD_NEAR_MODE + VCD_NEAR_SIZE as u8;
+
+// Code table instruction, type, size (0 when it is in the instructions section) and mode
+type VcdiffInstruction = (u8, usize, u8);
+
+// Target window data, either literal data or a basis byte range
+#[derive(Debug, PartialEq)]
+enum VcdiffSegment {
+    Literal(Vec<u8>),
+    Source { offset: usize, length: usize },
+}
+
+impl VcdiffSegment {
+    fn len(&self) -> usize {
+        match self {
+            VcdiffSegment::Literal(data) => data.len(),
+            VcdiffSegment::Source { length, .. } => *length,
+        }
+    }
+}
+
+// Near and same address caches, reset at every window
+struct VcdiffAddressCache {
+    near: [usize; VCD_NEAR_SIZE],
+    next_slot: usize,
+    same: Vec<usize>,
+}
+
+impl VcdiffAddressCache {
+    fn new() -> VcdiffAddressCache {
+        VcdiffAddressCache {
+            near: [0; VCD_NEAR_SIZE],
+            next_slot: 0,
+            same: vec![0; VCD_SAME_SIZE * 256],
+        }
+    }
+
+    fn decode(
+        &mut self,
+        mode: u8,
+        here: usize,
+        addresses: &mut &[u8],
+    ) -> Result<usize, RollingHashError> {
+        let address = match mode {
+            VCD_SELF_MODE => Some(Delta::read_vcdiff_int(addresses)?),
+            VCD_HERE_MODE => here.checked_sub(Delta::read_vcdiff_int(addresses)?),
+            mode if mode < VCD_SAME_MODE => self.near[(mode - VCD_NEAR_MODE) as usize]
+                .checked_add(Delta::read_vcdiff_int(addresses)?),
+            mode => {
+                let mut byte = [0_u8; 1];
+                Delta::read_vcdiff_bytes(addresses, &mut byte)?;
+                self.same
+                    .get((mode - VCD_SAME_MODE) as usize * 256 + byte[0] as usize)
+                    .copied()
+            }
+        }
+        .filter(|address| *address < here)
+        .ok_or_else(|| RollingHashError::new(INVALID_DELTA_FILE))?;
+        // Update caches with the decoded address
+        self.near[self.next_slot] = address;
+        self.next_slot = (self.next_slot + 1) % VCD_NEAR_SIZE;
+        let same_size = self.same.len();
+        self.same[address % same_size] = address;
+        Ok(address)
+    }
+}
+
+// VCDIFF delta file format: header followed by windows of COPY and ADD instructions
+// against a segment of the basis file. Deltas are written with the default code table,
+// single instructions and self mode addresses, and read with any default code table
+// instruction and address mode
+impl Delta {
+    pub fn is_vcdiff_delta(data: &[u8]) -> bool {
+        data.len() >= 4 && data[..4] == VCDIFF_MAGIC
+    }
+
+    pub fn write_vcdiff_delta<W: Write>(&self, writer: &mut W) -> Result<(), RollingHashError> {
+        // Chunk geometry is needed to get basis byte offsets from chunk indexes
+        let byte_operations = self.get_byte_operations()?;
+        // Header without secondary compression, code table nor application data
+        Delta::write_vcdiff_data(writer, &VCDIFF_MAGIC)?;
+        Delta::write_vcdiff_data(writer, &[0])?;
+        // Split operations in windows of at most VCDIFF_WINDOW_SIZE patched data bytes
+        let mut window: Vec<ByteOperation> = Vec::new();
+        let mut window_size: usize = 0;
+        for mut byte_operation in byte_operations {
+            loop {
+                let free_size = VCDIFF_WINDOW_SIZE - window_size;
+                let remaining_operation = match byte_operation {
+                    ByteOperation::Literal(data) if data.len() > free_size => {
+                        byte_operation = ByteOperation::Literal(&data[..free_size]);
+                        Some(ByteOperation::Literal(&data[free_size..]))
+                    }
+                    ByteOperation::Copy { offset, length } if length > free_size => {
+                        byte_operation = ByteOperation::Copy {
+                            offset,
+                            length: free_size,
+                        };
+                        Some(ByteOperation::Copy {
+                            offset: offset + free_size,
+                            length: length - free_size,
+                        })
+                    }
+                    _ => None,
+                };
+                window_size += Delta::get_byte_operation_size(&byte_operation);
+                window.push(byte_operation);
+                if window_size == VCDIFF_WINDOW_SIZE {
+                    Delta::write_vcdiff_window(writer, &window, window_size)?;
+                    window.clear();
+                    window_size = 0;
+                }
+                match remaining_operation {
+                    Some(remaining_operation) => byte_operation = remaining_operation,
+                    None => break,
+                }
+            }
+        }
+        if !window.is_empty() {
+            Delta::write_vcdiff_window(writer, &window, window_size)?;
+        }
+        Ok(())
+    }
+
+    fn get_byte_operation_size(byte_operation: &ByteOperation) -> usize {
+        match byte_operation {
+            ByteOperation::Literal(data) => data.len(),
+            ByteOperation::Copy { length, .. } => *length,
+        }
+    }
+
+    fn write_vcdiff_window<W: Write>(
+        writer: &mut W,
+        window: &[ByteOperation],
+        window_size: usize,
+    ) -> Result<(), RollingHashError> {
+        // Source segment is the basis byte range used by window copies
+        let source_start = window
+            .iter()
+            .filter_map(|byte_operation| match byte_operation {
+                ByteOperation::Copy { offset, .. } => Some(*offset),
+                _ => None,
+            })
+            .min();
+        let source_end = window
+            .iter()
+            .filter_map(|byte_operation| match byte_operation {
+                ByteOperation::Copy { offset, length } => Some(offset + length),
+                _ => None,
+            })
+            .max();
+        // Get data, instructions and addresses sections
+        let mut data: Vec<u8> = Vec::new();
+        let mut instructions: Vec<u8> = Vec::new();
+        let mut addresses: Vec<u8> = Vec::new();
+        for byte_operation in window.iter() {
+            match byte_operation {
+                ByteOperation::Literal(literal) => {
+                    data.extend_from_slice(literal);
+                    if literal.len() <= VCD_ADD_MAX_SIZE {
+                        instructions.push(VCD_ADD_0 + literal.len() as u8);
+                    } else {
+                        instructions.push(VCD_ADD_0);
+                        Delta::push_vcdiff_int(&mut instructions, literal.len());
+                    }
+                }
+                ByteOperation::Copy { offset, length } => {
+                    if (VCD_COPY_MIN_SIZE..=VCD_COPY_MAX_SIZE).contains(length) {
+                        let size_index = length - VCD_COPY_MIN_SIZE + 1;
+                        instructions.push(VCD_COPY_0 + size_index as u8);
+                    } else {
+                        instructions.push(VCD_COPY_0);
+                        Delta::push_vcdiff_int(&mut instructions, *length);
+                    }
+                    Delta::push_vcdiff_int(&mut addresses, offset - source_start.unwrap_or(0));
+                }
+            }
+        }
+        // Get delta encoding
+        let mut encoding: Vec<u8> = Vec::new();
+        Delta::push_vcdiff_int(&mut encoding, window_size);
+        encoding.push(0);
+        Delta::push_vcdiff_int(&mut encoding, data.len());
+        Delta::push_vcdiff_int(&mut encoding, instructions.len());
+        Delta::push_vcdiff_int(&mut encoding, addresses.len());
+        encoding.extend_from_slice(&data);
+        encoding.extend_from_slice(&instructions);
+        encoding.extend_from_slice(&addresses);
+        // Get window, a window without copies has no source segment
+        let mut window_data: Vec<u8> = Vec::new();
+        match (source_start, source_end) {
+            (Some(source_start), Some(source_end)) => {
+                window_data.push(VCD_SOURCE);
+                Delta::push_vcdiff_int(&mut window_data, source_end - source_start);
+                Delta::push_vcdiff_int(&mut window_data, source_start);
+            }
+            _ => window_data.push(0),
+        }
+        Delta::push_vcdiff_int(&mut window_data, encoding.len());
+        Delta::write_vcdiff_data(writer, &window_data)?;
+        Delta::write_vcdiff_data(writer, &encoding)
+    }
+
+    fn write_vcdiff_data<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), RollingHashError> {
+        writer
+            .write_all(data)
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    fn push_vcdiff_int(data: &mut Vec<u8>, value: usize) {
+        // Big endian base 128 digits, all but the last one have the high bit set
+        let mut value = value as u64;
+        let mut digits: Vec<u8> = vec![(value & 0x7F) as u8];
+        value >>= 7;
+        while value != 0 {
+            digits.push(0x80 | (value & 0x7F) as u8);
+            value >>= 7;
+        }
+        data.extend(digits.iter().rev());
+    }
+
+    // Copies of basis byte ranges are split in chunks of chunk size, so they
+    // must start at a chunk and only the basis last chunk may be shorter.
+    // Encoders like xdelta3 or open-vcdiff copy any byte range, their deltas
+    // are read with read_vcdiff_delta_with_basis
+    pub fn read_vcdiff_delta<R: Read>(
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        if chunk_size == 0 {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        let segments = Delta::read_vcdiff_segments(reader)?;
+        // Get chunk delta list, basis byte ranges are split in chunks of chunk size
+        let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+        let mut output_size: usize = 0;
+        let mut last_chunk_size: usize = 0;
+        for (_, segment) in segments.iter() {
+            match segment {
+                VcdiffSegment::Literal(data) => Delta::push_literal(&mut chunk_delta_list, data),
+                VcdiffSegment::Source { offset, length } => Delta::push_byte_copy(
+                    &mut chunk_delta_list,
+                    *offset,
+                    *length,
+                    chunk_size,
+                    &mut last_chunk_size,
+                )?,
+            }
+            output_size += segment.len();
+        }
+        let header = DeltaHeader::new_without_digest(chunk_size, last_chunk_size, output_size);
+        Ok(Delta {
+            header,
+            chunk_delta_list,
+        })
+    }
+
+    // Copies of basis byte ranges are split in chunks of chunk size, parts of
+    // chunks they start or end in are read from basis as differences
+    pub fn read_vcdiff_delta_with_basis<R: Read, B: Read + Seek>(
+        reader: &mut R,
+        basis: &mut B,
+        chunk_size: usize,
+    ) -> Result<Delta, RollingHashError> {
+        if chunk_size == 0 {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        let segments = Delta::read_vcdiff_segments(reader)?;
+        let basis_size = basis
+            .seek(SeekFrom::End(0))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        // Get chunk delta list
+        let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+        let mut output_size: usize = 0;
+        let mut buffer = vec![0; READ_BLOCK_SIZE];
+        for (_, segment) in segments.iter() {
+            match segment {
+                VcdiffSegment::Literal(data) => Delta::push_literal(&mut chunk_delta_list, data),
+                VcdiffSegment::Source { offset, length } => Delta::push_basis_copy(
+                    &mut chunk_delta_list,
+                    basis,
+                    basis_size,
+                    *offset,
+                    *length,
+                    chunk_size,
+                    &mut buffer,
+                )?,
+            }
+            output_size += segment.len();
+        }
+        let last_chunk_size = Delta::get_basis_last_chunk_size(basis_size, chunk_size);
+        let header = DeltaHeader::new_without_digest(chunk_size, last_chunk_size, output_size);
+        Ok(Delta {
+            header,
+            chunk_delta_list,
+        })
+    }
+
+    fn read_vcdiff_segments<R: Read>(
+        reader: &mut R,
+    ) -> Result<Vec<(usize, VcdiffSegment)>, RollingHashError> {
+        // Check magic number and header indicator
+        let mut magic = [0_u8; 4];
+        Delta::read_vcdiff_bytes(reader, &mut magic)?;
+        if !Delta::is_vcdiff_delta(&magic) {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        let mut header_indicator = [0_u8; 1];
+        Delta::read_vcdiff_bytes(reader, &mut header_indicator)?;
+        if header_indicator[0] & (VCD_DECOMPRESS | VCD_CODETABLE) != 0 {
+            return Err(RollingHashError::new(UNSUPPORTED_VCDIFF_FEATURE));
+        }
+        if header_indicator[0] & !(VCD_DECOMPRESS | VCD_CODETABLE | VCD_APPHEADER) != 0 {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        // Skip application data
+        if header_indicator[0] & VCD_APPHEADER != 0 {
+            let length = Delta::read_vcdiff_int(reader)?;
+            Delta::read_vcdiff_section(reader, length)?;
+        }
+        // Get patched data segments from all windows
+        let code_table = Delta::get_vcdiff_code_table();
+        let mut segments: Vec<(usize, VcdiffSegment)> = Vec::new();
+        loop {
+            let mut window_indicator = [0_u8; 1];
+            let read_size = reader
+                .read(&mut window_indicator)
+                .map_err(|e| RollingHashError::from(Box::new(e)))?;
+            if read_size == 0 {
+                break;
+            }
+            for (_, segment) in Delta::read_vcdiff_window(reader, window_indicator[0], &code_table)?
+            {
+                Delta::push_vcdiff_segment(&mut segments, segment);
+            }
+        }
+        Ok(segments)
+    }
+
+    fn read_vcdiff_window<R: Read>(
+        reader: &mut R,
+        window_indicator: u8,
+        code_table: &[[VcdiffInstruction; 2]],
+    ) -> Result<Vec<(usize, VcdiffSegment)>, RollingHashError> {
+        // Get source segment, segments from the patched data are not supported
+        if window_indicator & VCD_TARGET != 0 {
+            return Err(RollingHashError::new(UNSUPPORTED_VCDIFF_FEATURE));
+        }
+        if window_indicator & !(VCD_SOURCE | VCD_TARGET | VCD_ADLER32) != 0 {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        let (source_length, source_position) = if window_indicator & VCD_SOURCE != 0 {
+            let source_length = Delta::read_vcdiff_int(reader)?;
+            (source_length, Delta::read_vcdiff_int(reader)?)
+        } else {
+            (0, 0)
+        };
+        // Get delta encoding
+        let encoding_length = Delta::read_vcdiff_int(reader)?;
+        let encoding = Delta::read_vcdiff_section(reader, encoding_length)?;
+        let mut encoding = encoding.as_slice();
+        // Target windows are checked against the xdelta3 window size limit,
+        // so a few instructions can not allocate an unbounded patched data size
+        let target_length = Delta::read_vcdiff_int(&mut encoding)?;
+        if target_length > VCDIFF_MAX_WINDOW_SIZE {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        let mut delta_indicator = [0_u8; 1];
+        Delta::read_vcdiff_bytes(&mut encoding, &mut delta_indicator)?;
+        if delta_indicator[0] != 0 {
+            return Err(RollingHashError::new(UNSUPPORTED_VCDIFF_FEATURE));
+        }
+        let data_length = Delta::read_vcdiff_int(&mut encoding)?;
+        let instructions_length = Delta::read_vcdiff_int(&mut encoding)?;
+        let addresses_length = Delta::read_vcdiff_int(&mut encoding)?;
+        if window_indicator & VCD_ADLER32 != 0 {
+            let mut checksum = [0_u8; 4];
+            Delta::read_vcdiff_bytes(&mut encoding, &mut checksum)?;
+        }
+        let data = Delta::read_vcdiff_section(&mut encoding, data_length)?;
+        let instructions = Delta::read_vcdiff_section(&mut encoding, instructions_length)?;
+        let addresses = Delta::read_vcdiff_section(&mut encoding, addresses_length)?;
+        let (mut data, mut instructions, mut addresses) = (
+            data.as_slice(),
+            instructions.as_slice(),
+            addresses.as_slice(),
+        );
+        if !encoding.is_empty() {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        // Execute instructions
+        let mut segments: Vec<(usize, VcdiffSegment)> = Vec::new();
+        let mut target_size: usize = 0;
+        let mut address_cache = VcdiffAddressCache::new();
+        while !instructions.is_empty() {
+            let mut code = [0_u8; 1];
+            Delta::read_vcdiff_bytes(&mut instructions, &mut code)?;
+            for (instruction_type, size, mode) in code_table[code[0] as usize] {
+                if instruction_type == VCD_NOOP {
+                    continue;
+                }
+                let size = match size {
+                    0 => Delta::read_vcdiff_int(&mut instructions)?,
+                    size => size,
+                };
+                if size > target_length - target_size {
+                    return Err(RollingHashError::new(INVALID_DELTA_FILE));
+                }
+                match instruction_type {
+                    VCD_ADD => {
+                        let literal = Delta::read_vcdiff_section(&mut data, size)?;
+                        Delta::push_vcdiff_segment(&mut segments, VcdiffSegment::Literal(literal));
+                    }
+                    VCD_RUN => {
+                        let mut byte = [0_u8; 1];
+                        Delta::read_vcdiff_bytes(&mut data, &mut byte)?;
+                        let literal = vec![byte[0]; size];
+                        Delta::push_vcdiff_segment(&mut segments, VcdiffSegment::Literal(literal));
+                    }
+                    _ => {
+                        let here = source_length + target_size;
+                        let address = address_cache.decode(mode, here, &mut addresses)?;
+                        // Copy from source segment first, then from target window
+                        let source_size = source_length.saturating_sub(address).min(size);
+                        if source_size != 0 {
+                            let segment = VcdiffSegment::Source {
+                                offset: source_position + address,
+                                length: source_size,
+                            };
+                            Delta::push_vcdiff_segment(&mut segments, segment);
+                        }
+                        if source_size != size {
+                            let target_address = (address + source_size) - source_length;
+                            Delta::copy_vcdiff_target(
+                                &mut segments,
+                                target_address,
+                                size - source_size,
+                            );
+                        }
+                    }
+                }
+                target_size += size;
+            }
+        }
+        if target_size != target_length || !data.is_empty() || !addresses.is_empty() {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        Ok(segments)
+    }
+
+    fn copy_vcdiff_target(
+        segments: &mut Vec<(usize, VcdiffSegment)>,
+        mut position: usize,
+        mut size: usize,
+    ) {
+        // Copied data may overlap the data it creates, it is copied segment by segment
+        // from segments already in the target window
+        while size != 0 {
+            let index = segments.partition_point(|(start, _)| *start <= position) - 1;
+            let (start, segment) = &segments[index];
+            let skip = position - start;
+            let piece_size = (segment.len() - skip).min(size);
+            let piece = match segment {
+                VcdiffSegment::Literal(data) => {
+                    VcdiffSegment::Literal(data[skip..skip + piece_size].to_vec())
+                }
+                VcdiffSegment::Source { offset, .. } => VcdiffSegment::Source {
+                    offset: offset + skip,
+                    length: piece_size,
+                },
+            };
+            Delta::push_vcdiff_segment(segments, piece);
+            position += piece_size;
+            size -= piece_size;
+        }
+    }
+
+    fn push_vcdiff_segment(segments: &mut Vec<(usize, VcdiffSegment)>, segment: VcdiffSegment) {
+        // Segments are stored with their patched data start position,
+        // consecutive literals and contiguous basis byte ranges are merged
+        let start = segments.last().map_or(0, |(last_start, last_segment)| {
+            last_start + last_segment.len()
+        });
+        match (segments.last_mut(), segment) {
+            (_, segment) if segment.len() == 0 => {}
+            (Some((_, VcdiffSegment::Literal(last_data))), VcdiffSegment::Literal(data)) => {
+                last_data.extend_from_slice(&data)
+            }
+            (
+                Some((_, VcdiffSegment::Source { offset, length })),
+                VcdiffSegment::Source {
+                    offset: next_offset,
+                    length: next_length,
+                },
+            ) if *offset + *length == next_offset => *length += next_length,
+            (_, segment) => segments.push((start, segment)),
+        }
+    }
+
+    fn get_vcdiff_code_table() -> Vec<[VcdiffInstruction; 2]> {
+        // Default code table as listed in RFC 3284 section 5.6
+        let noop: VcdiffInstruction = (VCD_NOOP, 0, 0);
+        let mut code_table: Vec<[VcdiffInstruction; 2]> = vec![[(VCD_RUN, 0, 0), noop]];
+        for size in 0..=17 {
+            code_table.push([(VCD_ADD, size, 0), noop]);
+        }
+        for mode in 0..9 {
+            code_table.push([(VCD_COPY, 0, mode), noop]);
+            for size in 4..=18 {
+                code_table.push([(VCD_COPY, size, mode), noop]);
+            }
+        }
+        for mode in 0..6 {
+            for add_size in 1..=4 {
+                for copy_size in 4..=6 {
+                    code_table.push([(VCD_ADD, add_size, 0), (VCD_COPY, copy_size, mode)]);
+                }
+            }
+        }
+        for mode in 6..9 {
+            for add_size in 1..=4 {
+                code_table.push([(VCD_ADD, add_size, 0), (VCD_COPY, 4, mode)]);
+            }
+        }
+        for mode in 0..9 {
+            code_table.push([(VCD_COPY, 4, mode), (VCD_ADD, 1, 0)]);
+        }
+        code_table
+    }
+
+    fn read_vcdiff_int<R: Read>(reader: &mut R) -> Result<usize, RollingHashError> {
+        let mut value: u64 = 0;
+        loop {
+            let mut digit = [0_u8; 1];
+            Delta::read_vcdiff_bytes(reader, &mut digit)?;
+            if value >> 57 != 0 {
+                return Err(RollingHashError::new(INVALID_DELTA_FILE));
+            }
+            value = (value << 7) | (digit[0] & 0x7F) as u64;
+            if digit[0] & 0x80 == 0 {
+                break;
+            }
+        }
+        usize::try_from(value).map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))
+    }
+
+    fn read_vcdiff_section<R: Read>(
+        reader: &mut R,
+        length: usize,
+    ) -> Result<Vec<u8>, RollingHashError> {
+        // Lengths are not trusted to allocate memory before reading
+        let mut section: Vec<u8> = Vec::new();
+        reader
+            .take(length as u64)
+            .read_to_end(&mut section)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if section.len() != length {
+            return Err(RollingHashError::new(INVALID_DELTA_FILE));
+        }
+        Ok(section)
+    }
+
+    fn read_vcdiff_bytes<R: Read>(
+        reader: &mut R,
+        buffer: &mut [u8],
+    ) -> Result<(), RollingHashError> {
+        reader
+            .read_exact(buffer)
+            .map_err(|_| RollingHashError::new(INVALID_DELTA_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests;
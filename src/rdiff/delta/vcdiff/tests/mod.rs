@@ -0,0 +1,213 @@
+use std::io::Cursor;
+
+use crate::rdiff::{
+    chunk::iterator::BufferedRdiffChunkIterator,
+    constants::{VCDIFF_MAGIC, VCDIFF_MAX_WINDOW_SIZE, VCDIFF_WINDOW_SIZE},
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{
+            DELTA_BASIS_MISMATCH, DELTA_GEOMETRY_UNKNOWN, INVALID_DELTA_FILE, UNALIGNED_DELTA_COPY,
+            UNSUPPORTED_VCDIFF_FEATURE,
+        },
+        RollingHashError,
+    },
+    io::RdiffFile,
+};
+
+#[test]
+fn test_delta_write_vcdiff_delta_case1() {
+    // Create delta with a literal and a copy
+    let chunk_delta_list: Vec<ChunkDelta> =
+        vec![ChunkDelta::Diff(vec![b'a', b'b']), ChunkDelta::Match(2)];
+    let header = DeltaHeader::new_without_digest(4, 4, 6);
+    let delta = Delta::new(header, chunk_delta_list);
+
+    // Write delta in VCDIFF format
+    let mut data: Vec<u8> = Vec::new();
+    delta.write_vcdiff_delta(&mut data).unwrap();
+
+    // Verify computed values, one window with source segment from basis offset 4
+    let mut expected_data: Vec<u8> = Vec::new();
+    expected_data.extend_from_slice(&VCDIFF_MAGIC);
+    expected_data.push(0x00);
+    expected_data.extend_from_slice(&[0x01, 0x04, 0x04, 0x0A]);
+    expected_data.extend_from_slice(&[0x06, 0x00, 0x02, 0x02, 0x01]);
+    expected_data.extend_from_slice(&[b'a', b'b', 0x03, 0x14, 0x00]);
+    assert_eq!(data, expected_data);
+    assert!(Delta::is_vcdiff_delta(&data));
+
+    // Delta without chunk geometry can not be written
+    let delta = Delta::new(DeltaHeader::legacy(), vec![ChunkDelta::Match(1)]);
+    let mut data: Vec<u8> = Vec::new();
+    let error = delta.write_vcdiff_delta(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_GEOMETRY_UNKNOWN));
+}
+
+#[test]
+fn test_delta_read_vcdiff_delta_case1() {
+    // Write delta larger than a window in VCDIFF format and read it back
+    let get_chunk_delta_list = || -> Vec<ChunkDelta> {
+        vec![
+            ChunkDelta::Diff(vec![b'z'; VCDIFF_WINDOW_SIZE + 10]),
+            ChunkDelta::CopyRange {
+                start_index: 1,
+                count: 2048,
+            },
+            ChunkDelta::Diff(vec![b'y'; 5]),
+            ChunkDelta::Match(7),
+        ]
+    };
+    let output_size = VCDIFF_WINDOW_SIZE + 10 + 2048 * 1024 + 5 + 1024;
+    let header = DeltaHeader::new_without_digest(1024, 1024, output_size);
+    let delta = Delta::new(header, get_chunk_delta_list());
+    let mut data: Vec<u8> = Vec::new();
+    delta.write_vcdiff_delta(&mut data).unwrap();
+    let delta = Delta::read_vcdiff_delta(&mut Cursor::new(data), 1024).unwrap();
+
+    // Verify computed values
+    assert_eq!(delta.get_chunk_delta_list(), &get_chunk_delta_list());
+    assert_eq!(delta.get_header().get_chunk_size(), Some(1024));
+    assert_eq!(delta.get_header().get_output_size(), Some(output_size));
+    assert_eq!(delta.get_header().get_strong_hash_id(), None);
+}
+
+#[test]
+fn test_delta_read_vcdiff_delta_case2() {
+    // Create delta with application data, adler32 checksum, RUN, combined instructions,
+    // here mode address and overlapping copies from the target window,
+    // for a basis with chunk size 2 and last chunk size 1
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&VCDIFF_MAGIC);
+    data.extend_from_slice(&[0x04, 0x02, b'h', b'i']);
+    data.extend_from_slice(&[0x05, 0x03, 0x00, 0x14]);
+    data.extend_from_slice(&[0x0E, 0x00, 0x02, 0x06, 0x03]);
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+    data.extend_from_slice(b"xy");
+    data.extend_from_slice(&[0x13, 0x03, 0x00, 0x02, 0x24, 0xA3]);
+    data.extend_from_slice(&[0x00, 0x02, 0x08]);
+
+    // Read delta
+    let delta = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap();
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 2,
+        },
+        ChunkDelta::Diff(b"xxxxxxyxxxx".to_vec()),
+    ];
+    assert_eq!(delta.get_chunk_delta_list(), &expected_chunk_delta_list);
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(1));
+    assert_eq!(delta.get_header().get_output_size(), Some(14));
+
+    // Apply delta
+    let rdiff_file = RdiffFile::new("resources/buffered_rdiff_chunk_iterator_file1.txt").unwrap();
+    let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply(&mut iterator, &mut output).unwrap();
+    assert_eq!(output, b"aaaxxxxxxyxxxx");
+}
+
+#[test]
+fn test_delta_read_vcdiff_delta_with_basis_case1() {
+    // Create delta copying bcd from basis abcdef and adding x, copy is not aligned
+    // to chunk size 2
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x00, 0x01, 0x03, 0x01, 0x0A]);
+    data.extend_from_slice(&[0x04, 0x00, 0x01, 0x03, 0x01]);
+    data.extend_from_slice(&[b'x', 0x13, 0x03, 0x02, 0x00]);
+
+    // Copy can not be split in chunks without basis
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data.clone()), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNALIGNED_DELTA_COPY));
+
+    // Parts of basis chunks are read from basis
+    let mut basis = Cursor::new(b"abcdef".to_vec());
+    let delta =
+        Delta::read_vcdiff_delta_with_basis(&mut Cursor::new(data.clone()), &mut basis, 2).unwrap();
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::Diff(b"b".to_vec()),
+        ChunkDelta::Match(2),
+        ChunkDelta::Diff(b"x".to_vec()),
+    ];
+    assert_eq!(delta.get_chunk_delta_list(), &expected_chunk_delta_list);
+    assert_eq!(delta.get_header().get_last_chunk_size(), Some(2));
+    assert_eq!(delta.get_header().get_output_size(), Some(4));
+
+    // Apply delta
+    let mut output: Vec<u8> = Vec::new();
+    delta.apply_to_basis(&mut basis, &mut output).unwrap();
+    assert_eq!(output, b"bcdx");
+
+    // Source segment past the end of basis
+    let mut basis = Cursor::new(b"abc".to_vec());
+    let error =
+        Delta::read_vcdiff_delta_with_basis(&mut Cursor::new(data), &mut basis, 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(DELTA_BASIS_MISMATCH));
+}
+
+#[test]
+fn test_delta_read_vcdiff_delta_errors_case1() {
+    // Bad magic number
+    let data = vec![0xD6, 0xC3, 0xC4, 0x01, 0x00];
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+
+    // Secondary compression
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x01, 0x02]);
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_VCDIFF_FEATURE));
+
+    // Source segment from the patched data
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x00, 0x02, 0x01, 0x00]);
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_VCDIFF_FEATURE));
+
+    // Copy address past the current position
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x00, 0x01, 0x02, 0x00, 0x08]);
+    data.extend_from_slice(&[0x02, 0x00, 0x00, 0x02, 0x01, 0x13, 0x02, 0x02]);
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+
+    // Target window size does not match its instructions
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x00, 0x00, 0x07]);
+    data.extend_from_slice(&[0x03, 0x00, 0x01, 0x01, 0x00, b'a', 0x02]);
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+
+    // Truncated window
+    let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    data.extend_from_slice(&[0x00, 0x00, 0x07, 0x01]);
+    let error = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_DELTA_FILE));
+}
+
+#[test]
+fn test_delta_read_vcdiff_delta_errors_case2() {
+    // Small window with a single run as long as its target window size
+    for (target_length, expected_result) in [
+        (VCDIFF_MAX_WINDOW_SIZE, Ok(VCDIFF_MAX_WINDOW_SIZE)),
+        (
+            VCDIFF_MAX_WINDOW_SIZE + 1,
+            Err(RollingHashError::new(INVALID_DELTA_FILE)),
+        ),
+        (1 << 40, Err(RollingHashError::new(INVALID_DELTA_FILE))),
+    ] {
+        let mut size: Vec<u8> = Vec::new();
+        Delta::push_vcdiff_int(&mut size, target_length);
+        let mut encoding: Vec<u8> = size.clone();
+        encoding.extend_from_slice(&[0x00, 0x01, 1 + size.len() as u8, 0x00, b'a', 0x00]);
+        encoding.extend_from_slice(&size);
+        let mut data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+        data.extend_from_slice(&[0x00, 0x00, encoding.len() as u8]);
+        data.extend_from_slice(&encoding);
+
+        // Window size is checked before the run is allocated
+        let result = Delta::read_vcdiff_delta(&mut Cursor::new(data), 2)
+            .map(|delta| delta.get_header().get_output_size().unwrap());
+        assert_eq!(result, expected_result);
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    cmp,
+    io::{Seek, SeekFrom, Write},
+    mem,
+};
+
+use bincode::{serialize_into, serialized_size};
+
+use crate::rdiff::{
+    compression::CompressionPtr,
+    constants::MAX_DIFF_SIZE,
+    error::{messages::DELTA_HEADER_SIZE_MISMATCH, RollingHashError},
+};
+
+use super::{header::DeltaHeader, stats::DeltaStats, ChunkDelta, Delta};
+
+// Destination of chunk deltas, they are given to it as soon as they are found
+pub trait DeltaSink {
+    fn write_chunk_delta(&mut self, chunk_delta: ChunkDelta) -> Result<(), RollingHashError>;
+}
+
+// Chunk deltas kept in memory
+impl DeltaSink for Vec<ChunkDelta> {
+    fn write_chunk_delta(&mut self, chunk_delta: ChunkDelta) -> Result<(), RollingHashError> {
+        self.push(chunk_delta);
+        Ok(())
+    }
+}
+
+// Gives chunk deltas to a sink while a delta is generated.
+// The last match is kept until the next chunk delta, so contiguous matches are
+// merged in copy ranges, and differences are given in pieces of at most
+// max diff size bytes, so memory use does not grow with the new file size
+pub struct DeltaWriter<'a> {
+    sink: &'a mut dyn DeltaSink,
+    pending_match: Option<ChunkDelta>,
+    differences: Vec<u8>,
+    processed_data_size: usize,
+}
+
+impl<'a> DeltaWriter<'a> {
+    pub fn new(sink: &'a mut dyn DeltaSink) -> DeltaWriter<'a> {
+        DeltaWriter {
+            sink,
+            pending_match: None,
+            differences: Vec::new(),
+            processed_data_size: 0,
+        }
+    }
+
+    // Size of the new file data given to the writer so far
+    pub fn get_processed_data_size(&self) -> usize {
+        self.processed_data_size
+    }
+
+    pub fn push_difference(&mut self, byte: u8) -> Result<(), RollingHashError> {
+        self.write_pending_match()?;
+        self.differences.push(byte);
+        self.processed_data_size += 1;
+        if self.differences.len() >= MAX_DIFF_SIZE {
+            self.write_differences()?;
+        }
+        Ok(())
+    }
+
+    pub fn push_differences(&mut self, data: &[u8]) -> Result<(), RollingHashError> {
+        self.write_pending_match()?;
+        self.processed_data_size += data.len();
+        let mut data = data;
+        while !data.is_empty() {
+            // Fill differences up to max diff size and write them when full
+            let size = cmp::min(MAX_DIFF_SIZE - self.differences.len(), data.len());
+            self.differences.extend_from_slice(&data[..size]);
+            data = &data[size..];
+            if self.differences.len() >= MAX_DIFF_SIZE {
+                self.write_differences()?;
+            }
+        }
+        Ok(())
+    }
+
+    // Push a match chunk delta of a chunk of given length
+    pub fn push_match(
+        &mut self,
+        chunk_delta: ChunkDelta,
+        length: usize,
+    ) -> Result<(), RollingHashError> {
+        self.write_differences()?;
+        self.processed_data_size += length;
+        // A match following the pending match chunk index is merged with it
+        if let (ChunkDelta::Match(index), Some(pending_match)) =
+            (&chunk_delta, self.pending_match.as_mut())
+        {
+            if Delta::extend_match(pending_match, *index) {
+                return Ok(());
+            }
+        }
+        self.write_pending_match()?;
+        self.pending_match = Some(chunk_delta);
+        Ok(())
+    }
+
+    // Give remaining chunk deltas to the sink and return the processed data size
+    pub fn finish(mut self) -> Result<usize, RollingHashError> {
+        self.write_differences()?;
+        self.write_pending_match()?;
+        Ok(self.processed_data_size)
+    }
+
+    fn write_pending_match(&mut self) -> Result<(), RollingHashError> {
+        match self.pending_match.take() {
+            Some(chunk_delta) => self.sink.write_chunk_delta(chunk_delta),
+            None => Ok(()),
+        }
+    }
+
+    fn write_differences(&mut self) -> Result<(), RollingHashError> {
+        // Only non empty differences are given to the sink
+        if self.differences.is_empty() {
+            return Ok(());
+        }
+        let differences = mem::take(&mut self.differences);
+        self.sink.write_chunk_delta(ChunkDelta::Diff(differences))
+    }
+}
+
+// Writes chunk deltas to a delta file in rdiff format as they are found.
+// Delta header and chunk delta count are only known at the end, so a header
+// of the same size is written first and replaced once the delta is finished
+pub struct RdiffDeltaSink<W: Write + Seek> {
+    writer: W,
+    compression_ptr: Option<CompressionPtr>,
+    start_position: u64,
+    header_size: u64,
+    count: u64,
+    stats: DeltaStats,
+}
+
+impl<W: Write + Seek> RdiffDeltaSink<W> {
+    // Header must have the final header size, only its output size and digest
+    // values may change before the delta is finished
+    pub fn new(
+        mut writer: W,
+        header: &DeltaHeader,
+        compression_ptr: Option<CompressionPtr>,
+    ) -> Result<RdiffDeltaSink<W>, RollingHashError> {
+        let start_position = writer
+            .stream_position()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let header_size =
+            serialized_size(header).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut writer, header).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut writer, &0_u64).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        Ok(RdiffDeltaSink {
+            writer,
+            compression_ptr,
+            start_position,
+            header_size,
+            count: 0,
+            stats: DeltaStats::default(),
+        })
+    }
+
+    // Replace header and chunk delta count, and return the delta statistics
+    pub fn finish(mut self, header: &DeltaHeader) -> Result<DeltaStats, RollingHashError> {
+        let header_size =
+            serialized_size(header).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if header_size != self.header_size {
+            return Err(RollingHashError::new(DELTA_HEADER_SIZE_MISMATCH));
+        }
+        let end_position = self
+            .writer
+            .stream_position()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.writer
+            .seek(SeekFrom::Start(self.start_position))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut self.writer, header)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        serialize_into(&mut self.writer, &self.count)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.writer
+            .seek(SeekFrom::Start(end_position))
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.writer
+            .flush()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        Ok(self.stats)
+    }
+}
+
+impl<W: Write + Seek> DeltaSink for RdiffDeltaSink<W> {
+    fn write_chunk_delta(&mut self, chunk_delta: ChunkDelta) -> Result<(), RollingHashError> {
+        // Differences are compressed as they are written, if there is a compression
+        let chunk_delta = match (chunk_delta, self.compression_ptr.as_ref()) {
+            (ChunkDelta::Diff(differences), Some(compression_ptr)) => {
+                let compressed_differences = compression_ptr.compress(&differences);
+                self.stats
+                    .add_literal(differences.len(), compressed_differences.len());
+                ChunkDelta::Diff(compressed_differences)
+            }
+            (ChunkDelta::Diff(differences), None) => {
+                self.stats.add_literal(differences.len(), differences.len());
+                ChunkDelta::Diff(differences)
+            }
+            (chunk_delta, _) => chunk_delta,
+        };
+        serialize_into(&mut self.writer, &chunk_delta)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
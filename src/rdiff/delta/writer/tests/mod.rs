@@ -0,0 +1,128 @@
+use std::{fs, io::Cursor};
+
+use crate::rdiff::{
+    compression::rdiff_deflate::RdiffDeflate,
+    constants::MAX_DIFF_SIZE,
+    delta::{header::DeltaHeader, stats::DeltaStats, ChunkDelta, Delta},
+    error::{messages::DELTA_HEADER_SIZE_MISMATCH, RollingHashError},
+    hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
+    io::RdiffFile,
+    signature::Signature,
+};
+
+use super::{DeltaWriter, RdiffDeltaSink};
+
+#[test]
+fn test_delta_writer_push_match_case1() {
+    // Contiguous matches are merged, differences break copy ranges
+    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+    let mut delta_writer = DeltaWriter::new(&mut chunk_delta_list);
+    for index in 1..=3 {
+        delta_writer
+            .push_match(ChunkDelta::Match(index), 4)
+            .unwrap();
+    }
+    delta_writer.push_difference(b'x').unwrap();
+    delta_writer.push_match(ChunkDelta::Match(4), 4).unwrap();
+    delta_writer.push_match(ChunkDelta::Match(2), 4).unwrap();
+    let processed_data_size = delta_writer.finish().unwrap();
+
+    // Verify computed values
+    let expected_chunk_delta_list: Vec<ChunkDelta> = vec![
+        ChunkDelta::CopyRange {
+            start_index: 1,
+            count: 3,
+        },
+        ChunkDelta::Diff(vec![b'x']),
+        ChunkDelta::Match(4),
+        ChunkDelta::Match(2),
+    ];
+    assert_eq!(chunk_delta_list, expected_chunk_delta_list);
+    assert_eq!(processed_data_size, 21);
+}
+
+#[test]
+fn test_delta_writer_push_differences_case1() {
+    // Differences are given in pieces of at most max diff size bytes
+    let data: Vec<u8> = (0..(2 * MAX_DIFF_SIZE + 10)).map(|i| i as u8).collect();
+    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
+    let mut delta_writer = DeltaWriter::new(&mut chunk_delta_list);
+    delta_writer.push_difference(data[0]).unwrap();
+    delta_writer.push_differences(&data[1..]).unwrap();
+    let processed_data_size = delta_writer.finish().unwrap();
+
+    // Verify computed values
+    let expected_chunk_delta_list: Vec<ChunkDelta> = data
+        .chunks(MAX_DIFF_SIZE)
+        .map(|differences| ChunkDelta::Diff(differences.to_vec()))
+        .collect();
+    assert_eq!(chunk_delta_list, expected_chunk_delta_list);
+    assert_eq!(processed_data_size, data.len());
+}
+
+#[test]
+fn test_delta_rdiff_delta_sink_case1() {
+    // Get old data and new data with differences longer than max diff size
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    let mut new_data: Vec<u8> = (0..(MAX_DIFF_SIZE + 100)).map(|i| (i % 7) as u8).collect();
+    new_data.extend_from_slice(&old_data);
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Write delta while it is generated
+    let mut output = Cursor::new(Vec::new());
+    let stats = Delta::write_delta_from_rdiff_file(
+        RdiffFile::new_with_bytes("new", new_data.as_slice()),
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+        Some(RdiffDeflate::new_ptr()),
+        &mut output,
+    )
+    .unwrap();
+    let delta = Delta::get_delta_from_reader(output.get_ref().as_slice()).unwrap();
+
+    // Verify computed values, delta is the one generated in memory
+    let signature = Signature::create_signature_from_bytes(
+        old_data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let mut expected_delta = Delta::generate_delta_from_rdiff_file(
+        RdiffFile::new_with_bytes("new", new_data.as_slice()),
+        signature,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    expected_delta
+        .header
+        .set_compression_id(delta.get_header().get_compression_id());
+    assert_eq!(delta, expected_delta);
+    assert_eq!(stats.get_literal_size(), MAX_DIFF_SIZE + 100);
+    assert!(matches!(
+        delta.get_chunk_delta_list().first(),
+        Some(ChunkDelta::Diff(differences)) if differences.len() == MAX_DIFF_SIZE
+    ));
+}
+
+#[test]
+fn test_delta_rdiff_delta_sink_header_size_mismatch_case1() {
+    // Final header must have the size of the header written first
+    let header = DeltaHeader::new(4, 4, 0, 1, vec![0; 20], None);
+    let mut output = Cursor::new(Vec::new());
+    let sink = RdiffDeltaSink::new(&mut output, &header, None).unwrap();
+    let final_header = DeltaHeader::new(4, 4, 0, 1, vec![0; 32], None);
+    let result: Result<DeltaStats, RollingHashError> = sink.finish(&final_header);
+
+    // Verify computed values
+    assert_eq!(
+        result,
+        Err(RollingHashError::new(DELTA_HEADER_SIZE_MISMATCH))
+    );
+}
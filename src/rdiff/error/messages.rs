@@ -1,4 +1,39 @@
 pub const FILE_NOT_FOUND: &str = "The system cannot find the file specified";
-pub const HELP_USAGE:&str = "USAGE:\nrolling_hash_diff signature <file-name> <signature-file-name>\nrolling_hash_diff delta <signature-file-name> <new-file-name> <delta-file-name>";
-pub const INVALID_CHUNK_SIZE: &str = "Chunk size should be less than or equal to block size";
+pub const HELP_USAGE:&str = "USAGE:\nrolling_hash_diff signature [--block-size <block-size>] [--chunking <fixed|fastcdc>] [--weak-hash <adler32|rollsum|rabinkarp>] [--strong-hash <sha1|md4|blake2|blake3>] [--strong-len <strong-len>] [--format <rdiff|librsync>] <file-name> <signature-file-name>\nrolling_hash_diff delta [--compress] [--format <rdiff|librsync|vcdiff>] <signature-file-name> <new-file-name> <delta-file-name>\nrolling_hash_diff patch [--block-size <block-size>] <basis-file-name> <delta-file-name> <output-file-name>\nrolling_hash_diff patch --in-place [--block-size <block-size>] <basis-file-name> <delta-file-name>\nrolling_hash_diff patch --in-place --rollback <basis-file-name>\nrolling_hash_diff compose <basis-file-name> <first-delta-file-name> <second-delta-file-name> <delta-file-name>\nrolling_hash_diff compose --without-basis <first-delta-file-name> <second-delta-file-name> <delta-file-name>";
+pub const INVALID_CHUNK_SIZE: &str =
+    "Chunk size should be greater than zero and less than or equal to max block size";
 pub const DELTA_PROCESSED_DATA_SIZE_ERROR: &str = "Invalid Delta processed data";
+pub const INVALID_CHUNK_INDEX: &str = "Delta chunk index not found in basis file";
+pub const INVALID_OPTION_VALUE: &str = "Invalid option value";
+pub const INVALID_SIGNATURE_FILE: &str = "Invalid signature file";
+pub const UNSUPPORTED_SIGNATURE_VERSION: &str = "Unsupported signature file version";
+pub const UNKNOWN_WEAK_HASH: &str = "Unknown weak hash";
+pub const UNKNOWN_STRONG_HASH: &str = "Unknown strong hash";
+pub const HASH_ALREADY_REGISTERED: &str = "Hash name or id is already registered";
+pub const SIGNATURE_HASH_MISMATCH: &str = "Hash functions do not match signature hash functions";
+pub const INVALID_DELTA_FILE: &str = "Invalid delta file";
+pub const UNSUPPORTED_DELTA_VERSION: &str = "Unsupported delta file version";
+pub const DELTA_CHUNK_SIZE_MISMATCH: &str = "Chunk size does not match delta chunk size";
+pub const DELTA_BASIS_MISMATCH: &str = "Basis file does not match delta basis file";
+pub const DELTA_OUTPUT_SIZE_ERROR: &str = "Patched data size does not match delta output size";
+pub const DELTA_HEADER_SIZE_MISMATCH: &str = "Delta header size changed while writing delta";
+pub const DELTA_DIGEST_MISMATCH: &str = "Patched data digest does not match delta digest";
+pub const UNKNOWN_COMPRESSION: &str = "Unknown compression";
+pub const INVALID_COMPRESSED_DATA: &str = "Invalid compressed data";
+pub const UNSUPPORTED_LIBRSYNC_HASH: &str = "Hash functions are not supported by librsync format";
+pub const INVALID_DIGEST_SIZE: &str =
+    "Strong digest length should be greater than zero and less than or equal to strong hash digest size";
+pub const DELTA_GEOMETRY_UNKNOWN: &str = "Delta file does not record its chunk geometry";
+pub const UNALIGNED_DELTA_COPY: &str = "Delta copy is not aligned to chunk size";
+pub const COMPRESSION_FORMAT_UNSUPPORTED: &str =
+    "Compression is only available for the rdiff delta format";
+pub const UNSUPPORTED_VCDIFF_FEATURE: &str = "VCDIFF delta uses an unsupported feature";
+pub const FIXED_SIZE_CHUNKS_REQUIRED: &str = "Format only supports fixed size chunks";
+pub const IN_PLACE_RDIFF_FORMAT_REQUIRED: &str =
+    "In place patch requires a delta file in rdiff format";
+pub const INVALID_JOURNAL_FILE: &str = "Invalid in place patch journal file";
+pub const IN_PLACE_ROLLBACK_UNAVAILABLE: &str =
+    "In place patch can not be rolled back once basis file data was overwritten";
+pub const COMPOSE_HASH_MISMATCH: &str = "Deltas do not use the same strong hash";
+pub const COMPOSE_BASIS_REQUIRED: &str =
+    "Composed delta copies parts of basis chunks, basis is required";
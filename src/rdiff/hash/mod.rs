@@ -1,2 +1,3 @@
+pub mod registry;
 pub mod strong;
 pub mod weak;
@@ -0,0 +1,188 @@
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use crate::rdiff::{
+    constants::{
+        ADLER32_HASH, BLAKE2_HASH, BLAKE3_HASH, MD4_HASH, RABINKARP_HASH, ROLLSUM_HASH, SHA1_HASH,
+    },
+    error::{
+        messages::{HASH_ALREADY_REGISTERED, UNKNOWN_STRONG_HASH, UNKNOWN_WEAK_HASH},
+        RollingHashError,
+    },
+};
+
+use super::{
+    strong::{
+        rdiff_blake2::RdiffBlake2, rdiff_blake3::RdiffBlake3, rdiff_md4::RdiffMd4,
+        rdiff_sha1::RdiffSha1, StrongHashPtr,
+    },
+    weak::{
+        rdiff_addler::RdiffAddler, rdiff_rabinkarp::RdiffRabinKarp, rdiff_rollsum::RdiffRollsum,
+        WeakHashPtr,
+    },
+};
+
+pub type WeakHashConstructor = fn() -> WeakHashPtr;
+pub type StrongHashConstructor = fn() -> StrongHashPtr;
+
+// Hash functions known by name and by the id recorded in signature and delta files.
+// Applications register their own hash functions once, before creating or reading files
+// that use them, so the command line and the file readers can find them
+struct HashRegistry {
+    weak_hashes: Vec<(String, u32, WeakHashConstructor)>,
+    strong_hashes: Vec<(String, u32, StrongHashConstructor)>,
+}
+
+static HASH_REGISTRY: LazyLock<RwLock<HashRegistry>> =
+    LazyLock::new(|| RwLock::new(HashRegistry::new()));
+
+impl HashRegistry {
+    fn new() -> HashRegistry {
+        let mut hash_registry = HashRegistry {
+            weak_hashes: Vec::new(),
+            strong_hashes: Vec::new(),
+        };
+        // Built in hash functions
+        let weak_hashes: [(&str, WeakHashConstructor); 3] = [
+            (ADLER32_HASH, || RdiffAddler::new_ptr()),
+            (ROLLSUM_HASH, || RdiffRollsum::new_ptr()),
+            (RABINKARP_HASH, || RdiffRabinKarp::new_ptr()),
+        ];
+        for (name, constructor) in weak_hashes {
+            hash_registry
+                .weak_hashes
+                .push((name.to_string(), constructor().get_id(), constructor));
+        }
+        let strong_hashes: [(&str, StrongHashConstructor); 4] = [
+            (SHA1_HASH, || RdiffSha1::new_ptr()),
+            (MD4_HASH, || RdiffMd4::new_ptr()),
+            (BLAKE2_HASH, || RdiffBlake2::new_ptr()),
+            (BLAKE3_HASH, || RdiffBlake3::new_ptr()),
+        ];
+        for (name, constructor) in strong_hashes {
+            hash_registry.strong_hashes.push((
+                name.to_string(),
+                constructor().get_id(),
+                constructor,
+            ));
+        }
+        hash_registry
+    }
+}
+
+pub fn register_weak_hash(
+    name: &str,
+    constructor: WeakHashConstructor,
+) -> Result<(), RollingHashError> {
+    let id = constructor().get_id();
+    let mut hash_registry = HASH_REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    // Names and ids identify a single hash function
+    if hash_registry
+        .weak_hashes
+        .iter()
+        .any(|(hash_name, hash_id, _)| hash_name == name || *hash_id == id)
+    {
+        return Err(RollingHashError::new(HASH_ALREADY_REGISTERED));
+    }
+    hash_registry
+        .weak_hashes
+        .push((name.to_string(), id, constructor));
+    Ok(())
+}
+
+pub fn register_strong_hash(
+    name: &str,
+    constructor: StrongHashConstructor,
+) -> Result<(), RollingHashError> {
+    let id = constructor().get_id();
+    let mut hash_registry = HASH_REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    // Names and ids identify a single hash function
+    if hash_registry
+        .strong_hashes
+        .iter()
+        .any(|(hash_name, hash_id, _)| hash_name == name || *hash_id == id)
+    {
+        return Err(RollingHashError::new(HASH_ALREADY_REGISTERED));
+    }
+    hash_registry
+        .strong_hashes
+        .push((name.to_string(), id, constructor));
+    Ok(())
+}
+
+pub fn get_weak_hash_ptr_by_name(name: &str) -> Result<WeakHashPtr, RollingHashError> {
+    get_weak_hash_constructor(|(hash_name, _, _)| hash_name == name)
+        .map(|constructor| constructor())
+        .map_err(|error| get_unknown_name_error(error, get_weak_hash_names()))
+}
+
+pub fn get_weak_hash_ptr_by_id(id: u32) -> Result<WeakHashPtr, RollingHashError> {
+    get_weak_hash_constructor(|(_, hash_id, _)| *hash_id == id).map(|constructor| constructor())
+}
+
+pub fn get_strong_hash_ptr_by_name(name: &str) -> Result<StrongHashPtr, RollingHashError> {
+    get_strong_hash_constructor(|(hash_name, _, _)| hash_name == name)
+        .map(|constructor| constructor())
+        .map_err(|error| get_unknown_name_error(error, get_strong_hash_names()))
+}
+
+pub fn get_strong_hash_ptr_by_id(id: u32) -> Result<StrongHashPtr, RollingHashError> {
+    get_strong_hash_constructor(|(_, hash_id, _)| *hash_id == id).map(|constructor| constructor())
+}
+
+pub fn get_weak_hash_names() -> Vec<String> {
+    let hash_registry = HASH_REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    hash_registry
+        .weak_hashes
+        .iter()
+        .map(|(name, _, _)| name.clone())
+        .collect()
+}
+
+pub fn get_strong_hash_names() -> Vec<String> {
+    let hash_registry = HASH_REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    hash_registry
+        .strong_hashes
+        .iter()
+        .map(|(name, _, _)| name.clone())
+        .collect()
+}
+
+fn get_weak_hash_constructor<P>(predicate: P) -> Result<WeakHashConstructor, RollingHashError>
+where
+    P: Fn(&(String, u32, WeakHashConstructor)) -> bool,
+{
+    // Constructor is called after releasing the lock
+    let hash_registry = HASH_REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    hash_registry
+        .weak_hashes
+        .iter()
+        .find(|entry| predicate(entry))
+        .map(|(_, _, constructor)| *constructor)
+        .ok_or_else(|| RollingHashError::new(UNKNOWN_WEAK_HASH))
+}
+
+fn get_strong_hash_constructor<P>(predicate: P) -> Result<StrongHashConstructor, RollingHashError>
+where
+    P: Fn(&(String, u32, StrongHashConstructor)) -> bool,
+{
+    // Constructor is called after releasing the lock
+    let hash_registry = HASH_REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    hash_registry
+        .strong_hashes
+        .iter()
+        .find(|entry| predicate(entry))
+        .map(|(_, _, constructor)| *constructor)
+        .ok_or_else(|| RollingHashError::new(UNKNOWN_STRONG_HASH))
+}
+
+// Unknown names are reported with the registered names to choose from
+fn get_unknown_name_error(error: RollingHashError, names: Vec<String>) -> RollingHashError {
+    RollingHashError::new(format!("{}, registered names: {}", error, names.join(", ")).as_str())
+}
+
+#[cfg(test)]
+mod tests;
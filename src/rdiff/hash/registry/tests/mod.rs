@@ -0,0 +1,138 @@
+use crate::rdiff::{
+    chunk::{RdiffChecksum, RdiffDigest},
+    constants::{ADLER32_HASH, BLAKE3_HASH, RABINKARP_HASH, SHA1_HASH},
+    error::{
+        messages::{HASH_ALREADY_REGISTERED, UNKNOWN_STRONG_HASH, UNKNOWN_WEAK_HASH},
+        RollingHashError,
+    },
+    hash::{
+        strong::{
+            new_strong_hash_ptr,
+            rdiff_blake3::RDIFF_BLAKE3_ID,
+            rdiff_sha1::{RdiffSha1, RDIFF_SHA1_ID},
+            StrongHash, StrongHashPtr, StrongHasherPtr,
+        },
+        weak::{
+            new_weak_hash_ptr,
+            rdiff_addler::{RdiffAddler, RDIFF_ADDLER_ID},
+            rdiff_rabinkarp::RDIFF_RABINKARP_ID,
+            RollingWeakHashPtr, WeakHash, WeakHashPtr,
+        },
+    },
+};
+
+use super::{
+    get_strong_hash_names, get_strong_hash_ptr_by_name, get_weak_hash_names,
+    get_weak_hash_ptr_by_name, register_strong_hash, register_weak_hash,
+};
+
+// Application hash functions, Adler-32 and SHA-1 with other ids
+const TEST_WEAK_HASH_ID: u32 = 1001;
+const TEST_STRONG_HASH_ID: u32 = 1002;
+
+struct TestWeakHash;
+
+impl WeakHash for TestWeakHash {
+    fn get_id(&self) -> u32 {
+        TEST_WEAK_HASH_ID
+    }
+
+    fn checksum(&self, chunk: &[u8]) -> RdiffChecksum {
+        RdiffAddler::new().checksum(chunk)
+    }
+
+    fn rolling_checksum(&self, chunk: &[u8]) -> RollingWeakHashPtr {
+        RdiffAddler::new().rolling_checksum(chunk)
+    }
+}
+
+struct TestStrongHash;
+
+impl StrongHash for TestStrongHash {
+    fn get_id(&self) -> u32 {
+        TEST_STRONG_HASH_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        RdiffSha1::new().get_digest_size()
+    }
+
+    fn digest(&self, chunk: &[u8]) -> RdiffDigest {
+        RdiffSha1::new().digest(chunk)
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        RdiffSha1::new().hasher()
+    }
+}
+
+#[test]
+fn test_hash_registry_built_in_case1() {
+    // Built in hash functions are found by name and by id
+    let weak_hash_ptr = get_weak_hash_ptr_by_name(ADLER32_HASH).unwrap();
+    assert_eq!(weak_hash_ptr.get_id(), RDIFF_ADDLER_ID);
+    let weak_hash_ptr = get_weak_hash_ptr_by_name(RABINKARP_HASH).unwrap();
+    assert_eq!(weak_hash_ptr.get_id(), RDIFF_RABINKARP_ID);
+    let strong_hash_ptr = get_strong_hash_ptr_by_name(SHA1_HASH).unwrap();
+    assert_eq!(strong_hash_ptr.get_id(), RDIFF_SHA1_ID);
+    let strong_hash_ptr = new_strong_hash_ptr(RDIFF_BLAKE3_ID).unwrap();
+    assert_eq!(strong_hash_ptr.get_id(), RDIFF_BLAKE3_ID);
+    assert!(get_weak_hash_names().contains(&ADLER32_HASH.to_string()));
+    assert!(get_strong_hash_names().contains(&BLAKE3_HASH.to_string()));
+
+    // Unknown hash functions
+    // Unknown names are reported with the registered names
+    let error = get_weak_hash_ptr_by_name("unknown")
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.starts_with(UNKNOWN_WEAK_HASH));
+    assert!(error.contains(ADLER32_HASH));
+    let error = get_strong_hash_ptr_by_name("unknown")
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.starts_with(UNKNOWN_STRONG_HASH));
+    assert!(error.contains(BLAKE3_HASH));
+}
+
+#[test]
+fn test_hash_registry_register_case1() {
+    // Register application hash functions
+    register_weak_hash("test-weak", || -> WeakHashPtr { Box::new(TestWeakHash) }).unwrap();
+    register_strong_hash("test-strong", || -> StrongHashPtr {
+        Box::new(TestStrongHash)
+    })
+    .unwrap();
+
+    // They are found by name and by the id recorded in files
+    let weak_hash_ptr = get_weak_hash_ptr_by_name("test-weak").unwrap();
+    assert_eq!(weak_hash_ptr.get_id(), TEST_WEAK_HASH_ID);
+    let weak_hash_ptr = new_weak_hash_ptr(TEST_WEAK_HASH_ID).unwrap();
+    assert_eq!(
+        weak_hash_ptr.checksum(b"abc"),
+        RdiffAddler::new().checksum(b"abc")
+    );
+    let strong_hash_ptr = get_strong_hash_ptr_by_name("test-strong").unwrap();
+    assert_eq!(strong_hash_ptr.get_id(), TEST_STRONG_HASH_ID);
+    let strong_hash_ptr = new_strong_hash_ptr(TEST_STRONG_HASH_ID).unwrap();
+    assert_eq!(strong_hash_ptr.get_digest_size(), 20);
+    assert!(get_weak_hash_names().contains(&"test-weak".to_string()));
+
+    // Names and ids can not be registered twice
+    let expected_error = RollingHashError::new(HASH_ALREADY_REGISTERED);
+    let error =
+        register_weak_hash("test-weak", || -> WeakHashPtr { Box::new(TestWeakHash) }).unwrap_err();
+    assert_eq!(error, expected_error);
+    let error = register_weak_hash("other-test-weak", || -> WeakHashPtr {
+        Box::new(TestWeakHash)
+    })
+    .unwrap_err();
+    assert_eq!(error, expected_error);
+    let error = register_strong_hash(SHA1_HASH, || -> StrongHashPtr { Box::new(TestStrongHash) })
+        .unwrap_err();
+    assert_eq!(error, expected_error);
+    let error = register_strong_hash("other-sha1", || -> StrongHashPtr { RdiffSha1::new_ptr() })
+        .unwrap_err();
+    assert_eq!(error, expected_error);
+}
@@ -1,9 +1,28 @@
-use crate::rdiff::chunk::RdiffDigest;
+use crate::rdiff::{chunk::RdiffDigest, error::RollingHashError};
 
+use super::registry::get_strong_hash_ptr_by_id;
+
+pub mod rdiff_blake2;
+pub mod rdiff_blake3;
+pub mod rdiff_md4;
 pub mod rdiff_sha1;
 
 pub type StrongHashPtr = Box<dyn StrongHash>;
+pub type StrongHasherPtr = Box<dyn StrongHasher>;
 
 pub trait StrongHash {
+    fn get_id(&self) -> u32;
+    fn get_digest_size(&self) -> usize;
     fn digest(&self, chunk: &[u8]) -> RdiffDigest;
+    fn hasher(&self) -> StrongHasherPtr;
+}
+
+pub trait StrongHasher {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(&self) -> RdiffDigest;
+}
+
+// Strong hash with the given id, built in or registered by the application
+pub fn new_strong_hash_ptr(id: u32) -> Result<StrongHashPtr, RollingHashError> {
+    get_strong_hash_ptr_by_id(id)
 }
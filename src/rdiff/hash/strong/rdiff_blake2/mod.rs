@@ -0,0 +1,69 @@
+use blake2::{digest::consts::U32, Blake2b, Digest};
+
+use crate::rdiff::chunk::RdiffDigest;
+
+use super::{StrongHash, StrongHasher, StrongHasherPtr};
+
+pub const RDIFF_BLAKE2_ID: u32 = 3;
+const RDIFF_BLAKE2_DIGEST_SIZE: usize = 32;
+
+// BLAKE2b with a 32 bytes digest, the one used by librsync
+type Blake2b256 = Blake2b<U32>;
+
+pub type RdiffBlake2Ptr = Box<RdiffBlake2>;
+pub struct RdiffBlake2;
+
+impl Default for RdiffBlake2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffBlake2 {
+    pub fn new() -> RdiffBlake2 {
+        RdiffBlake2
+    }
+    pub fn new_ptr() -> RdiffBlake2Ptr {
+        Box::new(RdiffBlake2::new())
+    }
+}
+
+impl StrongHash for RdiffBlake2 {
+    fn get_id(&self) -> u32 {
+        RDIFF_BLAKE2_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        RDIFF_BLAKE2_DIGEST_SIZE
+    }
+
+    fn digest(&self, chunk: &[u8]) -> RdiffDigest {
+        let mut hasher = Blake2b256::new();
+        hasher.update(chunk);
+        Vec::from(hasher.finalize().as_slice())
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        Box::new(RdiffBlake2Hasher {
+            hasher: Blake2b256::new(),
+        })
+    }
+}
+
+pub struct RdiffBlake2Hasher {
+    hasher: Blake2b256,
+}
+
+impl StrongHasher for RdiffBlake2Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    fn finalize(&self) -> RdiffDigest {
+        let result = self.hasher.clone().finalize();
+        Vec::from(result.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use std::fs;
+
+use hex_literal::hex;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::strong::StrongHash};
+
+use super::RdiffBlake2;
+
+#[test]
+fn test_rdiff_blake2_digest_case1() {
+    let rdiff_blake2 = RdiffBlake2::new();
+    let digest = rdiff_blake2.digest(b"abc");
+    assert_eq!(
+        digest,
+        hex!("bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319")
+    );
+    assert_eq!(digest.len(), rdiff_blake2.get_digest_size());
+}
+
+#[test]
+fn test_rdiff_blake2_hasher_case1() {
+    let rdiff_blake2 = RdiffBlake2::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+
+    // Update hasher one chunk at a time
+    let mut hasher = rdiff_blake2.hasher();
+    data.chunks(BLOCK_SIZE)
+        .for_each(|chunk| hasher.update(chunk));
+    assert_eq!(hasher.finalize(), rdiff_blake2.digest(&data));
+}
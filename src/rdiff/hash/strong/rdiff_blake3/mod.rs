@@ -0,0 +1,64 @@
+use blake3::Hasher;
+
+use crate::rdiff::chunk::RdiffDigest;
+
+use super::{StrongHash, StrongHasher, StrongHasherPtr};
+
+pub const RDIFF_BLAKE3_ID: u32 = 4;
+const RDIFF_BLAKE3_DIGEST_SIZE: usize = 32;
+
+// BLAKE3 with its default 32 bytes digest, faster than SHA-1 and collision resistant
+pub type RdiffBlake3Ptr = Box<RdiffBlake3>;
+pub struct RdiffBlake3;
+
+impl Default for RdiffBlake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffBlake3 {
+    pub fn new() -> RdiffBlake3 {
+        RdiffBlake3
+    }
+    pub fn new_ptr() -> RdiffBlake3Ptr {
+        Box::new(RdiffBlake3::new())
+    }
+}
+
+impl StrongHash for RdiffBlake3 {
+    fn get_id(&self) -> u32 {
+        RDIFF_BLAKE3_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        RDIFF_BLAKE3_DIGEST_SIZE
+    }
+
+    fn digest(&self, chunk: &[u8]) -> RdiffDigest {
+        Vec::from(blake3::hash(chunk).as_bytes().as_slice())
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        Box::new(RdiffBlake3Hasher {
+            hasher: Hasher::new(),
+        })
+    }
+}
+
+pub struct RdiffBlake3Hasher {
+    hasher: Hasher,
+}
+
+impl StrongHasher for RdiffBlake3Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    fn finalize(&self) -> RdiffDigest {
+        Vec::from(self.hasher.finalize().as_bytes().as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use std::fs;
+
+use hex_literal::hex;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::strong::StrongHash};
+
+use super::RdiffBlake3;
+
+#[test]
+fn test_rdiff_blake3_digest_case1() {
+    let rdiff_blake3 = RdiffBlake3::new();
+    let digest = rdiff_blake3.digest(b"abc");
+    assert_eq!(
+        digest,
+        hex!("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")
+    );
+    assert_eq!(digest.len(), rdiff_blake3.get_digest_size());
+}
+
+#[test]
+fn test_rdiff_blake3_hasher_case1() {
+    let rdiff_blake3 = RdiffBlake3::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+
+    // Update hasher one chunk at a time
+    let mut hasher = rdiff_blake3.hasher();
+    data.chunks(BLOCK_SIZE)
+        .for_each(|chunk| hasher.update(chunk));
+    assert_eq!(hasher.finalize(), rdiff_blake3.digest(&data));
+}
@@ -0,0 +1,64 @@
+use md4::{Digest, Md4};
+
+use crate::rdiff::chunk::RdiffDigest;
+
+use super::{StrongHash, StrongHasher, StrongHasherPtr};
+
+pub const RDIFF_MD4_ID: u32 = 2;
+const RDIFF_MD4_DIGEST_SIZE: usize = 16;
+
+pub type RdiffMd4Ptr = Box<RdiffMd4>;
+pub struct RdiffMd4;
+
+impl Default for RdiffMd4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffMd4 {
+    pub fn new() -> RdiffMd4 {
+        RdiffMd4
+    }
+    pub fn new_ptr() -> RdiffMd4Ptr {
+        Box::new(RdiffMd4::new())
+    }
+}
+
+impl StrongHash for RdiffMd4 {
+    fn get_id(&self) -> u32 {
+        RDIFF_MD4_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        RDIFF_MD4_DIGEST_SIZE
+    }
+
+    fn digest(&self, chunk: &[u8]) -> RdiffDigest {
+        let mut hasher = Md4::new();
+        hasher.update(chunk);
+        Vec::from(hasher.finalize().as_slice())
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        Box::new(RdiffMd4Hasher { hasher: Md4::new() })
+    }
+}
+
+pub struct RdiffMd4Hasher {
+    hasher: Md4,
+}
+
+impl StrongHasher for RdiffMd4Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    fn finalize(&self) -> RdiffDigest {
+        let result = self.hasher.clone().finalize();
+        Vec::from(result.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests;
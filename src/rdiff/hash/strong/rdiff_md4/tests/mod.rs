@@ -0,0 +1,27 @@
+use std::fs;
+
+use hex_literal::hex;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::strong::StrongHash};
+
+use super::RdiffMd4;
+
+#[test]
+fn test_rdiff_md4_digest_case1() {
+    let rdiff_md4 = RdiffMd4::new();
+    let digest = rdiff_md4.digest(b"abc");
+    assert_eq!(digest, hex!("a448017aaf21d8525fc10ae87aa6729d"));
+    assert_eq!(digest.len(), rdiff_md4.get_digest_size());
+}
+
+#[test]
+fn test_rdiff_md4_hasher_case1() {
+    let rdiff_md4 = RdiffMd4::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+
+    // Update hasher one chunk at a time
+    let mut hasher = rdiff_md4.hasher();
+    data.chunks(BLOCK_SIZE)
+        .for_each(|chunk| hasher.update(chunk));
+    assert_eq!(hasher.finalize(), rdiff_md4.digest(&data));
+}
@@ -2,11 +2,20 @@ use sha1::{Digest, Sha1};
 
 use crate::rdiff::chunk::RdiffDigest;
 
-use super::StrongHash;
+use super::{StrongHash, StrongHasher, StrongHasherPtr};
+
+pub const RDIFF_SHA1_ID: u32 = 1;
+const RDIFF_SHA1_DIGEST_SIZE: usize = 20;
 
 pub type RdiffSha1Ptr = Box<RdiffSha1>;
 pub struct RdiffSha1;
 
+impl Default for RdiffSha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RdiffSha1 {
     pub fn new() -> RdiffSha1 {
         RdiffSha1
@@ -17,12 +26,43 @@ impl RdiffSha1 {
 }
 
 impl StrongHash for RdiffSha1 {
+    fn get_id(&self) -> u32 {
+        RDIFF_SHA1_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        RDIFF_SHA1_DIGEST_SIZE
+    }
+
     fn digest(&self, chunk: &[u8]) -> RdiffDigest {
         let mut hasher = Sha1::new();
         hasher.update(chunk);
         let result = hasher.finalize();
         let result2 = result.as_slice();
-        let vec = Vec::from(result2);
-        vec
+        Vec::from(result2)
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        Box::new(RdiffSha1Hasher {
+            hasher: Sha1::new(),
+        })
     }
 }
+
+pub struct RdiffSha1Hasher {
+    hasher: Sha1,
+}
+
+impl StrongHasher for RdiffSha1Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    fn finalize(&self) -> RdiffDigest {
+        let result = self.hasher.clone().finalize();
+        Vec::from(result.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use std::fs;
+
+use hex_literal::hex;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::strong::StrongHash};
+
+use super::RdiffSha1;
+
+#[test]
+fn test_rdiff_sha1_digest_case1() {
+    let rdiff_sha1 = RdiffSha1::new();
+    let digest = rdiff_sha1.digest(b"hello world");
+    assert_eq!(digest, hex!("2aae6c35c94fcfb415dbe95f408b9ce91ee846ed"));
+    assert_eq!(digest.len(), rdiff_sha1.get_digest_size());
+}
+
+#[test]
+fn test_rdiff_sha1_hasher_case1() {
+    let rdiff_sha1 = RdiffSha1::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+
+    // Update hasher one chunk at a time
+    let mut hasher = rdiff_sha1.hasher();
+    data.chunks(BLOCK_SIZE)
+        .for_each(|chunk| hasher.update(chunk));
+    assert_eq!(hasher.finalize(), rdiff_sha1.digest(&data));
+}
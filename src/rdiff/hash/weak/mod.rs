@@ -1,9 +1,26 @@
 pub mod rdiff_addler;
+pub mod rdiff_rabinkarp;
+pub mod rdiff_rollsum;
 
-use crate::rdiff::chunk::RdiffChecksum;
+use crate::rdiff::{chunk::RdiffChecksum, error::RollingHashError};
+
+use super::registry::get_weak_hash_ptr_by_id;
 
 pub type WeakHashPtr = Box<dyn WeakHash>;
+pub type RollingWeakHashPtr = Box<dyn RollingWeakHash>;
 
 pub trait WeakHash {
+    fn get_id(&self) -> u32;
     fn checksum(&self, chunk: &[u8]) -> RdiffChecksum;
+    fn rolling_checksum(&self, chunk: &[u8]) -> RollingWeakHashPtr;
+}
+
+pub trait RollingWeakHash {
+    fn checksum(&self) -> RdiffChecksum;
+    fn roll(&mut self, out_byte: u8, in_byte: u8);
+}
+
+// Weak hash with the given id, built in or registered by the application
+pub fn new_weak_hash_ptr(id: u32) -> Result<WeakHashPtr, RollingHashError> {
+    get_weak_hash_ptr_by_id(id)
 }
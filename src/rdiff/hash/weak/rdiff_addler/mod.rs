@@ -2,11 +2,20 @@ use adler::adler32_slice;
 
 use crate::rdiff::chunk::RdiffChecksum;
 
-use super::WeakHash;
+use super::{RollingWeakHash, RollingWeakHashPtr, WeakHash};
+
+pub const RDIFF_ADDLER_ID: u32 = 1;
+const ADLER_MODULUS: u32 = 65521;
 
 pub type RdiffAddlerPtr = Box<RdiffAddler>;
 pub struct RdiffAddler {}
 
+impl Default for RdiffAddler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RdiffAddler {
     pub fn new() -> RdiffAddler {
         RdiffAddler {}
@@ -17,9 +26,52 @@ impl RdiffAddler {
 }
 
 impl WeakHash for RdiffAddler {
+    fn get_id(&self) -> u32 {
+        RDIFF_ADDLER_ID
+    }
+
     fn checksum(&self, chunk: &[u8]) -> RdiffChecksum {
+        adler32_slice(chunk)
+    }
+
+    fn rolling_checksum(&self, chunk: &[u8]) -> RollingWeakHashPtr {
+        Box::new(RollingRdiffAddler::new(chunk))
+    }
+}
+
+pub struct RollingRdiffAddler {
+    a: u32,
+    b: u32,
+    window_size: u32,
+}
+
+impl RollingRdiffAddler {
+    pub fn new(chunk: &[u8]) -> RollingRdiffAddler {
         let checksum = adler32_slice(chunk);
-        checksum
+        let window_size = (chunk.len() as u64 % ADLER_MODULUS as u64) as u32;
+        RollingRdiffAddler {
+            a: checksum & 0xffff,
+            b: checksum >> 16,
+            window_size,
+        }
+    }
+}
+
+impl RollingWeakHash for RollingRdiffAddler {
+    fn checksum(&self) -> RdiffChecksum {
+        (self.b << 16) | self.a
+    }
+
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        // a' = a - out + in
+        // b' = b - window_size * out + a' - 1
+        // All values are kept in [0, ADLER_MODULUS) so adding the modulus
+        // before subtracting avoids underflow
+        let out_byte = out_byte as u32;
+        let in_byte = in_byte as u32;
+        self.a = (self.a + ADLER_MODULUS - out_byte + in_byte) % ADLER_MODULUS;
+        let out_weight = (self.window_size * out_byte) % ADLER_MODULUS;
+        self.b = (self.b + 2 * ADLER_MODULUS - out_weight + self.a - 1) % ADLER_MODULUS;
     }
 }
 
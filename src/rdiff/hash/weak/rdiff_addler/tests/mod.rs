@@ -1 +1,41 @@
+use std::fs;
 
+use crate::rdiff::{constants::BLOCK_SIZE, hash::weak::WeakHash};
+
+use super::RdiffAddler;
+
+#[test]
+fn test_rdiff_addler_rolling_checksum_case1() {
+    let rdiff_addler = RdiffAddler::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+    assert!(data.len() > BLOCK_SIZE);
+
+    // Roll the checksum one byte at a time over the whole file
+    let mut rolling_checksum = rdiff_addler.rolling_checksum(&data[..BLOCK_SIZE]);
+    assert_eq!(
+        rolling_checksum.checksum(),
+        rdiff_addler.checksum(&data[..BLOCK_SIZE])
+    );
+    for start in 1..=(data.len() - BLOCK_SIZE) {
+        rolling_checksum.roll(data[start - 1], data[start + BLOCK_SIZE - 1]);
+        let expected_checksum = rdiff_addler.checksum(&data[start..start + BLOCK_SIZE]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
+
+#[test]
+fn test_rdiff_addler_rolling_checksum_case2() {
+    let rdiff_addler = RdiffAddler::new();
+    // High bytes force every modular subtraction to wrap around
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&[0xff; 4 * BLOCK_SIZE]);
+    data.extend_from_slice(&[0x00; 4 * BLOCK_SIZE]);
+    data.extend_from_slice(&[0xff; 4 * BLOCK_SIZE]);
+
+    let mut rolling_checksum = rdiff_addler.rolling_checksum(&data[..BLOCK_SIZE]);
+    for start in 1..=(data.len() - BLOCK_SIZE) {
+        rolling_checksum.roll(data[start - 1], data[start + BLOCK_SIZE - 1]);
+        let expected_checksum = rdiff_addler.checksum(&data[start..start + BLOCK_SIZE]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
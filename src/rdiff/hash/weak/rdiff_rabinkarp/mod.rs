@@ -0,0 +1,83 @@
+use crate::rdiff::chunk::RdiffChecksum;
+
+use super::{RollingWeakHash, RollingWeakHashPtr, WeakHash};
+
+pub const RDIFF_RABINKARP_ID: u32 = 3;
+// Seed and multiplier, the same ones used by librsync
+const RABINKARP_SEED: u32 = 1;
+const RABINKARP_MULT: u32 = 0x0810_4225;
+// Removing a byte also removes the seed growth of one multiplication, MULT - 1
+const RABINKARP_ADJ: u32 = RABINKARP_MULT - 1;
+
+// Rabin-Karp polynomial hash modulo 2^32, every byte is spread over the whole checksum
+pub type RdiffRabinKarpPtr = Box<RdiffRabinKarp>;
+pub struct RdiffRabinKarp {}
+
+impl Default for RdiffRabinKarp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffRabinKarp {
+    pub fn new() -> RdiffRabinKarp {
+        RdiffRabinKarp {}
+    }
+    pub fn new_ptr() -> RdiffRabinKarpPtr {
+        Box::new(RdiffRabinKarp::new())
+    }
+}
+
+impl WeakHash for RdiffRabinKarp {
+    fn get_id(&self) -> u32 {
+        RDIFF_RABINKARP_ID
+    }
+
+    fn checksum(&self, chunk: &[u8]) -> RdiffChecksum {
+        RollingRdiffRabinKarp::new(chunk).checksum()
+    }
+
+    fn rolling_checksum(&self, chunk: &[u8]) -> RollingWeakHashPtr {
+        Box::new(RollingRdiffRabinKarp::new(chunk))
+    }
+}
+
+pub struct RollingRdiffRabinKarp {
+    hash: u32,
+    mult: u32,
+}
+
+impl RollingRdiffRabinKarp {
+    pub fn new(chunk: &[u8]) -> RollingRdiffRabinKarp {
+        // hash = SEED * MULT^n + sum of byte * MULT^(n - 1 - position)
+        // mult = MULT^n, the weight of the seed
+        let mut hash = RABINKARP_SEED;
+        let mut mult: u32 = 1;
+        for byte in chunk.iter() {
+            hash = hash.wrapping_mul(RABINKARP_MULT).wrapping_add(*byte as u32);
+            mult = mult.wrapping_mul(RABINKARP_MULT);
+        }
+        RollingRdiffRabinKarp { hash, mult }
+    }
+}
+
+impl RollingWeakHash for RollingRdiffRabinKarp {
+    fn checksum(&self) -> RdiffChecksum {
+        self.hash
+    }
+
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        // hash' = hash * MULT + in - MULT^n * (out + MULT - 1)
+        self.hash = self
+            .hash
+            .wrapping_mul(RABINKARP_MULT)
+            .wrapping_add(in_byte as u32)
+            .wrapping_sub(
+                self.mult
+                    .wrapping_mul((out_byte as u32).wrapping_add(RABINKARP_ADJ)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
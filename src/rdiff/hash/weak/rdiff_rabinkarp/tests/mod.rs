@@ -0,0 +1,56 @@
+use std::fs;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::weak::WeakHash};
+
+use super::RdiffRabinKarp;
+
+#[test]
+fn test_rdiff_rabinkarp_checksum_case1() {
+    let rdiff_rabinkarp = RdiffRabinKarp::new();
+    // Empty chunk checksum is the seed
+    assert_eq!(rdiff_rabinkarp.checksum(&[]), 1);
+    // hash = MULT + 'a'
+    assert_eq!(rdiff_rabinkarp.checksum(b"a"), 0x0810_4225 + 0x61);
+    assert_eq!(
+        rdiff_rabinkarp.checksum(b"abc"),
+        0x0810_4225_u32
+            .wrapping_mul(0x0810_4225)
+            .wrapping_mul(0x0810_4225)
+            .wrapping_add(0x61_u32.wrapping_mul(0x0810_4225).wrapping_mul(0x0810_4225))
+            .wrapping_add(0x62_u32.wrapping_mul(0x0810_4225))
+            .wrapping_add(0x63)
+    );
+}
+
+#[test]
+fn test_rdiff_rabinkarp_rolling_checksum_case1() {
+    let rdiff_rabinkarp = RdiffRabinKarp::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+    assert!(data.len() > BLOCK_SIZE);
+
+    // Roll the checksum one byte at a time over the whole file
+    let mut rolling_checksum = rdiff_rabinkarp.rolling_checksum(&data[..BLOCK_SIZE]);
+    for start in 1..=(data.len() - BLOCK_SIZE) {
+        rolling_checksum.roll(data[start - 1], data[start + BLOCK_SIZE - 1]);
+        let expected_checksum = rdiff_rabinkarp.checksum(&data[start..start + BLOCK_SIZE]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
+
+#[test]
+fn test_rdiff_rabinkarp_rolling_checksum_case2() {
+    let rdiff_rabinkarp = RdiffRabinKarp::new();
+    // Low entropy data, where windows only differ by their position
+    let window_size = 1024;
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&[0xff; 4 * 1024]);
+    data.extend_from_slice(&[0x00; 4 * 1024]);
+    data.extend_from_slice(&[0xff; 4 * 1024]);
+
+    let mut rolling_checksum = rdiff_rabinkarp.rolling_checksum(&data[..window_size]);
+    for start in 1..=(data.len() - window_size) {
+        rolling_checksum.roll(data[start - 1], data[start + window_size - 1]);
+        let expected_checksum = rdiff_rabinkarp.checksum(&data[start..start + window_size]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
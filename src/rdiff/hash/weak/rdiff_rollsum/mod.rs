@@ -0,0 +1,85 @@
+use crate::rdiff::chunk::RdiffChecksum;
+
+use super::{RollingWeakHash, RollingWeakHashPtr, WeakHash};
+
+pub const RDIFF_ROLLSUM_ID: u32 = 2;
+// Offset added to every byte, the same one used by rsync and librsync
+const ROLLSUM_CHAR_OFFSET: u16 = 31;
+
+// rsync and librsync rolling checksum, both sums are kept modulo 2^16
+pub type RdiffRollsumPtr = Box<RdiffRollsum>;
+pub struct RdiffRollsum {}
+
+impl Default for RdiffRollsum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdiffRollsum {
+    pub fn new() -> RdiffRollsum {
+        RdiffRollsum {}
+    }
+    pub fn new_ptr() -> RdiffRollsumPtr {
+        Box::new(RdiffRollsum::new())
+    }
+}
+
+impl WeakHash for RdiffRollsum {
+    fn get_id(&self) -> u32 {
+        RDIFF_ROLLSUM_ID
+    }
+
+    fn checksum(&self, chunk: &[u8]) -> RdiffChecksum {
+        RollingRdiffRollsum::new(chunk).checksum()
+    }
+
+    fn rolling_checksum(&self, chunk: &[u8]) -> RollingWeakHashPtr {
+        Box::new(RollingRdiffRollsum::new(chunk))
+    }
+}
+
+pub struct RollingRdiffRollsum {
+    s1: u16,
+    s2: u16,
+    window_size: u16,
+}
+
+impl RollingRdiffRollsum {
+    pub fn new(chunk: &[u8]) -> RollingRdiffRollsum {
+        // s1 = sum of bytes, s2 = sum of s1 after every byte
+        let mut s1: u16 = 0;
+        let mut s2: u16 = 0;
+        for byte in chunk.iter() {
+            s1 = s1.wrapping_add(*byte as u16 + ROLLSUM_CHAR_OFFSET);
+            s2 = s2.wrapping_add(s1);
+        }
+        RollingRdiffRollsum {
+            s1,
+            s2,
+            window_size: chunk.len() as u16,
+        }
+    }
+}
+
+impl RollingWeakHash for RollingRdiffRollsum {
+    fn checksum(&self) -> RdiffChecksum {
+        ((self.s2 as u32) << 16) | self.s1 as u32
+    }
+
+    fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        // s1' = s1 - out + in
+        // s2' = s2 - window_size * out + s1'
+        // Window size is only needed modulo 2^16 as sums wrap around
+        let out_value = out_byte as u16 + ROLLSUM_CHAR_OFFSET;
+        let in_value = in_byte as u16 + ROLLSUM_CHAR_OFFSET;
+        self.s1 = self.s1.wrapping_sub(out_value).wrapping_add(in_value);
+        self.s2 = self
+            .s2
+            .wrapping_sub(self.window_size.wrapping_mul(out_value))
+            .wrapping_add(self.s1);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,46 @@
+use std::fs;
+
+use crate::rdiff::{constants::BLOCK_SIZE, hash::weak::WeakHash};
+
+use super::RdiffRollsum;
+
+#[test]
+fn test_rdiff_rollsum_checksum_case1() {
+    let rdiff_rollsum = RdiffRollsum::new();
+    // s1 = 128 + 129 + 130, s2 = 128 + 257 + 387
+    assert_eq!(rdiff_rollsum.checksum(b"abc"), (772 << 16) | 387);
+    assert_eq!(rdiff_rollsum.checksum(&[]), 0);
+}
+
+#[test]
+fn test_rdiff_rollsum_rolling_checksum_case1() {
+    let rdiff_rollsum = RdiffRollsum::new();
+    let data = fs::read("resources/poem.txt").unwrap();
+    assert!(data.len() > BLOCK_SIZE);
+
+    // Roll the checksum one byte at a time over the whole file
+    let mut rolling_checksum = rdiff_rollsum.rolling_checksum(&data[..BLOCK_SIZE]);
+    for start in 1..=(data.len() - BLOCK_SIZE) {
+        rolling_checksum.roll(data[start - 1], data[start + BLOCK_SIZE - 1]);
+        let expected_checksum = rdiff_rollsum.checksum(&data[start..start + BLOCK_SIZE]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
+
+#[test]
+fn test_rdiff_rollsum_rolling_checksum_case2() {
+    let rdiff_rollsum = RdiffRollsum::new();
+    // High bytes and a big window force both sums to wrap around
+    let window_size = 1024;
+    let mut data: Vec<u8> = Vec::new();
+    data.extend_from_slice(&[0xff; 4 * 1024]);
+    data.extend_from_slice(&[0x00; 4 * 1024]);
+    data.extend_from_slice(&[0xff; 4 * 1024]);
+
+    let mut rolling_checksum = rdiff_rollsum.rolling_checksum(&data[..window_size]);
+    for start in 1..=(data.len() - window_size) {
+        rolling_checksum.roll(data[start - 1], data[start + window_size - 1]);
+        let expected_checksum = rdiff_rollsum.checksum(&data[start..start + window_size]);
+        assert_eq!(rolling_checksum.checksum(), expected_checksum);
+    }
+}
@@ -1,48 +1,96 @@
+use std::fmt::{self, Debug};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, ErrorKind, Read};
 
-use crate::rdiff::constants::BLOCK_SIZE;
+use crate::rdiff::constants::READ_BLOCK_SIZE;
 use crate::rdiff::error::RdiffError;
 
 use super::error::RollingHashError;
 
-#[derive(Debug)]
-pub struct RdiffFile {
-    reader: BufReader<File>,
+pub mod window;
+
+// Rdiff file data comes from a file, a memory buffer or any other reader,
+// readers other than files are named by the caller.
+// Size is unknown for streams like stdin, pipes or sockets, the end of data
+// is then only found when the reader returns no more bytes
+pub struct RdiffFile<'a> {
+    reader: BufReader<Box<dyn Read + 'a>>,
     name: String,
-    size: u64,
+    size: Option<u64>,
+    read_size: u64,
 }
 
-impl RdiffFile {
-    pub fn new(filename: &str) -> Result<RdiffFile, RollingHashError> {
-        let f = File::open(filename).or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
+impl<'a> RdiffFile<'a> {
+    pub fn new(filename: &str) -> Result<RdiffFile<'a>, RollingHashError> {
+        let f = File::open(filename).map_err(|e| RollingHashError::from(Box::new(e)))?;
+
+        let size = f
+            .metadata()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?
+            .len();
+        Ok(RdiffFile::new_with_reader(filename, f, size))
+    }
+
+    pub fn new_with_reader<R: Read + 'a>(name: &str, reader: R, size: u64) -> RdiffFile<'a> {
+        let mut rdiff_file = RdiffFile::new_with_unsized_reader(name, reader);
+        rdiff_file.size = Some(size);
+        rdiff_file
+    }
+
+    pub fn new_with_unsized_reader<R: Read + 'a>(name: &str, reader: R) -> RdiffFile<'a> {
+        let reader: Box<dyn Read + 'a> = Box::new(reader);
+        RdiffFile {
+            reader: BufReader::new(reader),
+            name: String::from(name),
+            size: None,
+            read_size: 0,
+        }
+    }
+
+    pub fn new_with_bytes(name: &str, data: &'a [u8]) -> RdiffFile<'a> {
+        RdiffFile::new_with_reader(name, data, data.len() as u64)
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
 
-        let size = f.metadata().unwrap().len();
-        let reader = BufReader::new(f);
-        Ok(RdiffFile {
-            reader: reader,
-            name: String::from(filename),
-            size: size,
-        })
+    pub fn size(&self) -> Option<usize> {
+        self.size.map(|size| size as usize)
     }
 
-    pub fn size(&self) -> usize {
-        let size = self.size as usize;
-        size
+    // Number of bytes read so far, it is the file size once there is no more data
+    pub fn get_read_size(&self) -> usize {
+        self.read_size as usize
     }
 
     pub fn read_byte(&mut self) -> Option<u8> {
         let mut buffer = [0; 1];
-        let x = self.reader.read(&mut buffer[..]);
+        let x = self.read_into(&mut buffer[..]);
         match x {
             Ok(c) if c > 0 => Some(buffer[0]),
             _ => None,
         }
     }
 
-    pub fn read_block(&mut self) -> Result<Option<(usize, [u8; BLOCK_SIZE as usize])>, RdiffError> {
-        let mut buffer: [u8; BLOCK_SIZE as usize] = [0; BLOCK_SIZE as usize];
-        let x = self.reader.read(&mut buffer)?;
+    pub fn read_into(&mut self, buffer: &mut [u8]) -> Result<usize, RdiffError> {
+        // Reads interrupted before reading any data are retried, streams
+        // return no data only at their end
+        loop {
+            match self.reader.read(buffer) {
+                Ok(size) => {
+                    self.read_size += size as u64;
+                    return Ok(size);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    pub fn read_block(&mut self) -> Result<Option<(usize, [u8; READ_BLOCK_SIZE])>, RdiffError> {
+        let mut buffer: [u8; READ_BLOCK_SIZE] = [0; READ_BLOCK_SIZE];
+        let x = self.read_into(&mut buffer)?;
         match x {
             c if c > 0 => Ok(Some((c, buffer))),
             _ => Ok(None),
@@ -50,7 +98,16 @@ impl RdiffFile {
     }
 }
 
-impl PartialEq for RdiffFile {
+impl Debug for RdiffFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RdiffFile")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl PartialEq for RdiffFile<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
     }
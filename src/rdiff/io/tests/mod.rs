@@ -1,11 +1,16 @@
-use crate::rdiff::{constants::BLOCK_SIZE, io::RdiffFile};
+use std::{
+    cmp, fs,
+    io::{self, Cursor, ErrorKind, Read},
+};
+
+use crate::rdiff::{constants::READ_BLOCK_SIZE, io::RdiffFile};
 
 #[test]
 fn test_rdiff_file_read_block_case1() {
     let filename = "resources/poem.txt";
     let mut rdiff_file = RdiffFile::new(filename).unwrap();
-    let (size, block) = rdiff_file.read_block().unwrap().unwrap();
-    assert_eq!(size, BLOCK_SIZE);
+    let (size, _block) = rdiff_file.read_block().unwrap().unwrap();
+    assert_eq!(size, cmp::min(READ_BLOCK_SIZE, rdiff_file.size().unwrap()));
 }
 
 #[test]
@@ -14,11 +19,76 @@ fn test_rdiff_file_read_block_case2() {
     let mut rdiff_file = RdiffFile::new(filename).unwrap();
     let mut count: usize = 0;
     while let Some(x) = rdiff_file.read_block().unwrap() {
-        let (size, block) = x;
+        let (size, _block) = x;
         count += 1;
         assert!(
-            (size == BLOCK_SIZE)
-                || (size == (rdiff_file.size() as usize) - (count - 1) * BLOCK_SIZE)
+            (size == READ_BLOCK_SIZE)
+                || (size == rdiff_file.size().unwrap() - (count - 1) * READ_BLOCK_SIZE)
         );
     }
 }
+
+#[test]
+fn test_rdiff_file_new_with_bytes_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+    let mut rdiff_file = RdiffFile::new_with_bytes("poem", data.as_slice());
+    assert_eq!(rdiff_file.size(), Some(data.len()));
+    assert_eq!(rdiff_file.get_name(), "poem");
+    let mut read_data: Vec<u8> = Vec::new();
+    while let Some((size, block)) = rdiff_file.read_block().unwrap() {
+        read_data.extend_from_slice(&block[..size]);
+    }
+    assert_eq!(read_data, data);
+}
+
+#[test]
+fn test_rdiff_file_new_with_reader_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+    let reader = Cursor::new(data.clone());
+    let mut rdiff_file = RdiffFile::new_with_reader("poem", reader, data.len() as u64);
+    assert_eq!(rdiff_file.size(), Some(data.len()));
+    let mut read_data: Vec<u8> = Vec::new();
+    while let Some(byte) = rdiff_file.read_byte() {
+        read_data.push(byte);
+    }
+    assert_eq!(read_data, data);
+}
+
+// Reader returning a few bytes at a time and interrupted every other read,
+// as a pipe or a socket may do
+struct TrickleReader {
+    data: Vec<u8>,
+    position: usize,
+    interrupted: bool,
+}
+
+impl Read for TrickleReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(io::Error::from(ErrorKind::Interrupted));
+        }
+        let size = cmp::min(cmp::min(buf.len(), 7), self.data.len() - self.position);
+        buf[..size].copy_from_slice(&self.data[self.position..self.position + size]);
+        self.position += size;
+        Ok(size)
+    }
+}
+
+#[test]
+fn test_rdiff_file_new_with_unsized_reader_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+    let reader = TrickleReader {
+        data: data.clone(),
+        position: 0,
+        interrupted: false,
+    };
+    let mut rdiff_file = RdiffFile::new_with_unsized_reader("stream", reader);
+    assert_eq!(rdiff_file.size(), None);
+    let mut read_data: Vec<u8> = Vec::new();
+    while let Some((size, block)) = rdiff_file.read_block().unwrap() {
+        read_data.extend_from_slice(&block[..size]);
+    }
+    assert_eq!(read_data, data);
+    assert_eq!(rdiff_file.get_read_size(), data.len());
+}
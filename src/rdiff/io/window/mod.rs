@@ -0,0 +1,115 @@
+use std::cmp;
+
+use crate::rdiff::{
+    chunk::iterator::BufferedRdiffChunkIterator, constants::WINDOW_SIZE, error::RollingHashError,
+};
+
+use super::RdiffFile;
+
+// Fixed capacity ring buffer over a rdiff file.
+// The window start and end are absolute positions in the file, so advancing
+// the window only updates an index and buffered data is never moved.
+// The ring is mirrored after its end, so any window range is contiguous
+#[derive(Debug)]
+pub struct RdiffWindow<'a> {
+    buffer: Vec<u8>,
+    mask: usize,
+    start: usize,
+    end: usize,
+    eof: bool,
+    chunk_size: usize,
+    rdiff_file: RdiffFile<'a>,
+}
+
+impl<'a> RdiffWindow<'a> {
+    pub fn new(
+        chunk_size: usize,
+        rdiff_file: RdiffFile<'a>,
+    ) -> Result<RdiffWindow<'a>, RollingHashError> {
+        BufferedRdiffChunkIterator::validate_chunk_size(chunk_size)?;
+        // Capacity is a power of two so positions can be wrapped with a mask
+        let capacity = cmp::max(WINDOW_SIZE, 2 * chunk_size).next_power_of_two();
+        let mut rdiff_window = RdiffWindow {
+            buffer: vec![0; 2 * capacity],
+            mask: capacity - 1,
+            start: 0,
+            end: 0,
+            eof: false,
+            chunk_size,
+            rdiff_file,
+        };
+        rdiff_window.fill()?;
+        Ok(rdiff_window)
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Number of bytes read from file so far
+    pub fn get_read_size(&self) -> usize {
+        self.rdiff_file.get_read_size()
+    }
+
+    pub fn has_chunk(&self) -> bool {
+        self.len() >= self.chunk_size
+    }
+
+    pub fn get(&self, offset: usize) -> u8 {
+        self.buffer[(self.start + offset) & self.mask]
+    }
+
+    // Get the first size bytes of the window as a contiguous slice,
+    // bytes wrapping around the ring are read from its mirror
+    pub fn peek(&self, size: usize) -> &[u8] {
+        let size = cmp::min(size, self.len());
+        let first = self.start & self.mask;
+        &self.buffer[first..first + size]
+    }
+
+    // Remove the first byte of the window and return it
+    pub fn pop_front(&mut self) -> Result<u8, RollingHashError> {
+        let byte = self.get(0);
+        self.consume(1)?;
+        Ok(byte)
+    }
+
+    // Advance the window start by size bytes
+    pub fn consume(&mut self, size: usize) -> Result<(), RollingHashError> {
+        self.start += cmp::min(size, self.len());
+        // Only read from file when a whole chunk is no longer available
+        if !self.has_chunk() {
+            self.fill()?;
+        }
+        Ok(())
+    }
+
+    fn fill(&mut self) -> Result<(), RollingHashError> {
+        let capacity = self.mask + 1;
+        while !self.eof && self.len() < capacity {
+            // Read into the contiguous free region after the window end
+            let first = self.end & self.mask;
+            let free_size = cmp::min(capacity - first, capacity - self.len());
+            let size = self
+                .rdiff_file
+                .read_into(&mut self.buffer[first..first + free_size])
+                .map_err(RollingHashError::from)?;
+            if size == 0 {
+                self.eof = true;
+            } else {
+                // Copy read data to the ring mirror
+                self.buffer
+                    .copy_within(first..first + size, capacity + first);
+                self.end += size;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use std::fs;
+
+use crate::rdiff::{
+    constants::{BLOCK_SIZE, MAX_BLOCK_SIZE, WINDOW_SIZE},
+    error::{messages::INVALID_CHUNK_SIZE, RollingHashError},
+    io::RdiffFile,
+    util::now_as_millis,
+};
+
+use super::RdiffWindow;
+
+#[test]
+fn test_rdiff_window_pop_front_case1() {
+    let file_name = "resources/poem.txt";
+    let data = fs::read(file_name).unwrap();
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let mut window = RdiffWindow::new(BLOCK_SIZE, rdiff_file).unwrap();
+    assert_eq!(window.len(), data.len());
+
+    // Pop every byte in the window
+    let mut window_data: Vec<u8> = Vec::new();
+    while !window.is_empty() {
+        window_data.push(window.pop_front().unwrap());
+    }
+    assert_eq!(window_data, data);
+}
+
+#[test]
+fn test_rdiff_window_peek_case1() {
+    // Create a file bigger than window size so the ring wraps around
+    let file_name = format!("resources/test_rdiff_window_case1.{}.txt", now_as_millis());
+    let data: Vec<u8> = (0..(3 * WINDOW_SIZE + 17))
+        .map(|i| (i % 251) as u8)
+        .collect();
+    fs::write(file_name.as_str(), &data).unwrap();
+
+    let rdiff_file = RdiffFile::new(file_name.as_str()).unwrap();
+    let mut window = RdiffWindow::new(BLOCK_SIZE, rdiff_file).unwrap();
+    // Alternate whole chunk and single byte moves
+    let mut position = 0;
+    while window.has_chunk() {
+        assert_eq!(
+            window.peek(BLOCK_SIZE),
+            &data[position..position + BLOCK_SIZE]
+        );
+        assert_eq!(window.get(BLOCK_SIZE - 1), data[position + BLOCK_SIZE - 1]);
+        if position % 3 == 0 {
+            window.consume(BLOCK_SIZE).unwrap();
+            position += BLOCK_SIZE;
+        } else {
+            assert_eq!(window.pop_front().unwrap(), data[position]);
+            position += 1;
+        }
+    }
+    // Remaining data is smaller than a chunk
+    let last_chunk_size = window.len();
+    assert_eq!(position + last_chunk_size, data.len());
+    assert_eq!(window.peek(last_chunk_size), &data[position..]);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_window_invalid_chunk_size_case1() {
+    let file_name = "resources/poem.txt";
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let error = RdiffWindow::new(MAX_BLOCK_SIZE + 1, rdiff_file).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_SIZE));
+    let rdiff_file = RdiffFile::new(file_name).unwrap();
+    let error = RdiffWindow::new(0, rdiff_file).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_CHUNK_SIZE));
+}
@@ -1,17 +1,37 @@
 use self::{
-    constants::{DELTA, SIGNATURE},
+    chunk::iterator::fastcdc::FastCdcParameters,
+    compression::rdiff_deflate::RdiffDeflate,
+    constants::{
+        BLOCK_SIZE_OPTION, CHUNKING_OPTION, COMPOSE, COMPRESS_OPTION, DELTA, FASTCDC_AVG_SIZE,
+        FASTCDC_CHUNKING, FIXED_CHUNKING, FORMAT_OPTION, IN_PLACE_OPTION, LIBRSYNC_FORMAT, PATCH,
+        RDIFF_FORMAT, ROLLBACK_OPTION, SIGNATURE, STRONG_HASH_OPTION, STRONG_LEN_OPTION,
+        VCDIFF_FORMAT, WEAK_HASH_OPTION, WITHOUT_BASIS_OPTION,
+    },
     delta::Delta,
-    error::{messages::HELP_USAGE, RollingHashError},
-    hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
+    error::{
+        messages::{
+            COMPRESSION_FORMAT_UNSUPPORTED, FIXED_SIZE_CHUNKS_REQUIRED, HELP_USAGE,
+            INVALID_OPTION_VALUE,
+        },
+        RollingHashError,
+    },
+    hash::{
+        registry::{get_strong_hash_ptr_by_name, get_weak_hash_ptr_by_name},
+        strong::{rdiff_blake2::RdiffBlake2, rdiff_sha1::RdiffSha1, StrongHashPtr},
+        weak::{rdiff_addler::RdiffAddler, rdiff_rollsum::RdiffRollsum, WeakHashPtr},
+    },
+    options::RdiffOptions,
     signature::Signature,
 };
 
 pub mod chunk;
+pub mod compression;
 pub mod constants;
 pub mod delta;
 pub mod error;
 pub mod hash;
 pub mod io;
+pub mod options;
 pub mod signature;
 pub mod util;
 
@@ -21,11 +41,15 @@ pub struct Rdiff;
 
 impl Rdiff {
     pub fn main_rdiff(args: Vec<String>) -> RdiffMainResult {
+        // Split options from positional args
+        let options = RdiffOptions::new(args)?;
         // Check for command
-        if args.len() > 1 {
-            match args.get(1).unwrap().as_str() {
-                SIGNATURE => Rdiff::rdiff_signature(args),
-                DELTA => Rdiff::rdiff_delta(args),
+        if options.len() > 1 {
+            match options.get(1).unwrap().as_str() {
+                SIGNATURE => Rdiff::rdiff_signature(options),
+                DELTA => Rdiff::rdiff_delta(options),
+                PATCH => Rdiff::rdiff_patch(options),
+                COMPOSE => Rdiff::rdiff_compose(options),
                 _ => Err(RollingHashError::new(HELP_USAGE)),
             }
         } else {
@@ -33,54 +57,246 @@ impl Rdiff {
         }
     }
 
-    fn rdiff_signature(args: Vec<String>) -> RdiffMainResult {
+    fn rdiff_signature(options: RdiffOptions) -> RdiffMainResult {
         // Process signature command option
+        options.validate(&[
+            BLOCK_SIZE_OPTION,
+            CHUNKING_OPTION,
+            WEAK_HASH_OPTION,
+            STRONG_HASH_OPTION,
+            STRONG_LEN_OPTION,
+            FORMAT_OPTION,
+        ])?;
         // If there are enough args
-        if args.len() >= 4 {
+        if options.len() >= 4 {
             // Get target file name
-            let file_name = args.get(2).unwrap();
+            let file_name = options.get(2).unwrap();
             // Get signature file name where the signature will be stored
-            let signature_file_name = args.get(3).unwrap();
-            // Get strong hash to compute digest
-            let strong_hash_ptr = RdiffSha1::new_ptr();
-            // Get weak thas to compute checksum
-            let weak_hash_ptr = RdiffAddler::new_ptr();
-            // Create signature and store it in file
-            let result = Signature::create_signature_file(
-                file_name,
-                signature_file_name,
-                weak_hash_ptr,
-                strong_hash_ptr,
-            )?;
-            Ok(result)
+            let signature_file_name = options.get(3).unwrap();
+            // Get block size option
+            let chunk_size = options.get_usize_option(BLOCK_SIZE_OPTION)?;
+            // Get strong digest length option
+            let digest_size = options.get_usize_option(STRONG_LEN_OPTION)?;
+            // Get chunking option, chunks have a fixed size by default
+            let content_defined_chunking = match options
+                .get_option(CHUNKING_OPTION)
+                .map(|chunking| chunking.as_str())
+            {
+                None | Some(FIXED_CHUNKING) => false,
+                Some(FASTCDC_CHUNKING) => true,
+                Some(_) => return Err(RollingHashError::new(INVALID_OPTION_VALUE)),
+            };
+            // Get signature format
+            let librsync_format = match options
+                .get_option(FORMAT_OPTION)
+                .map(|format| format.as_str())
+            {
+                None | Some(RDIFF_FORMAT) => false,
+                Some(LIBRSYNC_FORMAT) => true,
+                Some(_) => return Err(RollingHashError::new(INVALID_OPTION_VALUE)),
+            };
+            // Get hash functions by name from the hash registry
+            // Defaults are Adler-32 and SHA-1, or rollsum and BLAKE2 as librsync ones
+            let weak_hash_ptr: WeakHashPtr = match options.get_option(WEAK_HASH_OPTION) {
+                Some(name) => get_weak_hash_ptr_by_name(name)?,
+                None if librsync_format => RdiffRollsum::new_ptr(),
+                None => RdiffAddler::new_ptr(),
+            };
+            let strong_hash_ptr: StrongHashPtr = match options.get_option(STRONG_HASH_OPTION) {
+                Some(name) => get_strong_hash_ptr_by_name(name)?,
+                None if librsync_format => RdiffBlake2::new_ptr(),
+                None => RdiffSha1::new_ptr(),
+            };
+            // librsync signatures only have fixed size chunks and librsync hash functions
+            if librsync_format {
+                if content_defined_chunking {
+                    return Err(RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+                }
+                Signature::get_librsync_signature_magic(
+                    weak_hash_ptr.get_id(),
+                    strong_hash_ptr.get_id(),
+                )?;
+            }
+            // Create signature
+            // Use block size option as chunk size if it is given,
+            // or as average chunk size with content defined chunking
+            let mut signature = if content_defined_chunking {
+                let fastcdc_parameters =
+                    FastCdcParameters::new_with_avg_size(chunk_size.unwrap_or(FASTCDC_AVG_SIZE))?;
+                Signature::create_signature_with_fastcdc(
+                    file_name,
+                    fastcdc_parameters,
+                    weak_hash_ptr,
+                    strong_hash_ptr,
+                )?
+            } else {
+                match chunk_size {
+                    Some(chunk_size) => Signature::create_signature_with_chunk_size(
+                        file_name,
+                        chunk_size,
+                        weak_hash_ptr,
+                        strong_hash_ptr,
+                    )?,
+                    None => Signature::create_signature(file_name, weak_hash_ptr, strong_hash_ptr)?,
+                }
+            };
+            // Truncate chunk digests if strong digest length is given
+            if let Some(digest_size) = digest_size {
+                signature.truncate_digests(digest_size)?;
+            }
+            // Store signature in file with the given format
+            if librsync_format {
+                Signature::write_librsync_signature_file(&signature, signature_file_name)
+            } else {
+                Signature::write_signature_file(&signature, signature_file_name)
+            }
         } else {
             Err(RollingHashError::new(HELP_USAGE))
         }
     }
 
-    fn rdiff_delta(args: Vec<String>) -> RdiffMainResult {
+    fn rdiff_delta(options: RdiffOptions) -> RdiffMainResult {
         // Process delta comand option
-        if args.len() >= 5 {
+        options.validate(&[COMPRESS_OPTION, FORMAT_OPTION])?;
+        if options.len() >= 5 {
             // Get signature file name, file where the signature is stored
-            let signature_file_name = args.get(2).unwrap();
+            let signature_file_name = options.get(2).unwrap();
             // Get file name, new version of original file
-            let file_name = args.get(3).unwrap();
+            let file_name = options.get(3).unwrap();
             // Get delta file name, file where the differences between the original and
             // new version will be stored
-            let delta_file_name = args.get(4).unwrap();
-            // Get strong hash to compute digest
-            let strong_hash_ptr = RdiffSha1::new_ptr();
-            // Get weak hash to compute checksum
-            let weak_hash_ptr = RdiffAddler::new_ptr();
+            let delta_file_name = options.get(4).unwrap();
             // Create checkum delta and store it in a file
-            let result = Delta::create_delta_file(
-                file_name,
+            // Hash functions are the ones recorded in the signature
+            let format = options
+                .get_option(FORMAT_OPTION)
+                .map_or(RDIFF_FORMAT, |format| format.as_str());
+            if ![RDIFF_FORMAT, LIBRSYNC_FORMAT, VCDIFF_FORMAT].contains(&format) {
+                return Err(RollingHashError::new(INVALID_OPTION_VALUE));
+            }
+            // Only rdiff delta format has compression
+            if format != RDIFF_FORMAT && options.has_flag(COMPRESS_OPTION) {
+                return Err(RollingHashError::new(COMPRESSION_FORMAT_UNSUPPORTED));
+            }
+            if format == LIBRSYNC_FORMAT {
+                Delta::create_librsync_delta_file_from_signature(
+                    file_name,
+                    delta_file_name,
+                    signature_file_name,
+                )?;
+            } else if format == VCDIFF_FORMAT {
+                Delta::create_vcdiff_delta_file_from_signature(
+                    file_name,
+                    delta_file_name,
+                    signature_file_name,
+                )?;
+            } else if options.has_flag(COMPRESS_OPTION) {
+                // Differences are compressed, show how much data it saves
+                let stats = Delta::create_delta_file_from_signature_with_compression(
+                    file_name,
+                    delta_file_name,
+                    signature_file_name,
+                    RdiffDeflate::new_ptr(),
+                )?;
+                println!("{}", stats);
+            } else {
+                Delta::create_delta_file_from_signature(
+                    file_name,
+                    delta_file_name,
+                    signature_file_name,
+                )?;
+            }
+            Ok(())
+        } else {
+            Err(RollingHashError::new(HELP_USAGE))
+        }
+    }
+
+    fn rdiff_patch(options: RdiffOptions) -> RdiffMainResult {
+        // Process patch command option
+        options.validate(&[BLOCK_SIZE_OPTION, IN_PLACE_OPTION, ROLLBACK_OPTION])?;
+        let rollback = options.has_flag(ROLLBACK_OPTION);
+        if options.has_flag(IN_PLACE_OPTION) && rollback && options.len() >= 3 {
+            // Get basis file name, file of the interrupted in place patch
+            let basis_file_name = options.get(2).unwrap();
+            // Remove patch journal, only possible before basis file data is overwritten
+            Delta::rollback_in_place_patch(basis_file_name)?;
+            Ok(())
+        } else if options.has_flag(IN_PLACE_OPTION) && !rollback && options.len() >= 4 {
+            // Get basis file name, file rewritten into the new version
+            let basis_file_name = options.get(2).unwrap();
+            // Get delta file name, file where the differences are stored
+            let delta_file_name = options.get(3).unwrap();
+            // Apply delta to basis file in place, an interrupted patch is resumed
+            match options.get_usize_option(BLOCK_SIZE_OPTION)? {
+                Some(chunk_size) => Delta::patch_file_in_place_with_chunk_size(
+                    basis_file_name,
+                    delta_file_name,
+                    chunk_size,
+                )?,
+                None => Delta::patch_file_in_place(basis_file_name, delta_file_name)?,
+            }
+            Ok(())
+        } else if !options.has_flag(IN_PLACE_OPTION) && !rollback && options.len() >= 5 {
+            // Get basis file name, original file the signature was created from
+            let basis_file_name = options.get(2).unwrap();
+            // Get delta file name, file where the differences are stored
+            let delta_file_name = options.get(3).unwrap();
+            // Get output file name, file where the new version will be rebuilt
+            let output_file_name = options.get(4).unwrap();
+            // Apply delta to basis file and store the result in output file
+            // Block size option must be the one used to create the signature
+            match options.get_usize_option(BLOCK_SIZE_OPTION)? {
+                Some(chunk_size) => Delta::create_patched_file_with_chunk_size(
+                    basis_file_name,
+                    delta_file_name,
+                    output_file_name,
+                    chunk_size,
+                )?,
+                None => {
+                    Delta::create_patched_file(basis_file_name, delta_file_name, output_file_name)?
+                }
+            }
+            Ok(())
+        } else {
+            Err(RollingHashError::new(HELP_USAGE))
+        }
+    }
+
+    fn rdiff_compose(options: RdiffOptions) -> RdiffMainResult {
+        // Process compose command option
+        options.validate(&[WITHOUT_BASIS_OPTION])?;
+        if options.has_flag(WITHOUT_BASIS_OPTION) && options.len() >= 5 {
+            // Get first delta file name, differences from basis to intermediate version
+            let first_delta_file_name = options.get(2).unwrap();
+            // Get second delta file name, differences from intermediate to new version
+            let second_delta_file_name = options.get(3).unwrap();
+            // Get delta file name, file where basis to new version differences will be stored
+            let delta_file_name = options.get(4).unwrap();
+            // Compose both deltas, chunk geometries are taken from delta headers
+            Delta::create_composed_delta_file_without_basis(
+                first_delta_file_name,
+                second_delta_file_name,
+                delta_file_name,
+            )?;
+            Ok(())
+        } else if !options.has_flag(WITHOUT_BASIS_OPTION) && options.len() >= 6 {
+            // Get basis file name, original file the first delta applies to
+            let basis_file_name = options.get(2).unwrap();
+            // Get first delta file name, differences from basis to intermediate version
+            let first_delta_file_name = options.get(3).unwrap();
+            // Get second delta file name, differences from intermediate to new version
+            let second_delta_file_name = options.get(4).unwrap();
+            // Get delta file name, file where basis to new version differences will be stored
+            let delta_file_name = options.get(5).unwrap();
+            // Compose both deltas into one delta applying to basis file
+            Delta::create_composed_delta_file(
+                basis_file_name,
+                first_delta_file_name,
+                second_delta_file_name,
                 delta_file_name,
-                signature_file_name,
-                weak_hash_ptr,
-                strong_hash_ptr,
             )?;
-            Ok(result)
+            Ok(())
         } else {
             Err(RollingHashError::new(HELP_USAGE))
         }
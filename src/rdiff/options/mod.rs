@@ -0,0 +1,101 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    constants::{COMPRESS_OPTION, IN_PLACE_OPTION, ROLLBACK_OPTION, WITHOUT_BASIS_OPTION},
+    error::{
+        messages::{HELP_USAGE, INVALID_OPTION_VALUE},
+        RollingHashError,
+    },
+};
+
+const OPTION_PREFIX: &str = "--";
+// Options that are not followed by a value
+const FLAG_OPTIONS: [&str; 4] = [
+    COMPRESS_OPTION,
+    IN_PLACE_OPTION,
+    ROLLBACK_OPTION,
+    WITHOUT_BASIS_OPTION,
+];
+
+#[derive(Debug, PartialEq)]
+pub struct RdiffOptions {
+    args: Vec<String>,
+    options: HashMap<String, String>,
+    flags: HashSet<String>,
+}
+
+impl RdiffOptions {
+    pub fn new(args: Vec<String>) -> Result<RdiffOptions, RollingHashError> {
+        let mut positional_args: Vec<String> = Vec::new();
+        let mut options: HashMap<String, String> = HashMap::new();
+        let mut flags: HashSet<String> = HashSet::new();
+        let mut args_iter = args.into_iter();
+        while let Some(arg) = args_iter.next() {
+            if FLAG_OPTIONS.contains(&arg.as_str()) {
+                flags.insert(arg);
+            } else if arg.starts_with(OPTION_PREFIX) {
+                // Every other option is followed by its value
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| RollingHashError::new(HELP_USAGE))?;
+                options.insert(arg, value);
+            } else {
+                positional_args.push(arg);
+            }
+        }
+        Ok(RdiffOptions {
+            args: positional_args,
+            options,
+            flags,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&String> {
+        self.args.get(index)
+    }
+
+    pub fn get_option(&self, name: &str) -> Option<&String> {
+        self.options.get(name)
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    pub fn get_usize_option(&self, name: &str) -> Result<Option<usize>, RollingHashError> {
+        match self.get_option(name) {
+            Some(value) => {
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|_| RollingHashError::new(INVALID_OPTION_VALUE))?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn validate(&self, valid_options: &[&str]) -> Result<(), RollingHashError> {
+        // Any option not supported by the command is a usage error
+        if self
+            .options
+            .keys()
+            .chain(self.flags.iter())
+            .all(|name| valid_options.contains(&name.as_str()))
+        {
+            Ok(())
+        } else {
+            Err(RollingHashError::new(HELP_USAGE))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
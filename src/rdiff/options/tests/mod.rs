@@ -0,0 +1,74 @@
+use crate::rdiff::{
+    constants::{BLOCK_SIZE_OPTION, COMPRESS_OPTION},
+    error::{
+        messages::{HELP_USAGE, INVALID_OPTION_VALUE},
+        RollingHashError,
+    },
+};
+
+use super::RdiffOptions;
+
+#[test]
+fn test_rdiff_options_new_case1() {
+    let args: Vec<String> = vec![
+        "rolling_hash_diff".to_string(),
+        "signature".to_string(),
+        BLOCK_SIZE_OPTION.to_string(),
+        "256".to_string(),
+        "file.txt".to_string(),
+        "file.txt.sig".to_string(),
+    ];
+    let options = RdiffOptions::new(args).unwrap();
+    assert_eq!(options.len(), 4);
+    assert_eq!(options.get(1).unwrap(), "signature");
+    assert_eq!(options.get(3).unwrap(), "file.txt.sig");
+    assert_eq!(
+        options.get_usize_option(BLOCK_SIZE_OPTION).unwrap(),
+        Some(256)
+    );
+    assert_eq!(options.validate(&[BLOCK_SIZE_OPTION]), Ok(()));
+    assert_eq!(
+        options.validate(&[]),
+        Err(RollingHashError::new(HELP_USAGE))
+    );
+}
+
+#[test]
+fn test_rdiff_options_new_error_missing_value_case1() {
+    let args: Vec<String> = vec![
+        "rolling_hash_diff".to_string(),
+        "signature".to_string(),
+        BLOCK_SIZE_OPTION.to_string(),
+    ];
+    let error = RdiffOptions::new(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(HELP_USAGE));
+}
+
+#[test]
+fn test_rdiff_options_get_usize_option_error_case1() {
+    let args: Vec<String> = vec![BLOCK_SIZE_OPTION.to_string(), "abc".to_string()];
+    let options = RdiffOptions::new(args).unwrap();
+    let error = options.get_usize_option(BLOCK_SIZE_OPTION).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_OPTION_VALUE));
+}
+
+#[test]
+fn test_rdiff_options_has_flag_case1() {
+    let args: Vec<String> = vec![
+        "rolling_hash_diff".to_string(),
+        "delta".to_string(),
+        COMPRESS_OPTION.to_string(),
+        "file.txt.sig".to_string(),
+        "file.v1.txt".to_string(),
+        "file.v1.txt.delta".to_string(),
+    ];
+    let options = RdiffOptions::new(args).unwrap();
+    assert_eq!(options.len(), 5);
+    assert_eq!(options.get(2).unwrap(), "file.txt.sig");
+    assert!(options.has_flag(COMPRESS_OPTION));
+    assert_eq!(options.validate(&[COMPRESS_OPTION]), Ok(()));
+    assert_eq!(
+        options.validate(&[BLOCK_SIZE_OPTION]),
+        Err(RollingHashError::new(HELP_USAGE))
+    );
+}
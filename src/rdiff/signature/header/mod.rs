@@ -0,0 +1,231 @@
+use std::io::Read;
+
+use bincode::deserialize_from;
+use serde::{Deserialize, Serialize};
+
+use crate::rdiff::{
+    chunk::{
+        iterator::{fastcdc::FastCdcParameters, BufferedRdiffChunkIterator},
+        RdiffDigest,
+    },
+    constants::{
+        SIGNATURE_MAGIC, SIGNATURE_VERSION, SIGNATURE_VERSION_1, SIGNATURE_VERSION_2,
+        SIGNATURE_VERSION_3,
+    },
+    error::{
+        messages::{
+            INVALID_SIGNATURE_FILE, SIGNATURE_HASH_MISMATCH, UNSUPPORTED_SIGNATURE_VERSION,
+        },
+        RollingHashError,
+    },
+    hash::{
+        strong::{new_strong_hash_ptr, StrongHashPtr},
+        weak::{new_weak_hash_ptr, WeakHashPtr},
+    },
+};
+
+// Basis file fingerprint, it identifies the file a signature was created from.
+// The digest is computed with the signature strong hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BasisFingerprint {
+    size: usize,
+    digest: RdiffDigest,
+}
+
+impl BasisFingerprint {
+    pub fn new(size: usize, digest: RdiffDigest) -> BasisFingerprint {
+        BasisFingerprint { size, digest }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get_digest(&self) -> &RdiffDigest {
+        &self.digest
+    }
+}
+
+// Signature file header, it is stored before the signature chunk table
+// so a signature file can be identified and validated before reading it
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignatureHeader {
+    magic: u32,
+    version: u32,
+    weak_hash_id: u32,
+    strong_hash_id: u32,
+    digest_size: usize,
+    chunk_size: usize,
+    basis: Option<BasisFingerprint>,
+    fastcdc_parameters: Option<FastCdcParameters>,
+}
+
+impl SignatureHeader {
+    pub fn new(
+        weak_hash_ptr: &WeakHashPtr,
+        strong_hash_ptr: &StrongHashPtr,
+        chunk_size: usize,
+        basis: BasisFingerprint,
+    ) -> SignatureHeader {
+        SignatureHeader {
+            magic: SIGNATURE_MAGIC,
+            version: SIGNATURE_VERSION,
+            weak_hash_id: weak_hash_ptr.get_id(),
+            strong_hash_id: strong_hash_ptr.get_id(),
+            digest_size: strong_hash_ptr.get_digest_size(),
+            chunk_size,
+            basis: Some(basis),
+            fastcdc_parameters: None,
+        }
+    }
+
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn get_weak_hash_id(&self) -> u32 {
+        self.weak_hash_id
+    }
+
+    pub fn get_strong_hash_id(&self) -> u32 {
+        self.strong_hash_id
+    }
+
+    pub fn get_digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    pub fn get_chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    // Chunk digests may be truncated to a prefix of the strong hash digest
+    pub fn set_digest_size(&mut self, digest_size: usize) {
+        self.digest_size = digest_size;
+    }
+
+    // Header of signature files that do not record the basis file fingerprint
+    pub fn new_without_basis(
+        weak_hash_ptr: &WeakHashPtr,
+        strong_hash_ptr: &StrongHashPtr,
+        chunk_size: usize,
+    ) -> SignatureHeader {
+        SignatureHeader {
+            magic: SIGNATURE_MAGIC,
+            version: SIGNATURE_VERSION,
+            weak_hash_id: weak_hash_ptr.get_id(),
+            strong_hash_id: strong_hash_ptr.get_id(),
+            digest_size: strong_hash_ptr.get_digest_size(),
+            chunk_size,
+            basis: None,
+            fastcdc_parameters: None,
+        }
+    }
+
+    // Version 1 signature files have no basis fingerprint
+    pub fn get_basis(&self) -> Option<&BasisFingerprint> {
+        self.basis.as_ref()
+    }
+
+    // Signature files created before version 3 have fixed size chunks
+    pub fn get_fastcdc_parameters(&self) -> Option<&FastCdcParameters> {
+        self.fastcdc_parameters.as_ref()
+    }
+
+    pub fn set_fastcdc_parameters(&mut self, fastcdc_parameters: Option<FastCdcParameters>) {
+        self.fastcdc_parameters = fastcdc_parameters;
+    }
+
+    pub fn get_weak_hash_ptr(&self) -> Result<WeakHashPtr, RollingHashError> {
+        new_weak_hash_ptr(self.weak_hash_id)
+    }
+
+    pub fn get_strong_hash_ptr(&self) -> Result<StrongHashPtr, RollingHashError> {
+        new_strong_hash_ptr(self.strong_hash_id)
+    }
+
+    pub fn validate(&self) -> Result<(), RollingHashError> {
+        // Check it is a signature file
+        if self.magic != SIGNATURE_MAGIC {
+            return Err(RollingHashError::new(INVALID_SIGNATURE_FILE));
+        }
+        // Check signature file version is supported
+        if !(SIGNATURE_VERSION_1..=SIGNATURE_VERSION_3).contains(&self.version) {
+            return Err(RollingHashError::new(UNSUPPORTED_SIGNATURE_VERSION));
+        }
+        // Check hash functions are known and digest size is at most the strong hash one
+        self.get_weak_hash_ptr()?;
+        let strong_hash_ptr = self.get_strong_hash_ptr()?;
+        if self.digest_size == 0 || self.digest_size > strong_hash_ptr.get_digest_size() {
+            return Err(RollingHashError::new(INVALID_SIGNATURE_FILE));
+        }
+        // Check chunk size and content defined chunk sizes, if any
+        if let Some(fastcdc_parameters) = self.fastcdc_parameters.as_ref() {
+            fastcdc_parameters
+                .validate()
+                .map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))?;
+        }
+        BufferedRdiffChunkIterator::validate_chunk_size(self.chunk_size)
+            .map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<SignatureHeader, RollingHashError> {
+        // Get magic number and version, they are checked before reading the rest
+        let (magic, version): (u32, u32) = SignatureHeader::read_field(reader)?;
+        if magic != SIGNATURE_MAGIC {
+            return Err(RollingHashError::new(INVALID_SIGNATURE_FILE));
+        }
+        if !(SIGNATURE_VERSION_1..=SIGNATURE_VERSION_3).contains(&version) {
+            return Err(RollingHashError::new(UNSUPPORTED_SIGNATURE_VERSION));
+        }
+        // Get hash functions and chunk size
+        let (weak_hash_id, strong_hash_id, digest_size, chunk_size): (u32, u32, usize, usize) =
+            SignatureHeader::read_field(reader)?;
+        // Get basis fingerprint
+        let basis = if version >= SIGNATURE_VERSION_2 {
+            SignatureHeader::read_field(reader)?
+        } else {
+            None
+        };
+        // Get content defined chunking
+        let fastcdc_parameters = if version >= SIGNATURE_VERSION_3 {
+            SignatureHeader::read_field(reader)?
+        } else {
+            None
+        };
+        let header = SignatureHeader {
+            magic,
+            version,
+            weak_hash_id,
+            strong_hash_id,
+            digest_size,
+            chunk_size,
+            basis,
+            fastcdc_parameters,
+        };
+        header.validate()?;
+        Ok(header)
+    }
+
+    fn read_field<R: Read, T: for<'de> Deserialize<'de>>(
+        reader: &mut R,
+    ) -> Result<T, RollingHashError> {
+        deserialize_from(reader).map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))
+    }
+
+    pub fn validate_hashes(
+        &self,
+        weak_hash_ptr: &WeakHashPtr,
+        strong_hash_ptr: &StrongHashPtr,
+    ) -> Result<(), RollingHashError> {
+        if weak_hash_ptr.get_id() != self.weak_hash_id
+            || strong_hash_ptr.get_id() != self.strong_hash_id
+        {
+            return Err(RollingHashError::new(SIGNATURE_HASH_MISMATCH));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+use std::io::Cursor;
+
+use bincode::{deserialize, serialize};
+
+use crate::rdiff::{
+    chunk::iterator::fastcdc::FastCdcParameters,
+    constants::{
+        BLOCK_SIZE, SIGNATURE_MAGIC, SIGNATURE_VERSION, SIGNATURE_VERSION_1, SIGNATURE_VERSION_2,
+    },
+    error::{
+        messages::{
+            INVALID_SIGNATURE_FILE, SIGNATURE_HASH_MISMATCH, UNKNOWN_STRONG_HASH,
+            UNKNOWN_WEAK_HASH, UNSUPPORTED_SIGNATURE_VERSION,
+        },
+        RollingHashError,
+    },
+    hash::{
+        strong::{rdiff_sha1::RdiffSha1, StrongHashPtr},
+        weak::{rdiff_addler::RdiffAddler, WeakHashPtr},
+    },
+};
+
+use super::{BasisFingerprint, SignatureHeader};
+
+fn new_header() -> SignatureHeader {
+    let weak_hash_ptr: WeakHashPtr = RdiffAddler::new_ptr();
+    let strong_hash_ptr: StrongHashPtr = RdiffSha1::new_ptr();
+    let basis = BasisFingerprint::new(3 * BLOCK_SIZE, vec![1; 20]);
+    SignatureHeader::new(&weak_hash_ptr, &strong_hash_ptr, BLOCK_SIZE, basis)
+}
+
+#[test]
+fn test_signature_header_new_case1() {
+    let header = new_header();
+    let expected_header = SignatureHeader {
+        magic: SIGNATURE_MAGIC,
+        version: SIGNATURE_VERSION,
+        weak_hash_id: 1,
+        strong_hash_id: 1,
+        digest_size: 20,
+        chunk_size: BLOCK_SIZE,
+        basis: Some(BasisFingerprint::new(3 * BLOCK_SIZE, vec![1; 20])),
+        fastcdc_parameters: None,
+    };
+    assert_eq!(header, expected_header);
+    assert_eq!(header.validate(), Ok(()));
+}
+
+#[test]
+fn test_signature_header_validate_error_case1() {
+    let mut header = new_header();
+    header.magic = 0;
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    header.version = SIGNATURE_VERSION + 1;
+    let expected_error = RollingHashError::new(UNSUPPORTED_SIGNATURE_VERSION);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    header.weak_hash_id = 0;
+    let expected_error = RollingHashError::new(UNKNOWN_WEAK_HASH);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    header.strong_hash_id = 0;
+    let expected_error = RollingHashError::new(UNKNOWN_STRONG_HASH);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    // Digests can be truncated but not empty nor longer than the strong hash ones
+    let mut header = new_header();
+    header.digest_size = 8;
+    assert_eq!(header.validate(), Ok(()));
+
+    let mut header = new_header();
+    header.digest_size = 0;
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    header.digest_size = 21;
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    header.chunk_size = 0;
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    assert_eq!(header.validate(), Err(expected_error));
+
+    let mut header = new_header();
+    // Invalid parameters can only come from a signature file
+    let fastcdc_parameters: FastCdcParameters =
+        deserialize(&serialize(&(0_usize, BLOCK_SIZE, 4 * BLOCK_SIZE)).unwrap()).unwrap();
+    header.fastcdc_parameters = Some(fastcdc_parameters);
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    assert_eq!(header.validate(), Err(expected_error));
+}
+
+#[test]
+fn test_signature_header_validate_hashes_case1() {
+    let weak_hash_ptr: WeakHashPtr = RdiffAddler::new_ptr();
+    let strong_hash_ptr: StrongHashPtr = RdiffSha1::new_ptr();
+    let mut header = new_header();
+    assert_eq!(
+        header.validate_hashes(&weak_hash_ptr, &strong_hash_ptr),
+        Ok(())
+    );
+
+    header.strong_hash_id = 2;
+    let expected_error = RollingHashError::new(SIGNATURE_HASH_MISMATCH);
+    assert_eq!(
+        header.validate_hashes(&weak_hash_ptr, &strong_hash_ptr),
+        Err(expected_error)
+    );
+}
+
+#[test]
+fn test_signature_header_read_from_case1() {
+    let header = new_header();
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = SignatureHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header, header);
+    assert_eq!(
+        computed_header.get_basis(),
+        Some(&BasisFingerprint::new(3 * BLOCK_SIZE, vec![1; 20]))
+    );
+}
+
+#[test]
+fn test_signature_header_read_from_case2() {
+    // Content defined chunk sizes are read back
+    let mut header = new_header();
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(BLOCK_SIZE).unwrap();
+    header.set_fastcdc_parameters(Some(fastcdc_parameters));
+    let data = serialize(&header).unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = SignatureHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header, header);
+    assert_eq!(
+        computed_header.get_fastcdc_parameters(),
+        Some(&fastcdc_parameters)
+    );
+}
+
+#[test]
+fn test_signature_header_read_from_version_2_case1() {
+    // Version 2 signature headers have no content defined chunking
+    let basis = Some(BasisFingerprint::new(3 * BLOCK_SIZE, vec![1; 20]));
+    let data = serialize(&(
+        (SIGNATURE_MAGIC, SIGNATURE_VERSION_2),
+        (1_u32, 1_u32, 20_usize, BLOCK_SIZE),
+        basis,
+    ))
+    .unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = SignatureHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header.get_version(), SIGNATURE_VERSION_2);
+    assert!(computed_header.get_basis().is_some());
+    assert_eq!(computed_header.get_fastcdc_parameters(), None);
+}
+
+#[test]
+fn test_signature_header_read_from_version_1_case1() {
+    // Version 1 signature headers have no basis fingerprint
+    let data = serialize(&(
+        (SIGNATURE_MAGIC, SIGNATURE_VERSION_1),
+        (1_u32, 1_u32, 20_usize, BLOCK_SIZE),
+    ))
+    .unwrap();
+    let mut reader = Cursor::new(data);
+    let computed_header = SignatureHeader::read_from(&mut reader).unwrap();
+    assert_eq!(computed_header.get_version(), SIGNATURE_VERSION_1);
+    assert_eq!(computed_header.get_chunk_size(), BLOCK_SIZE);
+    assert_eq!(computed_header.get_basis(), None);
+}
+
+#[test]
+fn test_signature_header_read_from_error_case1() {
+    let mut header = new_header();
+    header.magic = 0;
+    let mut reader = Cursor::new(serialize(&header).unwrap());
+    let error = SignatureHeader::read_from(&mut reader).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+
+    let mut header = new_header();
+    header.version = SIGNATURE_VERSION + 1;
+    let mut reader = Cursor::new(serialize(&header).unwrap());
+    let error = SignatureHeader::read_from(&mut reader).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_SIGNATURE_VERSION));
+}
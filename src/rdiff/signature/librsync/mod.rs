@@ -0,0 +1,172 @@
+use std::io::{ErrorKind, Read, Write};
+
+use crate::rdiff::{
+    chunk::{RdiffChecksum, RdiffChunkDigest, RdiffChunkTable},
+    constants::{
+        LIBRSYNC_BLAKE2_SIG_MAGIC, LIBRSYNC_MD4_SIG_MAGIC, LIBRSYNC_RK_BLAKE2_SIG_MAGIC,
+        LIBRSYNC_RK_MD4_SIG_MAGIC,
+    },
+    error::{
+        messages::{FIXED_SIZE_CHUNKS_REQUIRED, INVALID_SIGNATURE_FILE, UNSUPPORTED_LIBRSYNC_HASH},
+        RollingHashError,
+    },
+    hash::{
+        strong::{
+            rdiff_blake2::{RdiffBlake2, RDIFF_BLAKE2_ID},
+            rdiff_md4::{RdiffMd4, RDIFF_MD4_ID},
+            StrongHashPtr,
+        },
+        weak::{
+            rdiff_rabinkarp::{RdiffRabinKarp, RDIFF_RABINKARP_ID},
+            rdiff_rollsum::{RdiffRollsum, RDIFF_ROLLSUM_ID},
+            WeakHashPtr,
+        },
+    },
+};
+
+use super::{header::SignatureHeader, Signature};
+
+// librsync signature file format, all numbers are big endian:
+// magic number, block length, strong sum length and then
+// the weak sum and the strong sum of every block in file order.
+// Basis file size is not recorded, so the last block size is unknown
+impl Signature {
+    pub fn is_librsync_signature(data: &[u8]) -> bool {
+        data.len() >= 4
+            && [
+                LIBRSYNC_MD4_SIG_MAGIC,
+                LIBRSYNC_BLAKE2_SIG_MAGIC,
+                LIBRSYNC_RK_MD4_SIG_MAGIC,
+                LIBRSYNC_RK_BLAKE2_SIG_MAGIC,
+            ]
+            .iter()
+            .any(|magic| data[..4] == magic.to_be_bytes())
+    }
+
+    // Magic number of librsync signatures with the given hash functions, only rollsum
+    // and Rabin-Karp weak hashes and MD4 and BLAKE2 strong hashes are supported
+    pub fn get_librsync_signature_magic(
+        weak_hash_id: u32,
+        strong_hash_id: u32,
+    ) -> Result<u32, RollingHashError> {
+        match (weak_hash_id, strong_hash_id) {
+            (RDIFF_ROLLSUM_ID, RDIFF_MD4_ID) => Ok(LIBRSYNC_MD4_SIG_MAGIC),
+            (RDIFF_ROLLSUM_ID, RDIFF_BLAKE2_ID) => Ok(LIBRSYNC_BLAKE2_SIG_MAGIC),
+            (RDIFF_RABINKARP_ID, RDIFF_MD4_ID) => Ok(LIBRSYNC_RK_MD4_SIG_MAGIC),
+            (RDIFF_RABINKARP_ID, RDIFF_BLAKE2_ID) => Ok(LIBRSYNC_RK_BLAKE2_SIG_MAGIC),
+            _ => Err(RollingHashError::new(UNSUPPORTED_LIBRSYNC_HASH)),
+        }
+    }
+
+    pub fn write_librsync_signature<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), RollingHashError> {
+        // Get magic number from hash functions
+        let header = self.get_header();
+        let magic = Signature::get_librsync_signature_magic(
+            header.get_weak_hash_id(),
+            header.get_strong_hash_id(),
+        )?;
+        // librsync blocks have a fixed size
+        if header.get_fastcdc_parameters().is_some() {
+            return Err(RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+        }
+        // Get chunks in file order
+        let mut chunks: Vec<(RdiffChecksum, &RdiffChunkDigest)> = self
+            .rdiff_chunk_table
+            .chunk_table
+            .iter()
+            .flat_map(|(checksum, chunk_digest_list)| {
+                chunk_digest_list
+                    .iter()
+                    .map(move |chunk_digest| (*checksum, chunk_digest))
+            })
+            .collect();
+        chunks.sort_by_key(|(_, chunk_digest)| chunk_digest.index);
+        // Write header and chunk sums
+        let mut data: Vec<u8> = Vec::new();
+        data.extend_from_slice(&magic.to_be_bytes());
+        data.extend_from_slice(&(header.get_chunk_size() as u32).to_be_bytes());
+        data.extend_from_slice(&(header.get_digest_size() as u32).to_be_bytes());
+        writer
+            .write_all(&data)
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        for (checksum, chunk_digest) in chunks {
+            writer
+                .write_all(&checksum.to_be_bytes())
+                .and_then(|_| writer.write_all(&chunk_digest.digest))
+                .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        }
+        Ok(())
+    }
+
+    pub fn read_librsync_signature<R: Read>(reader: &mut R) -> Result<Signature, RollingHashError> {
+        // Get magic number, block length and strong sum length
+        let mut data = [0_u8; 12];
+        if !Signature::read_librsync_field(reader, &mut data)? {
+            return Err(RollingHashError::new(INVALID_SIGNATURE_FILE));
+        }
+        let magic = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let chunk_size = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        let digest_size = u32::from_be_bytes([data[8], data[9], data[10], data[11]]) as usize;
+        // Get hash functions from magic number
+        let (weak_hash_ptr, strong_hash_ptr): (WeakHashPtr, StrongHashPtr) = match magic {
+            LIBRSYNC_MD4_SIG_MAGIC => (RdiffRollsum::new_ptr(), RdiffMd4::new_ptr()),
+            LIBRSYNC_BLAKE2_SIG_MAGIC => (RdiffRollsum::new_ptr(), RdiffBlake2::new_ptr()),
+            LIBRSYNC_RK_MD4_SIG_MAGIC => (RdiffRabinKarp::new_ptr(), RdiffMd4::new_ptr()),
+            LIBRSYNC_RK_BLAKE2_SIG_MAGIC => (RdiffRabinKarp::new_ptr(), RdiffBlake2::new_ptr()),
+            _ => return Err(RollingHashError::new(INVALID_SIGNATURE_FILE)),
+        };
+        // Strong sums may be truncated, their length is checked with the header
+        let mut header =
+            SignatureHeader::new_without_basis(&weak_hash_ptr, &strong_hash_ptr, chunk_size);
+        header.set_digest_size(digest_size);
+        header.validate()?;
+        // Get chunk sums until the end of the file
+        let mut rdiff_chunk_table = RdiffChunkTable::new();
+        let mut index: u32 = 0;
+        let mut data: Vec<u8> = vec![0; 4 + digest_size];
+        while Signature::read_librsync_field(reader, &mut data)? {
+            let checksum = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+            index += 1;
+            // Last block size is unknown, it is recorded as a whole block
+            let rdiff_chunk_digest = RdiffChunkDigest {
+                index,
+                length: chunk_size,
+                digest: data[4..].to_vec(),
+            };
+            rdiff_chunk_table
+                .chunk_table
+                .entry(checksum)
+                .or_default()
+                .push(rdiff_chunk_digest);
+        }
+        Ok(Signature {
+            header,
+            rdiff_chunk_table,
+            last_chunk_size: 0,
+        })
+    }
+
+    fn read_librsync_field<R: Read>(
+        reader: &mut R,
+        buffer: &mut [u8],
+    ) -> Result<bool, RollingHashError> {
+        // The end of the file is only valid before a field
+        let mut size = 0;
+        while size < buffer.len() {
+            match reader.read(&mut buffer[size..]) {
+                Ok(0) if size == 0 => return Ok(false),
+                Ok(0) => return Err(RollingHashError::new(INVALID_SIGNATURE_FILE)),
+                Ok(read_size) => size += read_size,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(RollingHashError::from(Box::new(e))),
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,195 @@
+use std::{fs, io::Cursor};
+
+use crate::rdiff::{
+    constants::{LIBRSYNC_BLAKE2_SIG_MAGIC, LIBRSYNC_MD4_SIG_MAGIC, LIBRSYNC_RK_BLAKE2_SIG_MAGIC},
+    error::{
+        messages::{INVALID_SIGNATURE_FILE, UNSUPPORTED_LIBRSYNC_HASH},
+        RollingHashError,
+    },
+    hash::{
+        strong::{
+            rdiff_blake2::RdiffBlake2, rdiff_md4::RdiffMd4, rdiff_sha1::RdiffSha1, StrongHash,
+        },
+        weak::{
+            rdiff_addler::RdiffAddler, rdiff_rabinkarp::RdiffRabinKarp,
+            rdiff_rollsum::RdiffRollsum, WeakHash,
+        },
+    },
+    util::now_as_millis,
+};
+
+use super::Signature;
+
+#[test]
+fn test_signature_write_librsync_signature_case1() {
+    // Create test file with two chunks
+    let file_name = format!(
+        "resources/test_librsync_signature_case1.{}.txt",
+        now_as_millis()
+    );
+    fs::write(file_name.as_str(), b"abcdefg").unwrap();
+    let signature = Signature::create_signature_with_chunk_size(
+        file_name.as_str(),
+        4,
+        RdiffRollsum::new_ptr(),
+        RdiffBlake2::new_ptr(),
+    )
+    .unwrap();
+
+    // Write signature in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    signature.write_librsync_signature(&mut data).unwrap();
+
+    // Verify computed values
+    let mut expected_data: Vec<u8> = Vec::new();
+    expected_data.extend_from_slice(&LIBRSYNC_BLAKE2_SIG_MAGIC.to_be_bytes());
+    expected_data.extend_from_slice(&4_u32.to_be_bytes());
+    expected_data.extend_from_slice(&32_u32.to_be_bytes());
+    for chunk in [&b"abcd"[..], &b"efg"[..]] {
+        expected_data.extend_from_slice(&RdiffRollsum::new().checksum(chunk).to_be_bytes());
+        expected_data.extend_from_slice(&RdiffBlake2::new().digest(chunk));
+    }
+    assert_eq!(data, expected_data);
+    assert!(Signature::is_librsync_signature(&data));
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn test_signature_read_librsync_signature_case1() {
+    // Create signature of a file with many chunks
+    let file_name = "resources/poem.txt";
+    let signature = Signature::create_signature_with_chunk_size(
+        file_name,
+        16,
+        RdiffRollsum::new_ptr(),
+        RdiffMd4::new_ptr(),
+    )
+    .unwrap();
+
+    // Write and read signature in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    signature.write_librsync_signature(&mut data).unwrap();
+    assert_eq!(data[..4], LIBRSYNC_MD4_SIG_MAGIC.to_be_bytes());
+    let mut reader = Cursor::new(data);
+    let computed_signature = Signature::read_librsync_signature(&mut reader).unwrap();
+
+    // Verify computed values, basis fingerprint and last chunk size are not recorded
+    assert_eq!(computed_signature.get_chunk_size(), 16);
+    assert_eq!(computed_signature.get_last_chunk_size(), 0);
+    assert_eq!(computed_signature.get_header().get_basis(), None);
+    assert_eq!(
+        computed_signature.get_header().get_strong_hash_id(),
+        RdiffMd4::new().get_id()
+    );
+    assert_eq!(
+        computed_signature.get_rdiff_chunk_table(),
+        signature.get_rdiff_chunk_table()
+    );
+}
+
+#[test]
+fn test_signature_read_librsync_signature_case2() {
+    // Create signature with truncated strong sums, as rdiff signature -S 8
+    let mut signature = Signature::create_signature_with_chunk_size(
+        "resources/poem.txt",
+        16,
+        RdiffRollsum::new_ptr(),
+        RdiffBlake2::new_ptr(),
+    )
+    .unwrap();
+    signature.truncate_digests(8).unwrap();
+
+    // Write and read signature in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    signature.write_librsync_signature(&mut data).unwrap();
+    assert_eq!(data[8..12], 8_u32.to_be_bytes());
+    let mut reader = Cursor::new(data);
+    let computed_signature = Signature::read_librsync_signature(&mut reader).unwrap();
+
+    // Verify computed values
+    assert_eq!(computed_signature.get_header().get_digest_size(), 8);
+    assert_eq!(
+        computed_signature.get_rdiff_chunk_table(),
+        signature.get_rdiff_chunk_table()
+    );
+}
+
+#[test]
+fn test_signature_read_librsync_signature_case3() {
+    // Create signature with Rabin-Karp weak sums, librsync default ones
+    let signature = Signature::create_signature_with_chunk_size(
+        "resources/poem.txt",
+        16,
+        RdiffRabinKarp::new_ptr(),
+        RdiffBlake2::new_ptr(),
+    )
+    .unwrap();
+
+    // Write and read signature in librsync format
+    let mut data: Vec<u8> = Vec::new();
+    signature.write_librsync_signature(&mut data).unwrap();
+    assert_eq!(data[..4], LIBRSYNC_RK_BLAKE2_SIG_MAGIC.to_be_bytes());
+    let file_data = fs::read("resources/poem.txt").unwrap();
+    assert_eq!(
+        data[12..16],
+        RdiffRabinKarp::new()
+            .checksum(&file_data[..16])
+            .to_be_bytes()
+    );
+    assert!(Signature::is_librsync_signature(&data));
+    let mut reader = Cursor::new(data);
+    let computed_signature = Signature::read_librsync_signature(&mut reader).unwrap();
+
+    // Verify computed values
+    assert_eq!(
+        computed_signature.get_header().get_weak_hash_id(),
+        RdiffRabinKarp::new().get_id()
+    );
+    assert_eq!(
+        computed_signature.get_rdiff_chunk_table(),
+        signature.get_rdiff_chunk_table()
+    );
+}
+
+#[test]
+fn test_signature_write_librsync_signature_error_case1() {
+    let file_name = "resources/poem.txt";
+    let signature =
+        Signature::create_signature(file_name, RdiffAddler::new_ptr(), RdiffSha1::new_ptr())
+            .unwrap();
+    let mut data: Vec<u8> = Vec::new();
+    let error = signature.write_librsync_signature(&mut data).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_LIBRSYNC_HASH));
+}
+
+#[test]
+fn test_signature_read_librsync_signature_error_case1() {
+    let mut header: Vec<u8> = Vec::new();
+    header.extend_from_slice(&LIBRSYNC_BLAKE2_SIG_MAGIC.to_be_bytes());
+    header.extend_from_slice(&4_u32.to_be_bytes());
+
+    // Empty strong sums
+    let mut data = header.clone();
+    data.extend_from_slice(&0_u32.to_be_bytes());
+    let error = Signature::read_librsync_signature(&mut Cursor::new(data)).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+
+    // Strong sums bigger than the strong hash digest
+    let mut data = header.clone();
+    data.extend_from_slice(&64_u32.to_be_bytes());
+    let error = Signature::read_librsync_signature(&mut Cursor::new(data)).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+
+    // Incomplete chunk sums
+    let mut data = header.clone();
+    data.extend_from_slice(&32_u32.to_be_bytes());
+    data.extend_from_slice(&[0; 10]);
+    let error = Signature::read_librsync_signature(&mut Cursor::new(data)).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+
+    // Incomplete header
+    let error = Signature::read_librsync_signature(&mut Cursor::new(header)).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+}
@@ -1,7 +1,8 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
     fs::File,
-    io::{BufReader, BufWriter},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
 };
 
 use bincode::{deserialize_from, serialize_into};
@@ -9,46 +10,79 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     chunk::{
-        iterator::{BufferedRdiffChunkIterator, RdiffChunkIterator},
-        RdiffChunkDigest, RdiffChunkTable,
+        iterator::{
+            fastcdc::{FastCdcChunkIterator, FastCdcParameters},
+            BufferedRdiffChunkIterator, RdiffChunkIterator,
+        },
+        RdiffChecksum, RdiffChunkDigest, RdiffChunkTable, RdiffDigest,
+    },
+    constants::SIGNATURE_VERSION_3,
+    error::{
+        messages::{INVALID_DIGEST_SIZE, INVALID_SIGNATURE_FILE},
+        RollingHashError,
     },
-    error::RollingHashError,
     hash::{strong::StrongHashPtr, weak::WeakHashPtr},
     io::RdiffFile,
 };
 
+use self::header::{BasisFingerprint, SignatureHeader};
+
+pub mod header;
+pub mod librsync;
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Signature {
+    header: SignatureHeader,
     rdiff_chunk_table: RdiffChunkTable,
-    chunk_size: usize,
     last_chunk_size: usize,
 }
 
 impl Signature {
     pub fn new(
+        header: SignatureHeader,
         rdiff_chunk_table: RdiffChunkTable,
-        chunk_size: usize,
         last_chunk_size: usize,
     ) -> Signature {
         Signature {
+            header,
             rdiff_chunk_table,
-            chunk_size,
             last_chunk_size,
         }
     }
 
+    pub fn get_header(&self) -> &SignatureHeader {
+        &self.header
+    }
+
     pub fn get_rdiff_chunk_table(&self) -> &RdiffChunkTable {
         &self.rdiff_chunk_table
     }
 
     pub fn get_chunk_size(&self) -> usize {
-        self.chunk_size
+        self.header.get_chunk_size()
     }
 
+    // Last chunk size is zero when it is unknown, as in librsync signature files
     pub fn get_last_chunk_size(&self) -> usize {
         self.last_chunk_size
     }
 
+    // Keep only the first digest size bytes of every chunk digest.
+    // Shorter digests make smaller signatures, but a changed chunk with the same weak
+    // checksum is taken as unchanged with a probability of 2^-(8 * digest size)
+    pub fn truncate_digests(&mut self, digest_size: usize) -> Result<(), RollingHashError> {
+        if digest_size == 0 || digest_size > self.header.get_digest_size() {
+            return Err(RollingHashError::new(INVALID_DIGEST_SIZE));
+        }
+        self.rdiff_chunk_table
+            .chunk_table
+            .values_mut()
+            .flatten()
+            .for_each(|chunk_digest| chunk_digest.digest.truncate(digest_size));
+        self.header.set_digest_size(digest_size);
+        Ok(())
+    }
+
     pub fn create_signature_file(
         file_name: &str,
         signature_file_name: &str,
@@ -56,41 +90,276 @@ impl Signature {
         strong_hash_ptr: StrongHashPtr,
     ) -> Result<(), RollingHashError> {
         let signature = Signature::create_signature(file_name, weak_hash_ptr, strong_hash_ptr)?;
+        Signature::write_signature_file(&signature, signature_file_name)
+    }
 
-        // Write serialized signature to file
-        let signature_file = File::create(signature_file_name)
-            .or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
+    pub fn create_signature_file_with_chunk_size(
+        file_name: &str,
+        signature_file_name: &str,
+        chunk_size: usize,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<(), RollingHashError> {
+        let signature = Signature::create_signature_with_chunk_size(
+            file_name,
+            chunk_size,
+            weak_hash_ptr,
+            strong_hash_ptr,
+        )?;
+        Signature::write_signature_file(&signature, signature_file_name)
+    }
 
+    pub fn create_signature_file_with_fastcdc(
+        file_name: &str,
+        signature_file_name: &str,
+        fastcdc_parameters: FastCdcParameters,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<(), RollingHashError> {
+        let signature = Signature::create_signature_with_fastcdc(
+            file_name,
+            fastcdc_parameters,
+            weak_hash_ptr,
+            strong_hash_ptr,
+        )?;
+        Signature::write_signature_file(&signature, signature_file_name)
+    }
+
+    pub fn create_librsync_signature_file(
+        file_name: &str,
+        signature_file_name: &str,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<(), RollingHashError> {
+        let signature = Signature::create_signature(file_name, weak_hash_ptr, strong_hash_ptr)?;
+        Signature::write_librsync_signature_file(&signature, signature_file_name)
+    }
+
+    pub fn create_librsync_signature_file_with_chunk_size(
+        file_name: &str,
+        signature_file_name: &str,
+        chunk_size: usize,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<(), RollingHashError> {
+        let signature = Signature::create_signature_with_chunk_size(
+            file_name,
+            chunk_size,
+            weak_hash_ptr,
+            strong_hash_ptr,
+        )?;
+        Signature::write_librsync_signature_file(&signature, signature_file_name)
+    }
+
+    pub fn write_librsync_signature_file(
+        signature: &Signature,
+        signature_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Write signature to file in librsync format
+        let signature_file =
+            File::create(signature_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
         let mut sig_writer = BufWriter::new(signature_file);
-        serialize_into(&mut sig_writer, &signature)
-            .or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
+        signature.write_librsync_signature(&mut sig_writer)?;
+        sig_writer
+            .flush()
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
 
-        Ok(())
+    pub fn write_signature_file(
+        signature: &Signature,
+        signature_file_name: &str,
+    ) -> Result<(), RollingHashError> {
+        // Write serialized signature to file
+        let signature_file =
+            File::create(signature_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        let mut sig_writer = BufWriter::new(signature_file);
+        signature.write_signature(&mut sig_writer)?;
+        sig_writer
+            .flush()
+            .map_err(|e| RollingHashError::from(Box::new(e)))
+    }
+
+    pub fn write_signature<W: Write>(&self, writer: &mut W) -> Result<(), RollingHashError> {
+        // Write serialized signature
+        serialize_into(writer, self).map_err(|e| RollingHashError::from(Box::new(e)))
     }
 
     pub fn get_signature_from_file(
         signature_file_name: &str,
     ) -> Result<Signature, RollingHashError> {
         // Get signture file
-        let signature_file = File::open(signature_file_name)
-            .or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
-        let sig_reader = BufReader::new(signature_file);
-        // Get signature from file
-        let signature: Signature =
-            deserialize_from(sig_reader).or_else(|e| Err(RollingHashError::from(Box::new(e))))?;
-        Ok(signature)
+        let signature_file =
+            File::open(signature_file_name).map_err(|e| RollingHashError::from(Box::new(e)))?;
+        Signature::get_signature_from_reader(signature_file)
+    }
+
+    pub fn get_signature_from_reader<R: Read>(reader: R) -> Result<Signature, RollingHashError> {
+        let mut sig_reader = BufReader::new(reader);
+        // Signature files in librsync format are identified by their magic number
+        let buffer = sig_reader
+            .fill_buf()
+            .map_err(|e| RollingHashError::from(Box::new(e)))?;
+        if Signature::is_librsync_signature(buffer) {
+            return Signature::read_librsync_signature(&mut sig_reader);
+        }
+        // Get signature header from file and check it before reading the chunk table
+        let header = SignatureHeader::read_from(&mut sig_reader)?;
+        // Get signature chunk table and last chunk size from file
+        let (rdiff_chunk_table, last_chunk_size) = if header.get_version() < SIGNATURE_VERSION_3 {
+            Signature::read_fixed_size_chunk_table(&mut sig_reader, header.get_chunk_size())?
+        } else {
+            let rdiff_chunk_table: RdiffChunkTable = deserialize_from(&mut sig_reader)
+                .map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))?;
+            let last_chunk_size: usize = deserialize_from(&mut sig_reader)
+                .map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))?;
+            (rdiff_chunk_table, last_chunk_size)
+        };
+        Ok(Signature {
+            header,
+            rdiff_chunk_table,
+            last_chunk_size,
+        })
+    }
+
+    fn read_fixed_size_chunk_table<R: Read>(
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<(RdiffChunkTable, usize), RollingHashError> {
+        // Signature files created before version 3 do not record chunk lengths,
+        // every chunk is chunk size long but the last one
+        let chunk_table: HashMap<RdiffChecksum, Vec<(u32, RdiffDigest)>> =
+            deserialize_from(&mut *reader)
+                .map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))?;
+        let last_chunk_size: usize =
+            deserialize_from(reader).map_err(|_| RollingHashError::new(INVALID_SIGNATURE_FILE))?;
+        let last_index = chunk_table
+            .values()
+            .flatten()
+            .map(|(index, _)| *index)
+            .max()
+            .unwrap_or(0);
+        let mut rdiff_chunk_table = RdiffChunkTable::new();
+        for (checksum, chunk_digest_list) in chunk_table {
+            let chunk_digest_list = chunk_digest_list
+                .into_iter()
+                .map(|(index, digest)| RdiffChunkDigest {
+                    index,
+                    length: if index == last_index {
+                        last_chunk_size
+                    } else {
+                        chunk_size
+                    },
+                    digest,
+                })
+                .collect();
+            rdiff_chunk_table
+                .chunk_table
+                .insert(checksum, chunk_digest_list);
+        }
+        Ok((rdiff_chunk_table, last_chunk_size))
+    }
+
+    pub fn create_signature(
+        file_name: &str,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Get rdiff file
+        let rdiff_file = RdiffFile::new(file_name)?;
+        Signature::create_signature_from_rdiff_file(rdiff_file, weak_hash_ptr, strong_hash_ptr)
     }
 
-    fn create_signature(
+    pub fn create_signature_with_chunk_size(
         file_name: &str,
+        chunk_size: usize,
         weak_hash_ptr: WeakHashPtr,
         strong_hash_ptr: StrongHashPtr,
     ) -> Result<Signature, RollingHashError> {
         // Get rdiff file
         let rdiff_file = RdiffFile::new(file_name)?;
+        Signature::create_signature_from_rdiff_file_with_chunk_size(
+            rdiff_file,
+            chunk_size,
+            weak_hash_ptr,
+            strong_hash_ptr,
+        )
+    }
 
-        // Build chunk iterator
+    pub fn create_signature_with_fastcdc(
+        file_name: &str,
+        fastcdc_parameters: FastCdcParameters,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Get rdiff file
+        let rdiff_file = RdiffFile::new(file_name)?;
+        Signature::create_signature_from_rdiff_file_with_fastcdc(
+            rdiff_file,
+            fastcdc_parameters,
+            weak_hash_ptr,
+            strong_hash_ptr,
+        )
+    }
+
+    pub fn create_signature_from_bytes(
+        data: &[u8],
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Get rdiff file over memory buffer
+        let rdiff_file = RdiffFile::new_with_bytes("", data);
+        Signature::create_signature_from_rdiff_file(rdiff_file, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn create_signature_from_reader<R: Read>(
+        reader: R,
+        size: u64,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Get rdiff file over reader, its size is needed to compute chunk size
+        let rdiff_file = RdiffFile::new_with_reader("", reader, size);
+        Signature::create_signature_from_rdiff_file(rdiff_file, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn create_signature_from_rdiff_file(
+        rdiff_file: RdiffFile,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Build chunk iterator, chunk size is computed from file size
         let mut iterator = BufferedRdiffChunkIterator::new(rdiff_file);
+        Signature::create_signature_from_iterator(&mut iterator, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn create_signature_from_rdiff_file_with_chunk_size(
+        rdiff_file: RdiffFile,
+        chunk_size: usize,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Build chunk iterator with given chunk size
+        let mut iterator = BufferedRdiffChunkIterator::new_with_chunk_size(chunk_size, rdiff_file)?;
+        Signature::create_signature_from_iterator(&mut iterator, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    pub fn create_signature_from_rdiff_file_with_fastcdc(
+        rdiff_file: RdiffFile,
+        fastcdc_parameters: FastCdcParameters,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
+        // Build content defined chunk iterator with given chunk sizes
+        fastcdc_parameters.validate()?;
+        let mut iterator = FastCdcChunkIterator::new(fastcdc_parameters, rdiff_file);
+        Signature::create_signature_from_iterator(&mut iterator, weak_hash_ptr, strong_hash_ptr)
+    }
+
+    fn create_signature_from_iterator(
+        iterator: &mut dyn RdiffChunkIterator,
+        weak_hash_ptr: WeakHashPtr,
+        strong_hash_ptr: StrongHashPtr,
+    ) -> Result<Signature, RollingHashError> {
         // Init chunk table
         let mut rdiff_chunk_table = RdiffChunkTable::new();
         // Init chunk index
@@ -99,6 +368,9 @@ impl Signature {
         let mut last_chunk_size = 0;
         // Get chunk size
         let chunk_size = iterator.get_chunk_size();
+        // Init basis size and basis hasher for the basis fingerprint
+        let mut basis_size: usize = 0;
+        let mut basis_hasher = strong_hash_ptr.hasher();
         loop {
             // Get next chunk
             let rdiff_chunk_result = iterator.next_chunk()?;
@@ -106,6 +378,9 @@ impl Signature {
                 // If there is another chunk, process it
                 // Update last chunk size
                 last_chunk_size = chunk.len();
+                // Update basis fingerprint
+                basis_size += chunk.len();
+                basis_hasher.update(chunk.as_slice());
                 // Compute checksum using weak hash
                 let checksum = weak_hash_ptr.checksum(chunk.as_slice());
                 // Compute digest using strong hash
@@ -114,14 +389,15 @@ impl Signature {
                 // Check for checksum's list, if there is an entry with current checksum,
                 // get the list with all previous digests
                 // Otherwise create new entry for current checksum with empty list
-                let chunk_data = rdiff_chunk_table
-                    .chunk_table
-                    .entry(checksum)
-                    .or_insert(Vec::new());
+                let chunk_data = rdiff_chunk_table.chunk_table.entry(checksum).or_default();
                 // Update chunk index
                 index += 1;
                 // Get new chunk data from current index and current digest
-                let rdiff_chunk_digest = RdiffChunkDigest { index, digest };
+                let rdiff_chunk_digest = RdiffChunkDigest {
+                    index,
+                    length: chunk.len(),
+                    digest,
+                };
                 // Update checksum's list with new chunk data
                 chunk_data.push(rdiff_chunk_digest);
             } else {
@@ -130,9 +406,12 @@ impl Signature {
             }
         }
         // Create signature
+        let basis = BasisFingerprint::new(basis_size, basis_hasher.finalize());
+        let mut header = SignatureHeader::new(&weak_hash_ptr, &strong_hash_ptr, chunk_size, basis);
+        header.set_fastcdc_parameters(iterator.get_fastcdc_parameters().copied());
         let signature = Signature {
+            header,
             rdiff_chunk_table,
-            chunk_size,
             last_chunk_size,
         };
         Ok(signature)
@@ -143,8 +422,8 @@ impl Display for Signature {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "rdiff_chunk_table:{:?},\nchunk_size:{},\nlast_chunk_size:{}",
-            self.rdiff_chunk_table, self.chunk_size, self.last_chunk_size
+            "header:{:?},\nrdiff_chunk_table:{:?},\nlast_chunk_size:{}",
+            self.header, self.rdiff_chunk_table, self.last_chunk_size
         )
     }
 }
@@ -1,13 +1,21 @@
 use std::{
-    fmt::format,
+    collections::HashMap,
     fs::{self, File},
-    io::{BufWriter, Write},
-    time::SystemTime,
+    io::{BufWriter, Cursor, Write},
 };
 
+use bincode::serialize;
+
 use crate::rdiff::{
-    constants::BLOCK_SIZE,
+    chunk::{iterator::fastcdc::FastCdcParameters, RdiffChecksum, RdiffDigest},
+    constants::{BLOCK_SIZE, SIGNATURE_MAGIC, SIGNATURE_VERSION_2},
+    error::{
+        messages::{INVALID_DIGEST_SIZE, INVALID_SIGNATURE_FILE},
+        RollingHashError,
+    },
     hash::{strong::rdiff_sha1::RdiffSha1, weak::rdiff_addler::RdiffAddler},
+    io::RdiffFile,
+    signature::header::BasisFingerprint,
     util::now_as_millis,
 };
 
@@ -17,26 +25,19 @@ use super::Signature;
 fn test_rdiff_signature_create_signature_case1() {
     let file_name = format!("resources/test_signature_case1.{}.txt", now_as_millis());
 
-    let mut file_size = 0;
+    let file_size;
     {
         // Create test file
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
-        file_size = input_data.len() as usize;
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+        file_size = input_data.len();
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Get hash functions
@@ -62,20 +63,17 @@ fn test_rdiff_signature_create_signature_case1() {
 #[test]
 fn test_rdiff_signature_create_signature_case2() {
     let file_name = format!("resources/test_signature_case2.{}.txt", now_as_millis());
-    let mut file_size = 0;
+    let file_size;
     {
         // Create test file
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
-        file_size = input_data.len() as usize;
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+        file_size = input_data.len();
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
     // Get hash functions
@@ -111,19 +109,12 @@ fn test_rdiff_signature_create_signature_file_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -132,14 +123,13 @@ fn test_rdiff_signature_create_signature_file_case1() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Clean up verification
     fs::remove_file(file_name).unwrap();
@@ -159,13 +149,10 @@ fn test_rdiff_signature_create_signature_file_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -174,14 +161,13 @@ fn test_rdiff_signature_create_signature_file_case2() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Clean up verification
     fs::remove_file(file_name).unwrap();
@@ -199,19 +185,12 @@ fn test_rdiff_signature_get_signature_from_file_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -228,14 +207,13 @@ fn test_rdiff_signature_get_signature_from_file_case1() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get signature from file
     let computed_signature =
@@ -260,13 +238,10 @@ fn test_rdiff_signature_get_signature_from_file_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
@@ -283,14 +258,13 @@ fn test_rdiff_signature_get_signature_from_file_case2() {
     let weak_hash_ptr = RdiffAddler::new_ptr();
 
     // Create signature file
-    let result = Signature::create_signature_file(
+    Signature::create_signature_file(
         file_name.as_str(),
         signature_file_name.as_str(),
         weak_hash_ptr,
         strong_hash_ptr,
     )
     .unwrap();
-    assert_eq!(result, ());
 
     // Get signature from file
     let computed_signature =
@@ -315,3 +289,188 @@ fn compute_chunk_size(file_size: usize) -> usize {
         1
     }
 }
+
+#[test]
+fn test_rdiff_signature_get_signature_from_file_error_case1() {
+    // A file that is not a signature file
+    let error = Signature::get_signature_from_file("resources/poem.txt").unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_SIGNATURE_FILE));
+}
+
+#[test]
+fn test_rdiff_signature_create_signature_file_with_fastcdc_case1() {
+    // Get file names
+    let file_name = "resources/poem.txt";
+    let signature_file_name = format!(
+        "resources/test_signature_file_with_fastcdc_case1.{}.sig",
+        now_as_millis()
+    );
+    let fastcdc_parameters = FastCdcParameters::new_with_avg_size(64).unwrap();
+
+    // Get signature
+    let expected_signature = Signature::create_signature_with_fastcdc(
+        file_name,
+        fastcdc_parameters,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Verify chunk sizes and chunk lengths
+    let header = expected_signature.get_header();
+    assert_eq!(header.get_fastcdc_parameters(), Some(&fastcdc_parameters));
+    assert_eq!(expected_signature.get_chunk_size(), 64);
+    let chunk_lengths: Vec<usize> = expected_signature
+        .get_rdiff_chunk_table()
+        .chunk_table
+        .values()
+        .flatten()
+        .map(|chunk_digest| chunk_digest.length)
+        .collect();
+    assert!(chunk_lengths.len() > 1);
+    assert_eq!(chunk_lengths.iter().sum::<usize>(), 1056);
+    assert!(chunk_lengths.iter().all(|length| *length <= 4 * 64));
+
+    // Create signature file and get signature from it
+    Signature::create_signature_file_with_fastcdc(
+        file_name,
+        signature_file_name.as_str(),
+        fastcdc_parameters,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let computed_signature =
+        Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    assert_eq!(computed_signature, expected_signature);
+
+    // Clean up verification
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_signature_get_signature_from_file_version_2_case1() {
+    // Version 2 signature files have no chunk lengths
+    let signature_file_name = format!(
+        "resources/test_get_signature_version_2_case1.{}.sig",
+        now_as_millis()
+    );
+    let basis = Some(BasisFingerprint::new(5, vec![1; 20]));
+    let mut chunk_table: HashMap<RdiffChecksum, Vec<(u32, RdiffDigest)>> = HashMap::new();
+    chunk_table.insert(10, vec![(1, vec![2; 20]), (3, vec![3; 20])]);
+    chunk_table.insert(20, vec![(2, vec![4; 20])]);
+    let data = serialize(&(
+        (SIGNATURE_MAGIC, SIGNATURE_VERSION_2),
+        (1_u32, 1_u32, 20_usize, 2_usize),
+        basis,
+        chunk_table,
+        1_usize,
+    ))
+    .unwrap();
+    fs::write(signature_file_name.as_str(), data).unwrap();
+
+    // Get signature from file, chunk lengths come from chunk size and last chunk size
+    let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    assert_eq!(signature.get_last_chunk_size(), 1);
+    let mut chunk_lengths: Vec<(u32, usize)> = signature
+        .get_rdiff_chunk_table()
+        .chunk_table
+        .values()
+        .flatten()
+        .map(|chunk_digest| (chunk_digest.index, chunk_digest.length))
+        .collect();
+    chunk_lengths.sort();
+    assert_eq!(chunk_lengths, vec![(1, 2), (2, 2), (3, 1)]);
+
+    // Clean up verification
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_signature_truncate_digests_case1() {
+    let signature_file_name = format!(
+        "resources/test_signature_truncate_digests_case1.{}.sig",
+        now_as_millis()
+    );
+    let mut signature = Signature::create_signature(
+        "resources/poem.txt",
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Truncate digests
+    signature.truncate_digests(4).unwrap();
+    assert_eq!(signature.get_header().get_digest_size(), 4);
+    assert!(signature
+        .get_rdiff_chunk_table()
+        .chunk_table
+        .values()
+        .flatten()
+        .all(|chunk_digest| chunk_digest.digest.len() == 4));
+
+    // Digests can not be empty nor grow back
+    let expected_error = RollingHashError::new(INVALID_DIGEST_SIZE);
+    assert_eq!(signature.truncate_digests(0), Err(expected_error));
+    let expected_error = RollingHashError::new(INVALID_DIGEST_SIZE);
+    assert_eq!(signature.truncate_digests(5), Err(expected_error));
+
+    // Write signature file and get signature from it
+    Signature::write_signature_file(&signature, signature_file_name.as_str()).unwrap();
+    let computed_signature =
+        Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    assert_eq!(computed_signature, signature);
+
+    // Clean up verification
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+#[test]
+fn test_rdiff_signature_create_signature_from_bytes_case1() {
+    let file_name = "resources/poem.txt";
+    let data = fs::read(file_name).unwrap();
+
+    // Get signatures from file, memory buffer and reader
+    let file_signature =
+        Signature::create_signature(file_name, RdiffAddler::new_ptr(), RdiffSha1::new_ptr())
+            .unwrap();
+    let bytes_signature = Signature::create_signature_from_bytes(
+        data.as_slice(),
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+    let reader_signature = Signature::create_signature_from_reader(
+        Cursor::new(data.clone()),
+        data.len() as u64,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Verify computed values
+    assert_eq!(bytes_signature, file_signature);
+    assert_eq!(reader_signature, file_signature);
+}
+
+#[test]
+fn test_rdiff_signature_write_signature_case1() {
+    let data = fs::read("resources/poem.txt").unwrap();
+    let fastcdc_parameters = FastCdcParameters::new(64, 128, 512).unwrap();
+    let rdiff_file = RdiffFile::new_with_bytes("poem", data.as_slice());
+    let signature = Signature::create_signature_from_rdiff_file_with_fastcdc(
+        rdiff_file,
+        fastcdc_parameters,
+        RdiffAddler::new_ptr(),
+        RdiffSha1::new_ptr(),
+    )
+    .unwrap();
+
+    // Write signature to memory buffer and read it back
+    let mut output: Vec<u8> = Vec::new();
+    signature.write_signature(&mut output).unwrap();
+    let read_signature = Signature::get_signature_from_reader(output.as_slice()).unwrap();
+
+    // Verify computed values
+    assert_eq!(read_signature, signature);
+}
@@ -1,11 +1,22 @@
+#[cfg(test)]
 use std::time::SystemTime;
 
+#[cfg(test)]
 pub(crate) fn now_as_millis() -> u128 {
-    let now = SystemTime::now()
+    SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
-        .as_millis();
-    now
+        .as_millis()
+}
+
+// Change, insert and remove data, so chunks after each edit move
+#[cfg(test)]
+pub(crate) fn get_edited_data(data: &[u8]) -> Vec<u8> {
+    let mut edited_data = data.to_vec();
+    edited_data[100..110].copy_from_slice(b"0123456789");
+    edited_data.splice(500..500, b"inserted".iter().copied());
+    edited_data.drain(900..950);
+    edited_data
 }
 
 #[cfg(test)]
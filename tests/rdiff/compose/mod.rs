@@ -0,0 +1,238 @@
+use std::{fs, path::Path};
+
+use rolling_hash_diff::rdiff::{
+    constants::{CHUNKING_OPTION, COMPRESS_OPTION, FASTCDC_CHUNKING, WITHOUT_BASIS_OPTION},
+    error::{
+        messages::{DELTA_GEOMETRY_UNKNOWN, HELP_USAGE},
+        RollingHashError,
+    },
+    Rdiff,
+};
+
+use crate::rdiff::{util::now_as_millis, COMMAND};
+
+fn create_delta(
+    file_name: &str,
+    new_file_name: &str,
+    delta_file_name: &str,
+    signature_options: &[&str],
+) {
+    // Create signature of file
+    let signature_file_name = format!("{}.sig", delta_file_name);
+    let mut args: Vec<String> = vec![COMMAND.to_string(), "signature".to_string()];
+    args.extend(signature_options.iter().map(|option| option.to_string()));
+    args.extend([file_name.to_string(), signature_file_name.to_string()]);
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create delta from file to new file
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "delta".to_string(),
+        COMPRESS_OPTION.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+fn compose_versions(case: &str, signature_options: &[&str]) {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_compose_{}.{}",
+        case,
+        now_as_millis()
+    );
+    let first_delta_file_name = format!("{}.v2.delta", prefix_file_name);
+    let second_delta_file_name = format!("{}.v3.delta", prefix_file_name);
+    let delta_file_name = format!("{}.delta", prefix_file_name);
+    let patched_file_name = format!("{}.patched.txt", prefix_file_name);
+
+    // Create deltas from first to second and from second to third version
+    create_delta(
+        "resources/poem.txt",
+        "resources/poem.v2.txt",
+        &first_delta_file_name,
+        signature_options,
+    );
+    create_delta(
+        "resources/poem.v2.txt",
+        "resources/poem.v3.txt",
+        &second_delta_file_name,
+        signature_options,
+    );
+
+    // Compose deltas
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "compose".to_string(),
+        "resources/poem.txt".to_string(),
+        first_delta_file_name.to_string(),
+        second_delta_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch first version with composed delta
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "patch".to_string(),
+        "resources/poem.txt".to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, fs::read("resources/poem.v3.txt").unwrap());
+
+    // Clean up verification
+    fs::remove_file(first_delta_file_name).unwrap();
+    fs::remove_file(second_delta_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_compose_case1() {
+    compose_versions("case1", &[]);
+}
+
+#[test]
+pub fn integration_test_rdiff_main_compose_fastcdc_case2() {
+    compose_versions("case2", &[CHUNKING_OPTION, FASTCDC_CHUNKING]);
+}
+
+#[test]
+pub fn integration_test_rdiff_main_compose_without_basis_case3() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_compose_case3.{}",
+        now_as_millis()
+    );
+    let second_file_name = format!("{}.v2.txt", prefix_file_name);
+    let third_file_name = format!("{}.v3.txt", prefix_file_name);
+    let first_delta_file_name = format!("{}.v2.delta", prefix_file_name);
+    let second_delta_file_name = format!("{}.v3.delta", prefix_file_name);
+    let delta_file_name = format!("{}.delta", prefix_file_name);
+    let patched_file_name = format!("{}.patched.txt", prefix_file_name);
+
+    // Create versions by changing data at the beginning, then adding data at the end,
+    // so blocks of every version start at basis blocks
+    let first_data = fs::read("resources/poem.txt").unwrap();
+    let mut second_data = first_data.clone();
+    second_data[0..10].copy_from_slice(b"0123456789");
+    fs::write(second_file_name.as_str(), &second_data).unwrap();
+    let mut third_data = second_data.clone();
+    third_data.extend_from_slice(b"new line\n");
+    fs::write(third_file_name.as_str(), &third_data).unwrap();
+    create_delta(
+        "resources/poem.txt",
+        &second_file_name,
+        &first_delta_file_name,
+        &[],
+    );
+    create_delta(
+        &second_file_name,
+        &third_file_name,
+        &second_delta_file_name,
+        &[],
+    );
+
+    // Compose deltas without basis file
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "compose".to_string(),
+        WITHOUT_BASIS_OPTION.to_string(),
+        first_delta_file_name.to_string(),
+        second_delta_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch first version with composed delta
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "patch".to_string(),
+        "resources/poem.txt".to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, third_data);
+
+    // Clean up verification
+    fs::remove_file(second_file_name).unwrap();
+    fs::remove_file(third_file_name).unwrap();
+    fs::remove_file(first_delta_file_name).unwrap();
+    fs::remove_file(second_delta_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_compose_error_without_basis_fastcdc_case2() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_compose_error_case2.{}",
+        now_as_millis()
+    );
+    let first_delta_file_name = format!("{}.v2.delta", prefix_file_name);
+    let second_delta_file_name = format!("{}.v3.delta", prefix_file_name);
+    let delta_file_name = format!("{}.delta", prefix_file_name);
+
+    // Create deltas of content defined blocks
+    let signature_options = [CHUNKING_OPTION, FASTCDC_CHUNKING];
+    create_delta(
+        "resources/poem.txt",
+        "resources/poem.v2.txt",
+        &first_delta_file_name,
+        &signature_options,
+    );
+    create_delta(
+        "resources/poem.v2.txt",
+        "resources/poem.v3.txt",
+        &second_delta_file_name,
+        &signature_options,
+    );
+
+    // Content defined blocks are only found by reading the basis file
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "compose".to_string(),
+        WITHOUT_BASIS_OPTION.to_string(),
+        first_delta_file_name.to_string(),
+        second_delta_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value
+    assert_eq!(error, RollingHashError::new(DELTA_GEOMETRY_UNKNOWN));
+    assert!(!Path::new(&delta_file_name).exists());
+
+    // Clean up verification
+    fs::remove_file(first_delta_file_name).unwrap();
+    fs::remove_file(second_delta_file_name).unwrap();
+}
+
+#[test]
+fn integration_test_rdiff_main_compose_error_delta_file_missing_case1() {
+    // Execute command without composed delta file name
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "compose".to_string(),
+        "resources/poem.txt".to_string(),
+        "first.delta".to_string(),
+        "second.delta".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value
+    assert_eq!(error, RollingHashError::new(HELP_USAGE));
+}
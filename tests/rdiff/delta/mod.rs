@@ -1,13 +1,28 @@
 use std::{
     fs::{self, File},
     io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    process::{Command, Stdio},
 };
 
-use bincode::deserialize_from;
 use rolling_hash_diff::rdiff::{
-    constants::BLOCK_SIZE,
-    delta::{ChunkDelta, Delta},
-    error::{messages::HELP_USAGE, RollingHashError},
+    constants::{
+        BLOCK_SIZE, CHUNKING_OPTION, COMPRESS_OPTION, FASTCDC_CHUNKING, FORMAT_OPTION,
+        LIBRSYNC_FORMAT, VCDIFF_FORMAT,
+    },
+    delta::{header::DeltaHeader, ChunkDelta, Delta},
+    error::{
+        messages::{
+            COMPRESSION_FORMAT_UNSUPPORTED, FIXED_SIZE_CHUNKS_REQUIRED, HELP_USAGE,
+            INVALID_SIGNATURE_FILE,
+        },
+        RollingHashError,
+    },
+    hash::strong::{
+        rdiff_sha1::{RdiffSha1, RDIFF_SHA1_ID},
+        StrongHash,
+    },
+    signature::header::BasisFingerprint,
     Rdiff,
 };
 
@@ -27,43 +42,36 @@ pub fn integration_test_rdiff_main_delta_equals_files_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create signature
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Create new file version
     // Equals to the old one
     {
-        let old_file = File::open(file_name).unwrap();
+        let old_file = File::open(file_name.as_str()).unwrap();
         let mut reader = BufReader::new(old_file);
         let new_file = File::create(new_file_name.clone()).unwrap();
         let mut writer = BufWriter::new(new_file);
-        let mut buffer: [u8; BLOCK_SIZE as usize] = [0; BLOCK_SIZE as usize];
+        let mut buffer: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
         loop {
             let size = reader.read(&mut buffer).unwrap();
-            writer.write(&buffer[..size]).unwrap();
+            writer.write_all(&buffer[..size]).unwrap();
             if size == 0 {
                 break;
             }
@@ -72,26 +80,35 @@ pub fn integration_test_rdiff_main_delta_equals_files_case1() {
 
     // Create delta
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
-    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta::new(chunk_delta_list);
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::CopyRange {
+        start_index: 1,
+        count: 3,
+    }];
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta::new(header, chunk_delta_list);
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -122,31 +139,24 @@ pub fn integration_test_rdiff_main_delta_chunk_removed_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create signature
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Create new file version
     // By removing second chunk
@@ -154,40 +164,42 @@ pub fn integration_test_rdiff_main_delta_chunk_removed_case2() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create delta
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
-    let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta::new(chunk_delta_list);
+    let chunk_delta_list: Vec<ChunkDelta> = vec![ChunkDelta::Match(1), ChunkDelta::Match(3)];
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta::new(header, chunk_delta_list);
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -218,31 +230,24 @@ pub fn integration_test_rdiff_main_delta_chunk_changed_case3() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create signature
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Create new file version
     // By changing first chunk
@@ -250,48 +255,50 @@ pub fn integration_test_rdiff_main_delta_chunk_changed_case3() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'd'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create delta
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..BLOCK_SIZE {
-        differences.push(b'd');
-    }
+    differences.extend_from_slice(&[b'd'; BLOCK_SIZE]);
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta::new(chunk_delta_list);
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 2,
+        count: 2,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta::new(header, chunk_delta_list);
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -322,31 +329,24 @@ pub fn integration_test_rdiff_main_delta_chunk_shifted_case4() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create signature
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Create new file version
     // By shifted first chunk
@@ -355,52 +355,51 @@ pub fn integration_test_rdiff_main_delta_chunk_shifted_case4() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..shifted_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend(vec![b'd'; shifted_size]);
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create delta
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..shifted_size {
-        differences.push(b'd');
-    }
+    differences.extend(vec![b'd'; shifted_size]);
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(1));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta::new(chunk_delta_list);
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 1,
+        count: 3,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta::new(header, chunk_delta_list);
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -431,31 +430,24 @@ pub fn integration_test_rdiff_main_delta_addition_between_chunks_case5() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create signature
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Create new file version
     // By addition between chunks
@@ -464,52 +456,52 @@ pub fn integration_test_rdiff_main_delta_addition_between_chunks_case5() {
         let file = File::create(new_file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..addition_size {
-            input_data.push(b'd');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend(vec![b'd'; addition_size]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Create delta
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
-    assert_eq!(rdiff_main_result, ());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
 
     // Get computed Delta from file
-    let delta_file = File::open(delta_file_name.as_str()).unwrap();
-    let reader = BufReader::new(delta_file);
-    let delta: Delta = deserialize_from(reader).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
 
     // Set expected values
     let mut chunk_delta_list: Vec<ChunkDelta> = Vec::new();
     let mut differences: Vec<u8> = Vec::new();
-    for _ in 0..addition_size {
-        differences.push(b'd');
-    }
+    differences.extend(vec![b'd'; addition_size]);
     chunk_delta_list.push(ChunkDelta::Match(1));
     chunk_delta_list.push(ChunkDelta::Diff(differences));
-    chunk_delta_list.push(ChunkDelta::Match(2));
-    chunk_delta_list.push(ChunkDelta::Match(3));
-    let expected_delta = Delta::new(chunk_delta_list);
+    chunk_delta_list.push(ChunkDelta::CopyRange {
+        start_index: 2,
+        count: 2,
+    });
+    let file_data = fs::read(file_name.as_str()).unwrap();
+    let basis = BasisFingerprint::new(file_data.len(), RdiffSha1::new().digest(&file_data));
+    let new_file_data = fs::read(new_file_name.as_str()).unwrap();
+    let header = DeltaHeader::new(
+        BLOCK_SIZE,
+        BLOCK_SIZE - 1,
+        new_file_data.len(),
+        RDIFF_SHA1_ID,
+        RdiffSha1::new().digest(&new_file_data),
+        Some(basis),
+    );
+    let expected_delta = Delta::new(header, chunk_delta_list);
 
     // Verify computed values
     assert_eq!(delta, expected_delta);
@@ -526,6 +518,76 @@ pub fn integration_test_rdiff_main_delta_addition_between_chunks_case5() {
     fs::remove_file(delta_file_name).unwrap();
 }
 
+#[test]
+pub fn integration_test_rdiff_main_delta_stdin_case6() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_main_delta_file_case6.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let expected_delta_file_name = format!("{}.v1.txt.expected.delta", prefix_file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+
+    // Create old file and new file version
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+    let mut new_data = old_data.clone();
+    new_data[200..210].copy_from_slice(b"0123456789");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create delta of new file version piped to standard input
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rolling_hash_diff"))
+        .args([
+            "delta",
+            signature_file_name.as_str(),
+            "-",
+            delta_file_name.as_str(),
+        ])
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(new_data.as_slice())
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+
+    // Create delta of new file version
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        expected_delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed values
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
+    let expected_delta = Delta::get_delta_from_file(expected_delta_file_name.as_str()).unwrap();
+    assert_eq!(delta, expected_delta);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(expected_delta_file_name).unwrap();
+}
+
 #[test]
 fn integration_test_rdiff_main_delta_error_no_option_case1() {
     // Get file names
@@ -537,12 +599,13 @@ fn integration_test_rdiff_main_delta_error_no_option_case1() {
 
     // Execute command
     //let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    //args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        //option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
     // Set expected value
     let expected_error = RollingHashError::new(HELP_USAGE);
@@ -561,12 +624,13 @@ fn integration_test_rdiff_main_delta_error_file_name_missing_case2() {
 
     // Execute command
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    //args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        //signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
     // Set expected value
     let expected_error = RollingHashError::new(HELP_USAGE);
@@ -585,15 +649,152 @@ fn integration_test_rdiff_main_delta_error_file_missing_case3() {
 
     // Execute command
     let option = "delta";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(signature_file_name.to_string());
-    args.push(new_file_name.to_string());
-    args.push(delta_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
     // Set expected value
     let expected_error = RollingHashError::from(Box::new(io::Error::from_raw_os_error(2)));
     // Verify computed value
     assert_eq!(error, expected_error);
 }
+
+#[test]
+fn integration_test_rdiff_main_delta_error_invalid_signature_file_case4() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_main_delta_error_case4.{}", now_as_millis());
+    // Use a file that is not a signature file
+    let signature_file_name = "resources/poem.txt";
+    let new_file_name = "resources/poem.v2.txt";
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Execute command
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    // Set expected value
+    let expected_error = RollingHashError::new(INVALID_SIGNATURE_FILE);
+    // Verify computed value
+    assert_eq!(error, expected_error);
+}
+
+#[test]
+fn integration_test_rdiff_main_delta_error_compress_format_case5() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_main_delta_error_case5.{}", now_as_millis());
+    let signature_file_name = format!("{}.sig", prefix_file_name);
+    let new_file_name = "resources/poem.v2.txt";
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Execute command, only rdiff deltas have compressed differences
+    let option = "delta";
+    for format in [LIBRSYNC_FORMAT, VCDIFF_FORMAT] {
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            COMPRESS_OPTION.to_string(),
+            FORMAT_OPTION.to_string(),
+            format.to_string(),
+            signature_file_name.to_string(),
+            new_file_name.to_string(),
+            delta_file_name.to_string(),
+        ];
+        let error = Rdiff::main_rdiff(args).unwrap_err();
+
+        // Verify computed value, delta file is not created
+        assert_eq!(error, RollingHashError::new(COMPRESSION_FORMAT_UNSUPPORTED));
+        assert!(!Path::new(delta_file_name.as_str()).exists());
+    }
+}
+
+#[test]
+fn integration_test_rdiff_main_delta_error_librsync_fastcdc_case6() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_main_delta_error_case6.{}", now_as_millis());
+    let file_name = "resources/poem.txt";
+    let signature_file_name = format!("{}.sig", prefix_file_name);
+    let new_file_name = "resources/poem.v2.txt";
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Create signature file of content defined chunks
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "signature".to_string(),
+        CHUNKING_OPTION.to_string(),
+        FASTCDC_CHUNKING.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Execute command, librsync deltas only copy fixed size chunks
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value, delta file is removed
+    assert_eq!(error, RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+    assert!(!Path::new(delta_file_name.as_str()).exists());
+
+    // Remove test files
+    fs::remove_file(signature_file_name).unwrap();
+}
+
+#[test]
+fn integration_test_rdiff_main_delta_error_vcdiff_fastcdc_case7() {
+    // Get file names
+    let prefix_file_name = format!("resources/test_main_delta_error_case7.{}", now_as_millis());
+    let file_name = "resources/poem.txt";
+    let signature_file_name = format!("{}.sig", prefix_file_name);
+    let new_file_name = "resources/poem.v2.txt";
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Create signature file of content defined chunks
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        "signature".to_string(),
+        CHUNKING_OPTION.to_string(),
+        FASTCDC_CHUNKING.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Execute command, VCDIFF deltas only copy fixed size chunks
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        VCDIFF_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value, delta file is removed
+    assert_eq!(error, RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+    assert!(!Path::new(delta_file_name.as_str()).exists());
+
+    // Remove test files
+    fs::remove_file(signature_file_name).unwrap();
+}
@@ -5,3 +5,7 @@ mod util;
 mod signature;
 
 mod delta;
+
+mod patch;
+
+mod compose;
@@ -0,0 +1,1102 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use rolling_hash_diff::rdiff::{
+    constants::{
+        BLAKE3_HASH, BLOCK_SIZE, BLOCK_SIZE_OPTION, CHUNKING_OPTION, COMPRESS_OPTION,
+        FASTCDC_CHUNKING, FORMAT_OPTION, IN_PLACE_OPTION, JOURNAL_FILE_SUFFIX, JOURNAL_MAGIC,
+        JOURNAL_VERSION, LIBRSYNC_BLAKE2_SIG_MAGIC, LIBRSYNC_DELTA_MAGIC, LIBRSYNC_FORMAT,
+        LIBRSYNC_RK_BLAKE2_SIG_MAGIC, RABINKARP_HASH, RDIFF_FORMAT, ROLLBACK_OPTION, ROLLSUM_HASH,
+        STRONG_HASH_OPTION, STRONG_LEN_OPTION, VCDIFF_FORMAT, VCDIFF_MAGIC, WEAK_HASH_OPTION,
+    },
+    delta::Delta,
+    error::{
+        messages::{COMPRESSION_FORMAT_UNSUPPORTED, HELP_USAGE, IN_PLACE_ROLLBACK_UNAVAILABLE},
+        RollingHashError,
+    },
+    hash::{
+        registry::register_strong_hash,
+        strong::{
+            rdiff_blake3::{RdiffBlake3, RDIFF_BLAKE3_ID},
+            StrongHash, StrongHashPtr, StrongHasherPtr,
+        },
+        weak::{rdiff_rabinkarp::RDIFF_RABINKARP_ID, rdiff_rollsum::RDIFF_ROLLSUM_ID},
+    },
+    signature::Signature,
+    Rdiff,
+};
+
+use crate::rdiff::{util::now_as_millis, COMMAND};
+
+// Application strong hash, BLAKE3 digests truncated to 16 bytes
+const TRUNCATED_BLAKE3_ID: u32 = 2001;
+
+struct TruncatedBlake3;
+
+impl StrongHash for TruncatedBlake3 {
+    fn get_id(&self) -> u32 {
+        TRUNCATED_BLAKE3_ID
+    }
+
+    fn get_digest_size(&self) -> usize {
+        16
+    }
+
+    fn digest(&self, chunk: &[u8]) -> Vec<u8> {
+        RdiffBlake3::new().digest(chunk)[..16].to_vec()
+    }
+
+    fn hasher(&self) -> StrongHasherPtr {
+        RdiffBlake3::new().hasher()
+    }
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_chunk_changed_case1() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case1.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    {
+        let file = File::create(file_name.as_str()).unwrap();
+        let mut writer = BufWriter::new(file);
+        let mut input_data: Vec<u8> = Vec::new();
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+        writer.write_all(&input_data).unwrap();
+    }
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By changing second chunk
+    let mut new_data: Vec<u8> = Vec::new();
+    new_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'd'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
+    {
+        let file = File::create(new_file_name.as_str()).unwrap();
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&new_data).unwrap();
+    }
+
+    // Create delta
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+fn integration_test_rdiff_main_patch_error_output_file_missing_case1() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_patch_error_case1.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Execute command
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        //patched_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Set expected value
+    let expected_error = RollingHashError::new(HELP_USAGE);
+    // Verify computed value
+    assert_eq!(error, expected_error);
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_block_size_case2() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case2.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+    let block_size = 4 * BLOCK_SIZE;
+
+    // Create old file
+    let mut old_data: Vec<u8> = Vec::new();
+    old_data.extend_from_slice(&[b'a'; 4 * BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'b'; 4 * BLOCK_SIZE]);
+    old_data.extend_from_slice(&[b'c'; 3 * BLOCK_SIZE]);
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature with block size
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        "--block-size".to_string(),
+        block_size.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By removing first block
+    let new_data = old_data[block_size..].to_vec();
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file with same block size
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        "--block-size".to_string(),
+        block_size.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_compressed_delta_case3() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case3.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let compressed_delta_file_name = format!("{}.v1.txt.compressed.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By adding repeated text at the beginning
+    let mut new_data: Vec<u8> = b"new text\n".repeat(100);
+    new_data.extend_from_slice(&old_data);
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta and compressed delta
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        COMPRESS_OPTION.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        compressed_delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        compressed_delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+    let delta_size = fs::metadata(delta_file_name.as_str()).unwrap().len();
+    let compressed_delta_size = fs::metadata(compressed_delta_file_name.as_str())
+        .unwrap()
+        .len();
+    assert!(compressed_delta_size < delta_size);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(compressed_delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_librsync_signature_case4() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case4.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature in librsync format
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        BLOCK_SIZE_OPTION.to_string(),
+        "16".to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let signature_data = fs::read(signature_file_name.as_str()).unwrap();
+    assert_eq!(signature_data[..4], LIBRSYNC_BLAKE2_SIG_MAGIC.to_be_bytes());
+
+    // Create new file version
+    // By changing data in the middle and at the end
+    let mut new_data = old_data.clone();
+    new_data[100..110].copy_from_slice(b"0123456789");
+    new_data.extend_from_slice(b"new line\n");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta, hash functions are found from the signature magic number
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_librsync_delta_case5() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case5.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By changing data at the beginning and at the end
+    let mut new_data = old_data.clone();
+    new_data[0..10].copy_from_slice(b"0123456789");
+    new_data.extend_from_slice(b"new line\n");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta in librsync format, it can not be compressed
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        COMPRESS_OPTION.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(COMPRESSION_FORMAT_UNSUPPORTED));
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let delta_data = fs::read(delta_file_name.as_str()).unwrap();
+    assert_eq!(delta_data[..4], LIBRSYNC_DELTA_MAGIC.to_be_bytes());
+
+    // Patch old file, block size is computed from the old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_vcdiff_delta_case6() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case6.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By changing data at the beginning and at the end
+    let mut new_data = old_data.clone();
+    new_data[0..10].copy_from_slice(b"0123456789");
+    new_data.extend_from_slice(b"new line\n");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta in VCDIFF format, it can not be compressed
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        COMPRESS_OPTION.to_string(),
+        FORMAT_OPTION.to_string(),
+        VCDIFF_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(COMPRESSION_FORMAT_UNSUPPORTED));
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        VCDIFF_FORMAT.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let delta_data = fs::read(delta_file_name.as_str()).unwrap();
+    assert_eq!(delta_data[..4], VCDIFF_MAGIC);
+
+    // Patch old file, block size is computed from the old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Create a VCDIFF delta copying bcd of old file abcdef and adding x,
+    // as other tools write copies not aligned to block size
+    let unaligned_file_name = format!("{}.unaligned.txt", prefix_file_name);
+    fs::write(unaligned_file_name.as_str(), b"abcdef").unwrap();
+    let mut delta_data: Vec<u8> = VCDIFF_MAGIC.to_vec();
+    delta_data.extend_from_slice(&[0x00, 0x01, 0x03, 0x01, 0x0A]);
+    delta_data.extend_from_slice(&[0x04, 0x00, 0x01, 0x03, 0x01]);
+    delta_data.extend_from_slice(&[b'x', 0x13, 0x03, 0x02, 0x00]);
+    fs::write(delta_file_name.as_str(), &delta_data).unwrap();
+
+    // Patch it, parts of blocks are read from the old file
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        BLOCK_SIZE_OPTION.to_string(),
+        "2".to_string(),
+        unaligned_file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, b"bcdx");
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(unaligned_file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_fastcdc_case7() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case7.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature with content defined chunks, block size is the average chunk size
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        CHUNKING_OPTION.to_string(),
+        FASTCDC_CHUNKING.to_string(),
+        BLOCK_SIZE_OPTION.to_string(),
+        "64".to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By inserting data in the middle
+    let mut new_data = old_data.clone();
+    new_data.splice(500..500, b"new words ".iter().copied());
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_blake3_case8() {
+    // Get file names
+    let prefix_file_name = format!("resources/integration_test_patch_case8.{}", now_as_millis());
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature with BLAKE3 strong hash
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        STRONG_HASH_OPTION.to_string(),
+        BLAKE3_HASH.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    assert_eq!(signature.get_header().get_strong_hash_id(), RDIFF_BLAKE3_ID);
+
+    // Create new file version
+    // By changing data in the middle
+    let mut new_data = old_data.clone();
+    new_data[300..310].copy_from_slice(b"0123456789");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta, strong hash is the one recorded in the signature
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let delta = Delta::get_delta_from_file(delta_file_name.as_str()).unwrap();
+    assert_eq!(
+        delta.get_header().get_strong_hash_id(),
+        Some(RDIFF_BLAKE3_ID)
+    );
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_strong_len_case9() {
+    // Patch with truncated digests in both signature formats
+    for (case, format) in [(1, RDIFF_FORMAT), (2, LIBRSYNC_FORMAT)] {
+        // Get file names
+        let prefix_file_name = format!(
+            "resources/integration_test_patch_case9.{}.{}",
+            case,
+            now_as_millis()
+        );
+        let file_name = format!("{}.txt", prefix_file_name);
+        let signature_file_name = format!("{}.sig", file_name);
+        let new_file_name = format!("{}.v1.txt", prefix_file_name);
+        let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+        let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+        // Create old file
+        let old_data = fs::read("resources/poem.txt").unwrap();
+        fs::write(file_name.as_str(), &old_data).unwrap();
+
+        // Create signature with 8 bytes digests
+        let option = "signature";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            FORMAT_OPTION.to_string(),
+            format.to_string(),
+            STRONG_LEN_OPTION.to_string(),
+            "8".to_string(),
+            BLOCK_SIZE_OPTION.to_string(),
+            "16".to_string(),
+            file_name.to_string(),
+            signature_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+        let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+        assert_eq!(signature.get_header().get_digest_size(), 8);
+
+        // Create new file version
+        // By changing data in the middle
+        let mut new_data = old_data.clone();
+        new_data[300..310].copy_from_slice(b"0123456789");
+        fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+        // Create delta
+        let option = "delta";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            signature_file_name.to_string(),
+            new_file_name.to_string(),
+            delta_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+
+        // Patch old file
+        let option = "patch";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            file_name.to_string(),
+            delta_file_name.to_string(),
+            patched_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+
+        // Verify computed value
+        let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+        assert_eq!(patched_data, new_data);
+
+        // Clean up verification
+        fs::remove_file(file_name).unwrap();
+        fs::remove_file(signature_file_name).unwrap();
+        fs::remove_file(new_file_name).unwrap();
+        fs::remove_file(delta_file_name).unwrap();
+        fs::remove_file(patched_file_name).unwrap();
+    }
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_weak_hash_case10() {
+    // Patch with rollsum and Rabin-Karp weak hashes in both signature formats
+    let cases = [
+        (1, RDIFF_FORMAT, ROLLSUM_HASH, RDIFF_ROLLSUM_ID),
+        (2, RDIFF_FORMAT, RABINKARP_HASH, RDIFF_RABINKARP_ID),
+        (3, LIBRSYNC_FORMAT, RABINKARP_HASH, RDIFF_RABINKARP_ID),
+    ];
+    for (case, format, weak_hash, weak_hash_id) in cases {
+        // Get file names
+        let prefix_file_name = format!(
+            "resources/integration_test_patch_case10.{}.{}",
+            case,
+            now_as_millis()
+        );
+        let file_name = format!("{}.txt", prefix_file_name);
+        let signature_file_name = format!("{}.sig", file_name);
+        let new_file_name = format!("{}.v1.txt", prefix_file_name);
+        let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+        let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+        // Create old file
+        let old_data = fs::read("resources/poem.txt").unwrap();
+        fs::write(file_name.as_str(), &old_data).unwrap();
+
+        // Create signature with the weak hash
+        let option = "signature";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            FORMAT_OPTION.to_string(),
+            format.to_string(),
+            WEAK_HASH_OPTION.to_string(),
+            weak_hash.to_string(),
+            BLOCK_SIZE_OPTION.to_string(),
+            "16".to_string(),
+            file_name.to_string(),
+            signature_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+        let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+        assert_eq!(signature.get_header().get_weak_hash_id(), weak_hash_id);
+        if format == LIBRSYNC_FORMAT {
+            let signature_data = fs::read(signature_file_name.as_str()).unwrap();
+            assert_eq!(
+                signature_data[..4],
+                LIBRSYNC_RK_BLAKE2_SIG_MAGIC.to_be_bytes()
+            );
+        }
+
+        // Create new file version
+        // By inserting data in the middle, so chunks are found by rolling the weak hash
+        let mut new_data = old_data.clone();
+        new_data.splice(300..300, b"new words ".iter().copied());
+        fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+        // Create delta
+        let option = "delta";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            signature_file_name.to_string(),
+            new_file_name.to_string(),
+            delta_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+
+        // Patch old file
+        let option = "patch";
+        let args: Vec<String> = vec![
+            COMMAND.to_string(),
+            option.to_string(),
+            file_name.to_string(),
+            delta_file_name.to_string(),
+            patched_file_name.to_string(),
+        ];
+        Rdiff::main_rdiff(args).unwrap();
+
+        // Verify computed value
+        let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+        assert_eq!(patched_data, new_data);
+
+        // Clean up verification
+        fs::remove_file(file_name).unwrap();
+        fs::remove_file(signature_file_name).unwrap();
+        fs::remove_file(new_file_name).unwrap();
+        fs::remove_file(delta_file_name).unwrap();
+        fs::remove_file(patched_file_name).unwrap();
+    }
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_registered_hash_case11() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_patch_case11.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+    let patched_file_name = format!("{}.v1.patched.txt", prefix_file_name);
+
+    // Register application strong hash
+    register_strong_hash("truncated-blake3", || -> StrongHashPtr {
+        Box::new(TruncatedBlake3)
+    })
+    .unwrap();
+
+    // Create old file
+    let old_data = fs::read("resources/poem.txt").unwrap();
+    fs::write(file_name.as_str(), &old_data).unwrap();
+
+    // Create signature with the application strong hash
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        WEAK_HASH_OPTION.to_string(),
+        RABINKARP_HASH.to_string(),
+        STRONG_HASH_OPTION.to_string(),
+        "truncated-blake3".to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+    let signature = Signature::get_signature_from_file(signature_file_name.as_str()).unwrap();
+    assert_eq!(
+        signature.get_header().get_strong_hash_id(),
+        TRUNCATED_BLAKE3_ID
+    );
+
+    // Create new file version
+    // By changing data in the middle
+    let mut new_data = old_data.clone();
+    new_data[300..310].copy_from_slice(b"0123456789");
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta, strong hash is found from the id recorded in the signature
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+        patched_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(patched_file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+    fs::remove_file(patched_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_in_place_case12() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_patch_case12.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let signature_file_name = format!("{}.sig", file_name);
+    let new_file_name = format!("{}.v1.txt", prefix_file_name);
+    let delta_file_name = format!("{}.v1.txt.delta", prefix_file_name);
+
+    // Create old file
+    let mut input_data: Vec<u8> = Vec::new();
+    input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+    input_data.extend_from_slice(&[b'c'; BLOCK_SIZE]);
+    input_data.extend_from_slice(&[b'd'; BLOCK_SIZE - 1]);
+    fs::write(file_name.as_str(), &input_data).unwrap();
+
+    // Create signature
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Create new file version
+    // By inserting data and swapping second and third chunks
+    let mut new_data: Vec<u8> = b"inserted".to_vec();
+    new_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'c'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+    new_data.extend_from_slice(&[b'd'; BLOCK_SIZE - 1]);
+    fs::write(new_file_name.as_str(), &new_data).unwrap();
+
+    // Create delta
+    let option = "delta";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        COMPRESS_OPTION.to_string(),
+        signature_file_name.to_string(),
+        new_file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Patch old file in place
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        IN_PLACE_OPTION.to_string(),
+        file_name.to_string(),
+        delta_file_name.to_string(),
+    ];
+    Rdiff::main_rdiff(args).unwrap();
+
+    // Verify computed value
+    let patched_data = fs::read(file_name.as_str()).unwrap();
+    assert_eq!(patched_data, new_data);
+    assert!(!Path::new(&format!("{}{}", file_name, JOURNAL_FILE_SUFFIX)).exists());
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(signature_file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(delta_file_name).unwrap();
+}
+
+#[test]
+pub fn integration_test_rdiff_main_patch_in_place_rollback_case13() {
+    // Get file names
+    let prefix_file_name = format!(
+        "resources/integration_test_patch_case13.{}",
+        now_as_millis()
+    );
+    let file_name = format!("{}.txt", prefix_file_name);
+    let journal_file_name = format!("{}{}", file_name, JOURNAL_FILE_SUFFIX);
+
+    // Create old file
+    fs::write(file_name.as_str(), b"old data").unwrap();
+
+    // Roll back in place patch, there is no journal of an interrupted patch
+    let option = "patch";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        IN_PLACE_OPTION.to_string(),
+        ROLLBACK_OPTION.to_string(),
+        file_name.to_string(),
+    ];
+    assert!(Rdiff::main_rdiff(args).is_err());
+
+    // Journal of a patch interrupted after basis file data was overwritten
+    // is kept, the patch can only be resumed
+    let mut journal_data: Vec<u8> = Vec::new();
+    journal_data.extend_from_slice(&JOURNAL_MAGIC.to_le_bytes());
+    journal_data.extend_from_slice(&JOURNAL_VERSION.to_le_bytes());
+    journal_data.extend_from_slice(&1u64.to_le_bytes());
+    journal_data.extend_from_slice(&[0; 3 * 8]);
+    fs::write(journal_file_name.as_str(), &journal_data).unwrap();
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        IN_PLACE_OPTION.to_string(),
+        ROLLBACK_OPTION.to_string(),
+        file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args.clone()).unwrap_err();
+    assert_eq!(error, RollingHashError::new(IN_PLACE_ROLLBACK_UNAVAILABLE));
+    assert!(Path::new(journal_file_name.as_str()).exists());
+
+    // Journal of a patch interrupted before basis file data was overwritten
+    // is removed
+    journal_data[8..16].copy_from_slice(&0u64.to_le_bytes());
+    fs::write(journal_file_name.as_str(), &journal_data).unwrap();
+    Rdiff::main_rdiff(args).unwrap();
+    assert!(!Path::new(journal_file_name.as_str()).exists());
+
+    // Rollback only applies to in place patches
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        ROLLBACK_OPTION.to_string(),
+        file_name.to_string(),
+        "delta".to_string(),
+        "output".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(HELP_USAGE));
+    assert_eq!(fs::read(file_name.as_str()).unwrap(), b"old data");
+
+    // Clean up verification
+    fs::remove_file(file_name).unwrap();
+}
@@ -1,11 +1,21 @@
 use std::{
     fs::{self, File},
     io::{self, BufWriter, Write},
+    path::Path,
 };
 
 use rolling_hash_diff::rdiff::{
-    constants::BLOCK_SIZE,
-    error::{messages::HELP_USAGE, RollingHashError},
+    constants::{
+        ADLER32_HASH, BLAKE3_HASH, BLOCK_SIZE, CHUNKING_OPTION, FASTCDC_CHUNKING, FORMAT_OPTION,
+        LIBRSYNC_FORMAT, STRONG_HASH_OPTION, STRONG_LEN_OPTION, WEAK_HASH_OPTION,
+    },
+    error::{
+        messages::{
+            FIXED_SIZE_CHUNKS_REQUIRED, HELP_USAGE, INVALID_DIGEST_SIZE, INVALID_OPTION_VALUE,
+            UNKNOWN_STRONG_HASH, UNKNOWN_WEAK_HASH, UNSUPPORTED_LIBRSYNC_HASH,
+        },
+        RollingHashError,
+    },
     Rdiff,
 };
 
@@ -22,33 +32,27 @@ pub fn integration_test_rdiff_main_signature_case1() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'b');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'b'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Execute command
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    let rdiff_main_result = Rdiff::main_rdiff(args);
 
     // Verify computed value
-    assert_eq!(rdiff_main_result, ());
+    assert!(rdiff_main_result.is_ok());
 
     // Clean up verification
     fs::remove_file(file_name).unwrap();
@@ -66,30 +70,26 @@ pub fn integration_test_rdiff_main_signature_case2() {
         let file = File::create(file_name.as_str()).unwrap();
         let mut writer = BufWriter::new(file);
         let mut input_data: Vec<u8> = Vec::new();
-        for _ in 0..BLOCK_SIZE {
-            input_data.push(b'a');
-        }
-        for _ in 0..BLOCK_SIZE - 1 {
-            input_data.push(b'c');
-        }
+        input_data.extend_from_slice(&[b'a'; BLOCK_SIZE]);
+        input_data.extend_from_slice(&[b'c'; BLOCK_SIZE - 1]);
         let chunks = input_data.chunks(BLOCK_SIZE);
         chunks.for_each(|x| {
-            writer.write(x).unwrap();
-            ()
+            writer.write_all(x).unwrap();
         });
     }
 
     // Execute command
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
-    let rdiff_main_result = Rdiff::main_rdiff(args).unwrap();
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    let rdiff_main_result = Rdiff::main_rdiff(args);
 
     // Verify computed value
-    assert_eq!(rdiff_main_result, ());
+    assert!(rdiff_main_result.is_ok());
 
     // Clean up verification
     fs::remove_file(file_name).unwrap();
@@ -107,11 +107,12 @@ fn integration_test_rdiff_main_signature_error_no_option_case1() {
 
     // Execute command
     //let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    //args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        //option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
 
     // Set expected value
@@ -131,11 +132,12 @@ fn integration_test_rdiff_main_signature_error_file_name_missing_case2() {
 
     // Execute command
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    //args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        //file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
     // Set expected value
     let expected_error = RollingHashError::new(HELP_USAGE);
@@ -154,14 +156,175 @@ fn integration_test_rdiff_main_signature_error_file_missing_case3() {
 
     // Execute command
     let option = "signature";
-    let mut args: Vec<String> = Vec::new();
-    args.push(COMMAND.to_string());
-    args.push(option.to_string());
-    args.push(file_name.to_string());
-    args.push(signature_file_name.to_string());
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
     let error = Rdiff::main_rdiff(args).unwrap_err();
     // Set expected value
     let expected_error = RollingHashError::from(Box::new(io::Error::from_raw_os_error(2)));
     // Verify computed value
     assert_eq!(error, expected_error);
 }
+
+#[test]
+fn integration_test_rdiff_main_signature_error_unknown_format_case4() {
+    // Execute command
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        "unknown".to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value
+    assert_eq!(error, RollingHashError::new(INVALID_OPTION_VALUE));
+}
+
+#[test]
+fn integration_test_rdiff_main_signature_error_unknown_chunking_case5() {
+    // Execute command
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        CHUNKING_OPTION.to_string(),
+        "unknown".to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value
+    assert_eq!(error, RollingHashError::new(INVALID_OPTION_VALUE));
+}
+
+#[test]
+fn integration_test_rdiff_main_signature_error_librsync_fastcdc_case6() {
+    // Execute command
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        CHUNKING_OPTION.to_string(),
+        FASTCDC_CHUNKING.to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value
+    assert_eq!(error, RollingHashError::new(FIXED_SIZE_CHUNKS_REQUIRED));
+}
+
+#[test]
+fn integration_test_rdiff_main_signature_error_unknown_strong_hash_case7() {
+    // Execute command
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        STRONG_HASH_OPTION.to_string(),
+        "unknown".to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err().to_string();
+
+    // Verify computed value, registered names are listed
+    assert!(error.starts_with(UNKNOWN_STRONG_HASH));
+    assert!(error.contains(BLAKE3_HASH));
+
+    // librsync signatures do not have BLAKE3 strong sums
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        STRONG_HASH_OPTION.to_string(),
+        BLAKE3_HASH.to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_LIBRSYNC_HASH));
+}
+
+#[test]
+fn integration_test_rdiff_main_signature_error_strong_len_case8() {
+    // Get file names
+    let file_name = "resources/poem.txt";
+    let signature_file_name = format!(
+        "resources/test_signature_error_case8.{}.sig",
+        now_as_millis()
+    );
+
+    // Execute command with a strong digest length longer than SHA-1 digests
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        STRONG_LEN_OPTION.to_string(),
+        "21".to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+
+    // Verify computed value, signature file is not created
+    assert_eq!(error, RollingHashError::new(INVALID_DIGEST_SIZE));
+    assert!(!Path::new(signature_file_name.as_str()).exists());
+
+    // Strong digest length is a number
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        STRONG_LEN_OPTION.to_string(),
+        "eight".to_string(),
+        file_name.to_string(),
+        signature_file_name.to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(INVALID_OPTION_VALUE));
+}
+
+#[test]
+fn integration_test_rdiff_main_signature_error_unknown_weak_hash_case9() {
+    // Execute command
+    let option = "signature";
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        WEAK_HASH_OPTION.to_string(),
+        "unknown".to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err().to_string();
+
+    // Verify computed value, registered names are listed
+    assert!(error.starts_with(UNKNOWN_WEAK_HASH));
+    assert!(error.contains(ADLER32_HASH));
+
+    // librsync signatures do not have Adler-32 weak sums
+    let args: Vec<String> = vec![
+        COMMAND.to_string(),
+        option.to_string(),
+        FORMAT_OPTION.to_string(),
+        LIBRSYNC_FORMAT.to_string(),
+        WEAK_HASH_OPTION.to_string(),
+        ADLER32_HASH.to_string(),
+        "resources/poem.txt".to_string(),
+        "resources/poem.txt.sig".to_string(),
+    ];
+    let error = Rdiff::main_rdiff(args).unwrap_err();
+    assert_eq!(error, RollingHashError::new(UNSUPPORTED_LIBRSYNC_HASH));
+}